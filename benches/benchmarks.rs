@@ -77,6 +77,18 @@ fn criterion_benchmark_poseidon2_statevec(c: &mut Criterion) {
     });
 }
 
+fn criterion_benchmark_poseidon2_folded_constants(c: &mut Criterion) {
+    use boojum::implementations::poseidon2::state_generic_impl::State as GenericState;
+
+    let state = GenericState::from_field_array([GoldilocksField::ONE; 12]);
+    c.bench_function("Poseidon2 generic impl", |b| {
+        b.iter(|| GenericState::poseidon2_permutation(&mut black_box(state)))
+    });
+    c.bench_function("Poseidon2 generic impl with folded constants", |b| {
+        b.iter(|| GenericState::poseidon2_permutation_folded(&mut black_box(state)))
+    });
+}
+
 // fn criterion_benchmark_poseidon2_statevecd(c: &mut Criterion) {
 //     let state = StateVecD::from_array([GoldilocksField::ONE; 12]);
 //     c.bench_function("Poseidon2 StateVecD", |b| {
@@ -457,6 +469,245 @@ fn criterion_benchmark_mul_vectors_mixedgl(c: &mut Criterion) {
     });
 }
 
+fn criterion_benchmark_mul_constant_mixedgl(c: &mut Criterion) {
+    let degree: usize = 1 << 20;
+
+    let aa: Vec<GoldilocksField> = (0..degree)
+        .map(|x| x as u64 + 1)
+        .map(GoldilocksField::from_u64_with_reduction)
+        .collect();
+    let constant = GoldilocksField::from_u64_with_reduction(0x1234567890abcdef);
+
+    let mut aa: Vec<MixedGL> =
+        MixedGL::vec_from_base_vec(clone_respecting_allignment::<GoldilocksField, MixedGL, _>(&aa));
+    let mut bb: Vec<GoldilocksField> = MixedGL::vec_into_base_vec(aa.clone());
+
+    c.bench_function("MixedGL Vec mul by constant", |b| {
+        b.iter(|| {
+            for a in black_box(&mut aa).iter_mut() {
+                a.mul_constant_assign(black_box(&constant));
+            }
+        })
+    });
+
+    c.bench_function("Goldilocks Vec mul by constant", |b| {
+        b.iter(|| {
+            for a in black_box(&mut bb).iter_mut() {
+                GoldilocksField::mul_assign(a, black_box(&constant));
+            }
+        })
+    });
+}
+
+fn criterion_benchmark_square_mixedgl(c: &mut Criterion) {
+    use boojum::field::traits::field_like::PrimeFieldLike;
+
+    let degree: usize = 1 << 16;
+    // squaring-heavy loop, like the exponentiation by a power of two
+    let num_squarings = 64;
+
+    let aa: Vec<GoldilocksField> = (0..degree)
+        .map(|x| x as u64 + 1)
+        .map(GoldilocksField::from_u64_with_reduction)
+        .collect();
+
+    let mut aa: Vec<MixedGL> =
+        MixedGL::vec_from_base_vec(clone_respecting_allignment::<GoldilocksField, MixedGL, _>(&aa));
+
+    c.bench_function("MixedGL repeated square", |b| {
+        b.iter(|| {
+            for a in black_box(&mut aa).iter_mut() {
+                for _ in 0..num_squarings {
+                    PrimeFieldLike::square(a, &mut ());
+                }
+            }
+        })
+    });
+
+    c.bench_function("MixedGL repeated mul by self", |b| {
+        b.iter(|| {
+            for a in black_box(&mut aa).iter_mut() {
+                for _ in 0..num_squarings {
+                    let t = *a;
+                    PrimeFieldLike::mul_assign(a, &t, &mut ());
+                }
+            }
+        })
+    });
+}
+
+use boojum::field::goldilocks::poly::batch_horner;
+
+fn criterion_benchmark_batch_horner(c: &mut Criterion) {
+    let num_polys = 64;
+    let degree: usize = 1 << 14;
+    let worker = Worker::new();
+
+    let polys: Vec<Vec<GoldilocksField>> = (0..num_polys)
+        .map(|i| {
+            (0..degree)
+                .map(|x| GoldilocksField::from_u64_with_reduction((i * degree + x) as u64 + 1))
+                .collect()
+        })
+        .collect();
+    let refs: Vec<&[GoldilocksField]> = polys.iter().map(|el| &el[..]).collect();
+    let point = GoldilocksField::from_u64_with_reduction(0x1234567890abcdef);
+
+    c.bench_function("Batch Horner over MixedGL", |b| {
+        b.iter(|| batch_horner(black_box(&refs), black_box(point), &worker))
+    });
+
+    c.bench_function("Naive Horner per polynomial", |b| {
+        b.iter(|| {
+            black_box(&refs)
+                .iter()
+                .map(|poly| {
+                    let mut result = GoldilocksField::ZERO;
+                    for c in poly.iter().rev() {
+                        result.mul_assign(black_box(&point));
+                        result.add_assign(c);
+                    }
+                    result
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
+fn criterion_benchmark_axpy_mixedgl(c: &mut Criterion) {
+    let degree: usize = 1 << 22;
+    // single thread, so only the fusion of the passes is measured
+    let worker = Worker::new_with_num_threads(1);
+
+    let aa: Vec<GoldilocksField> = (0..degree)
+        .map(|x| x as u64 + 1)
+        .map(GoldilocksField::from_u64_with_reduction)
+        .collect();
+    let xx: Vec<GoldilocksField> = (0..degree)
+        .map(|x| x as u64 + 2)
+        .map(GoldilocksField::from_u64_with_reduction)
+        .collect();
+    let alpha = GoldilocksField::from_u64_with_reduction(0x1234567890abcdef);
+
+    let mut aa: Vec<MixedGL> =
+        MixedGL::vec_from_base_vec(clone_respecting_allignment::<GoldilocksField, MixedGL, _>(&aa));
+    let xx: Vec<MixedGL> =
+        MixedGL::vec_from_base_vec(clone_respecting_allignment::<GoldilocksField, MixedGL, _>(&xx));
+    let mut scratch = xx.clone();
+
+    c.bench_function("MixedGL axpy", |b| {
+        b.iter(|| MixedGL::axpy(black_box(&mut aa), black_box(alpha), black_box(&xx), &worker))
+    });
+
+    c.bench_function("MixedGL scale then add", |b| {
+        b.iter(|| {
+            for (dst, src) in scratch.iter_mut().zip(black_box(&xx).iter()) {
+                *dst = *src;
+                dst.mul_constant_assign(black_box(&alpha));
+            }
+            MixedGL::vec_add_assign(black_box(&mut aa), &scratch);
+        })
+    });
+}
+
+fn criterion_benchmark_vec_ops_parallel(c: &mut Criterion) {
+    let degree: usize = 1 << 22;
+    let worker = Worker::new();
+
+    let aa: Vec<GoldilocksField> = (0..degree)
+        .map(|x| x as u64 + 1)
+        .map(GoldilocksField::from_u64_with_reduction)
+        .collect();
+    let bb: Vec<GoldilocksField> = (0..degree)
+        .map(|x| x as u64 + 2)
+        .map(GoldilocksField::from_u64_with_reduction)
+        .collect();
+
+    let mut aa: Vec<MixedGL> =
+        MixedGL::vec_from_base_vec(clone_respecting_allignment::<GoldilocksField, MixedGL, _>(&aa));
+    let bb: Vec<MixedGL> =
+        MixedGL::vec_from_base_vec(clone_respecting_allignment::<GoldilocksField, MixedGL, _>(&bb));
+
+    c.bench_function("MixedGL vec add", |b| {
+        b.iter(|| MixedGL::vec_add_assign(black_box(&mut aa), black_box(&bb)))
+    });
+
+    c.bench_function("MixedGL vec add parallel", |b| {
+        b.iter(|| MixedGL::vec_add_assign_parallel(black_box(&mut aa), black_box(&bb), &worker))
+    });
+
+    c.bench_function("MixedGL vec mul", |b| {
+        b.iter(|| MixedGL::vec_mul_assign(black_box(&mut aa), black_box(&bb)))
+    });
+
+    c.bench_function("MixedGL vec mul parallel", |b| {
+        b.iter(|| MixedGL::vec_mul_assign_parallel(black_box(&mut aa), black_box(&bb), &worker))
+    });
+}
+
+fn criterion_benchmark_scale_slice(c: &mut Criterion) {
+    let degree: usize = 1 << 22;
+    let worker = Worker::new();
+
+    let mut aa: Vec<GoldilocksField> = (0..degree)
+        .map(|x| x as u64 + 1)
+        .map(GoldilocksField::from_u64_with_reduction)
+        .collect();
+    let by = GoldilocksField::from_u64_with_reduction(0x1234567890abcdef);
+
+    c.bench_function("GoldilocksField scale slice", |b| {
+        b.iter(|| GoldilocksField::scale_slice(black_box(&mut aa), black_box(by), &worker))
+    });
+
+    c.bench_function("GoldilocksField scale slice scalar", |b| {
+        b.iter(|| {
+            for a in black_box(&mut aa).iter_mut() {
+                GoldilocksField::mul_assign(a, black_box(&by));
+            }
+        })
+    });
+}
+
+use boojum::field::{goldilocks::poly::fri_fold, PrimeField};
+
+fn criterion_benchmark_fri_fold(c: &mut Criterion) {
+    let degree: usize = 1 << 22;
+    let worker = Worker::new();
+
+    let layer: Vec<GoldilocksField> = (0..degree)
+        .map(|x| x as u64 + 1)
+        .map(GoldilocksField::from_u64_with_reduction)
+        .collect();
+    let twiddles =
+        precompute_twiddles_for_fft_wrapper::<GoldilocksField, Global, true>(degree, &worker);
+    let challenge = GoldilocksField::from_u64_with_reduction(0x1234567890abcdef);
+    let coset = GoldilocksField::multiplicative_generator();
+    let mut scale = coset.inverse().unwrap();
+    scale.mul_assign(&challenge);
+
+    c.bench_function("FRI fold over MixedGL", |b| {
+        b.iter(|| fri_fold(black_box(&layer), black_box(challenge), coset, &twiddles, &worker))
+    });
+
+    c.bench_function("Naive FRI fold", |b| {
+        b.iter(|| {
+            black_box(&layer)
+                .chunks_exact(2)
+                .zip(twiddles.iter())
+                .map(|(pair, root)| {
+                    let mut diff = pair[0];
+                    diff.sub_assign(&pair[1])
+                        .mul_assign(root)
+                        .mul_assign(&scale);
+                    let mut result = pair[0];
+                    result.add_assign(&pair[1]).add_assign(&diff);
+                    result
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
 use std::alloc::Global;
 
 use boojum::{
@@ -554,6 +805,35 @@ fn criterion_benchmark_fft_mixedgl(c: &mut Criterion) {
     });
 }
 
+// Large enough for the first rounds to not fit into the cache, so it measures the memory bound part
+// of the MixedGL FFT (and prefetching in it)
+fn criterion_benchmark_fft_mixedgl_out_of_cache(c: &mut Criterion) {
+    let worker = Worker::new();
+    let mut ctx = ();
+    let mut rng = rand::thread_rng();
+    let poly_size_log = 22;
+    let poly_size = 1 << poly_size_log;
+
+    let original: Vec<GoldilocksField> = (0..poly_size).map(|_| rand_from_rng(&mut rng)).collect();
+    let forward_twiddles =
+        MixedGL::precompute_forward_twiddles_for_fft::<Global>(poly_size, &worker, &mut ctx);
+    let mut reference: Vec<MixedGL> =
+        MixedGL::vec_from_base_vec(clone_respecting_allignment::<GoldilocksField, MixedGL, _>(
+            &original,
+        ));
+
+    c.bench_function("FFT MixedGL 2^22", |b| {
+        b.iter(|| {
+            MixedGL::fft_natural_to_bitreversed(
+                black_box(&mut reference),
+                black_box(GoldilocksField::ONE),
+                black_box(&forward_twiddles),
+                &mut ctx,
+            )
+        })
+    });
+}
+
 fn criterion_benchmark_ifft_naive(c: &mut Criterion) {
     let worker = Worker::new();
     let mut ctx = ();
@@ -643,6 +923,85 @@ fn criterion_benchmark_bitreverse_naive(c: &mut Criterion) {
     });
 }
 
+// Inputs of the benches below come from fixed seeds, so they are the same on every machine
+const PRIMITIVES_SEED: u64 = 42;
+
+fn criterion_benchmark_arithmetic_mixedgl(c: &mut Criterion) {
+    use boojum::field::traits::field_like::PrimeFieldLike;
+
+    let num_vectors: usize = 1 << 16;
+    println!("Using {:?} backend", MixedGL::active_backend());
+
+    let mut aa = MixedGL::random_vec(num_vectors, PRIMITIVES_SEED);
+    let bb = MixedGL::random_vec(num_vectors, PRIMITIVES_SEED + 1);
+
+    c.bench_function("MixedGL add", |b| {
+        b.iter(|| {
+            for (a, b) in black_box(&mut aa).iter_mut().zip(black_box(&bb).iter()) {
+                PrimeFieldLike::add_assign(a, b, &mut ());
+            }
+        })
+    });
+
+    c.bench_function("MixedGL sub", |b| {
+        b.iter(|| {
+            for (a, b) in black_box(&mut aa).iter_mut().zip(black_box(&bb).iter()) {
+                PrimeFieldLike::sub_assign(a, b, &mut ());
+            }
+        })
+    });
+
+    c.bench_function("MixedGL mul", |b| {
+        b.iter(|| {
+            for (a, b) in black_box(&mut aa).iter_mut().zip(black_box(&bb).iter()) {
+                PrimeFieldLike::mul_assign(a, b, &mut ());
+            }
+        })
+    });
+}
+
+fn criterion_benchmark_poseidon2_batch(c: &mut Criterion) {
+    use boojum::implementations::poseidon2::{poseidon2_permutation, Poseidon2Goldilocks};
+
+    let num_states: usize = 1 << 12;
+
+    let original: Vec<GoldilocksField> = GoldilocksField::sample_from_seed(PRIMITIVES_SEED)
+        .take(12 * num_states)
+        .collect();
+    let mut states: Vec<[GoldilocksField; 12]> = original
+        .chunks_exact(12)
+        .map(|el| el.try_into().unwrap())
+        .collect();
+    let mut soa = original.clone();
+
+    c.bench_function("Poseidon2 permutation one by one", |b| {
+        b.iter(|| {
+            black_box(&mut states)
+                .iter_mut()
+                .for_each(poseidon2_permutation)
+        })
+    });
+
+    c.bench_function("Poseidon2 permutation batch SoA", |b| {
+        b.iter(|| Poseidon2Goldilocks::permute_batch_soa(black_box(&mut soa), num_states))
+    });
+}
+
+fn criterion_benchmark_fft_sizes(c: &mut Criterion) {
+    let worker = Worker::new();
+    let coset = GoldilocksField::multiplicative_generator();
+
+    for poly_size_log in [10, 14, 18, 22] {
+        let input: Vec<GoldilocksField> = GoldilocksField::sample_from_seed(PRIMITIVES_SEED)
+            .take(1 << poly_size_log)
+            .collect();
+
+        c.bench_function(&format!("FFT of size 2^{}", poly_size_log), |b| {
+            b.iter(|| boojum::field::goldilocks::poly::fft(black_box(&input), coset, &worker))
+        });
+    }
+}
+
 criterion_group!(multiplication, criterion_benchmark_multiplication,);
 
 criterion_group!(
@@ -653,6 +1012,7 @@ criterion_group!(
     criterion_benchmark_poseidon2_mds_mul_vectorized,
     criterion_benchmark_poseidon2_inner_matrix_mul,
     criterion_benchmark_poseidon2_inner_matrix_split_mul,
+    criterion_benchmark_poseidon2_folded_constants,
     // criterion_benchmark_poseidon_naive,
     // criterion_benchmark_poseidon_optimized,
     // criterion_benchmark_poseidon_vectorized,
@@ -680,6 +1040,13 @@ criterion_group!(
     // // criterion_benchmark_add_vectors_simd,
     // // criterion_benchmark_add_vectors_portable_simd,
     // // criterion_benchmark_add_vectors_glps,
+    criterion_benchmark_mul_constant_mixedgl,
+    criterion_benchmark_square_mixedgl,
+    criterion_benchmark_batch_horner,
+    criterion_benchmark_axpy_mixedgl,
+    criterion_benchmark_vec_ops_parallel,
+    criterion_benchmark_scale_slice,
+    criterion_benchmark_fri_fold,
     criterion_benchmark_add_vectors_mixedgl, /* candidate #3
                                               * criterion_benchmark_add_vectors_x86, //candidate #2
                                               * criterion_benchmark_mul_vectors_naive,
@@ -701,6 +1068,7 @@ criterion_group!(
     // criterion_benchmark_fft_naive,
     // criterion_benchmark_fft_cache_friendly,
     criterion_benchmark_fft_mixedgl,
+    criterion_benchmark_fft_mixedgl_out_of_cache,
 );
 
 criterion_group!(
@@ -716,6 +1084,13 @@ criterion_group!(
 //     criterion_benchmark_split_concat,
 // );
 
+criterion_group!(
+    primitives,
+    criterion_benchmark_arithmetic_mixedgl,
+    criterion_benchmark_poseidon2_batch,
+    criterion_benchmark_fft_sizes,
+);
+
 criterion_group!(
     bitreverse,
     criterion_benchmark_bitreverse_naive,
@@ -729,3 +1104,4 @@ criterion_main!(poseidon);
 // criterion_main!(vectorized);
 // criterion_main!(fft);
 // criterion_main!(bitreverse);
+// criterion_main!(primitives);
@@ -1,13 +1,14 @@
 use derivative::*;
 
 use super::*;
-use crate::field::SmallField;
+use crate::field::{goldilocks::MixedGL, SmallField};
 
 #[derive(Derivative)]
 #[derivative(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum AbsorptionMode {
     Addition = 0,
-    Overwrite, // https://keccak.team/files/CSF-0.1.pdf - e.g. some info about this mode
+    Overwrite,   // https://keccak.team/files/CSF-0.1.pdf - e.g. some info about this mode
+    Subtraction, // inverse of `Addition`, e.g. for reversible sponges
 }
 
 pub trait AbsorptionModeTrait<T: Sized>:
@@ -55,6 +56,55 @@ impl<F: PrimeField> AbsorptionModeTrait<F> for AbsorptionModeOverwrite {
     }
 }
 
+#[derive(Clone, Copy, Eq, Debug)]
+pub struct AbsorptionModeSub;
+
+impl PartialEq<AbsorptionModeSub> for AbsorptionModeSub {
+    fn eq(&self, _other: &AbsorptionModeSub) -> bool {
+        true
+    }
+}
+
+impl<F: PrimeField> AbsorptionModeTrait<F> for AbsorptionModeSub {
+    #[inline(always)]
+    fn absorb(dst: &mut F, src: &F) {
+        dst.sub_assign(src);
+    }
+    #[inline(always)]
+    fn pad(_dst: &mut F) {}
+}
+
+// element-wise versions for sponges running in the lanes of `MixedGL`, e.g. `Poseidon2Wide`
+
+impl AbsorptionModeTrait<MixedGL> for AbsorptionModeAdd {
+    #[inline(always)]
+    fn absorb(dst: &mut MixedGL, src: &MixedGL) {
+        field::traits::field_like::PrimeFieldLike::add_assign(dst, src, &mut ());
+    }
+    #[inline(always)]
+    fn pad(_dst: &mut MixedGL) {}
+}
+
+impl AbsorptionModeTrait<MixedGL> for AbsorptionModeOverwrite {
+    #[inline(always)]
+    fn absorb(dst: &mut MixedGL, src: &MixedGL) {
+        *dst = *src;
+    }
+    #[inline(always)]
+    fn pad(dst: &mut MixedGL) {
+        *dst = <MixedGL as field::traits::field_like::PrimeFieldLike>::zero(&mut ());
+    }
+}
+
+impl AbsorptionModeTrait<MixedGL> for AbsorptionModeSub {
+    #[inline(always)]
+    fn absorb(dst: &mut MixedGL, src: &MixedGL) {
+        field::traits::field_like::PrimeFieldLike::sub_assign(dst, src, &mut ());
+    }
+    #[inline(always)]
+    fn pad(_dst: &mut MixedGL) {}
+}
+
 pub trait AlgebraicRoundFunctionWithParams<
     F: PrimeField,
     const AW: usize,
@@ -68,6 +118,27 @@ pub trait AlgebraicRoundFunctionWithParams<
     fn absorb_into_state(&self, state: &mut [F; SW], to_absorb: &[F; AW], mode: AbsorptionMode);
     fn state_get_commitment<'a>(&self, state: &'a [F; SW]) -> &'a [F];
     fn state_into_commitment_fixed<const N: usize>(&self, state: &[F; SW]) -> [F; N];
+
+    /// Hashes `input` of any length from the initial state in the overwrite mode, padding the
+    /// last block with zeroes, so it's the same as [`super::sponge::AlgebraicSponge`] in its
+    /// default mode. The length is not mixed in, use [`Self::specialize_for_len`] on the state
+    /// explicitly if it's required.
+    fn hash_many<const N: usize>(&self, input: &[F]) -> [F; N] {
+        let mut state = self.initial_state();
+        let mut it = input.array_chunks::<AW>();
+        for chunk in &mut it {
+            self.absorb_into_state(&mut state, chunk, AbsorptionMode::Overwrite);
+            self.round_function(&mut state);
+        }
+        if it.remainder().is_empty() == false {
+            let mut tmp = [F::ZERO; AW];
+            tmp[..it.remainder().len()].copy_from_slice(it.remainder());
+            self.absorb_into_state(&mut state, &tmp, AbsorptionMode::Overwrite);
+            self.round_function(&mut state);
+        }
+
+        self.state_into_commitment_fixed::<N>(&state)
+    }
 }
 
 pub trait AlgebraicRoundFunction<F: PrimeField, const AW: usize, const SW: usize, const CW: usize>:
@@ -78,6 +149,26 @@ pub trait AlgebraicRoundFunction<F: PrimeField, const AW: usize, const SW: usize
     fn specialize_for_len(len: u32, state: &mut [F; SW]);
     fn absorb_into_state<M: AbsorptionModeTrait<F>>(state: &mut [F; SW], to_absorb: &[F; AW]);
     fn state_into_commitment<const N: usize>(state: &[F; SW]) -> [F; N];
+
+    /// Same as [`AlgebraicRoundFunctionWithParams::hash_many`]: hashes `input` of any length from
+    /// the initial state in the overwrite mode with zero padding of the last block, without
+    /// mixing in the length.
+    fn hash_many<const N: usize>(input: &[F]) -> [F; N] {
+        let mut state = Self::initial_state();
+        let mut it = input.array_chunks::<AW>();
+        for chunk in &mut it {
+            Self::absorb_into_state::<AbsorptionModeOverwrite>(&mut state, chunk);
+            Self::round_function(&mut state);
+        }
+        if it.remainder().is_empty() == false {
+            let mut tmp = [F::ZERO; AW];
+            tmp[..it.remainder().len()].copy_from_slice(it.remainder());
+            Self::absorb_into_state::<AbsorptionModeOverwrite>(&mut state, &tmp);
+            Self::round_function(&mut state);
+        }
+
+        Self::state_into_commitment::<N>(&state)
+    }
 }
 
 pub trait GenericAlgebraicRoundFunction<
@@ -180,3 +271,136 @@ pub fn absorb_into_state_vararg<
         T::round_function(state);
     }
 }
+
+/// Object safe counterpart of [`AlgebraicRoundFunctionWithParams`] for the Goldilocks round
+/// functions of width 12 and rate 8. It allows to pick the hash function at runtime (e.g. from
+/// config) and keep it as `Box<dyn DynRoundFunction>` without making all the code around generic
+/// over it, at the price of dynamic dispatch and runtime length checks.
+pub trait DynRoundFunction: Send + Sync {
+    fn state_width(&self) -> usize;
+    fn rate(&self) -> usize;
+    /// Permutes the `state` inplace, it must contain exactly `state_width()` elements.
+    fn permute(&self, state: &mut [GoldilocksField]);
+    /// Hashes `input` of any length with the overwrite mode sponge and returns the commitment.
+    fn compress(&self, input: &[GoldilocksField]) -> Vec<GoldilocksField>;
+}
+
+impl<R: AlgebraicRoundFunctionWithParams<GoldilocksField, 8, 12, 4>> DynRoundFunction for R {
+    fn state_width(&self) -> usize {
+        12
+    }
+
+    fn rate(&self) -> usize {
+        8
+    }
+
+    fn permute(&self, state: &mut [GoldilocksField]) {
+        let len = state.len();
+        let state: &mut [GoldilocksField; 12] = state
+            .try_into()
+            .unwrap_or_else(|_| panic!("state must have 12 elements, got {}", len));
+        self.round_function(state);
+    }
+
+    fn compress(&self, input: &[GoldilocksField]) -> Vec<GoldilocksField> {
+        let mut sponge = super::sponge::AlgebraicSponge::<GoldilocksField, 8, 12, 4, R>::new(
+            self.clone(),
+            AbsorptionMode::Overwrite,
+        );
+        sponge.absorb(input);
+
+        sponge.finalize::<4>().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        field::rand_from_rng,
+        implementations::{
+            poseidon2::Poseidon2Goldilocks, poseidon_goldilocks_naive::PoseidonGoldilocks,
+        },
+    };
+
+    fn compress_reference<R: AlgebraicRoundFunction<GoldilocksField, 8, 12, 4>>(
+        input: &[GoldilocksField],
+    ) -> Vec<GoldilocksField> {
+        let mut state = R::initial_state();
+        absorb_into_state_vararg::<_, R, AbsorptionModeOverwrite, 8, 12, 4>(&mut state, input);
+
+        R::state_into_commitment::<4>(&state).to_vec()
+    }
+
+    #[test]
+    fn test_dyn_round_function() {
+        let mut rng = rand::thread_rng();
+        let hashers: Vec<Box<dyn DynRoundFunction>> =
+            vec![Box::new(Poseidon2Goldilocks), Box::new(PoseidonGoldilocks)];
+        let input: Vec<GoldilocksField> = (0..20).map(|_| rand_from_rng(&mut rng)).collect();
+        let state: [GoldilocksField; 12] = std::array::from_fn(|_| rand_from_rng(&mut rng));
+
+        let mut expected_state = state;
+        <Poseidon2Goldilocks as AlgebraicRoundFunction<GoldilocksField, 8, 12, 4>>::round_function(
+            &mut expected_state,
+        );
+        let mut dyn_state = state;
+        hashers[0].permute(&mut dyn_state);
+        assert_eq!(dyn_state, expected_state);
+        assert_eq!(hashers[0].compress(&input), compress_reference::<Poseidon2Goldilocks>(&input));
+
+        let mut expected_state = state;
+        <PoseidonGoldilocks as AlgebraicRoundFunction<GoldilocksField, 8, 12, 4>>::round_function(
+            &mut expected_state,
+        );
+        let mut dyn_state = state;
+        hashers[1].permute(&mut dyn_state);
+        assert_eq!(dyn_state, expected_state);
+        assert_eq!(hashers[1].compress(&input), compress_reference::<PoseidonGoldilocks>(&input));
+
+        assert_ne!(hashers[0].compress(&input), hashers[1].compress(&input));
+        for hasher in hashers.iter() {
+            assert_eq!(hasher.state_width(), 12);
+            assert_eq!(hasher.compress(&input[..8]).len(), 4);
+        }
+    }
+
+    fn hash_many_static<R: AlgebraicRoundFunction<GoldilocksField, 8, 12, 4>>(
+        input: &[GoldilocksField],
+    ) -> [GoldilocksField; 4] {
+        R::hash_many::<4>(input)
+    }
+
+    #[test]
+    fn test_hash_many() {
+        use crate::algebraic_props::sponge::AlgebraicSponge;
+
+        let mut rng = rand::thread_rng();
+        for len in 0..30 {
+            let input: Vec<GoldilocksField> = (0..len).map(|_| rand_from_rng(&mut rng)).collect();
+
+            let mut sponge = AlgebraicSponge::<GoldilocksField, 8, 12, 4, Poseidon2Goldilocks>::new(
+                Poseidon2Goldilocks,
+                AbsorptionMode::Overwrite,
+            );
+            sponge.absorb(&input);
+            let expected = sponge.finalize::<4>();
+
+            assert_eq!(
+                hash_many_static::<Poseidon2Goldilocks>(&input),
+                expected,
+                "invalid hash of length {}",
+                len
+            );
+            assert_eq!(
+                AlgebraicRoundFunctionWithParams::<GoldilocksField, 8, 12, 4>::hash_many::<4>(
+                    &Poseidon2Goldilocks,
+                    &input
+                ),
+                expected,
+                "invalid hash of length {}",
+                len
+            );
+        }
+    }
+}
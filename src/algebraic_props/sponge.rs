@@ -71,6 +71,9 @@ impl<
                     AbsorptionMode::Addition => {
                         dst.add_assign(src);
                     }
+                    AbsorptionMode::Subtraction => {
+                        dst.sub_assign(src);
+                    }
                     AbsorptionMode::Overwrite => {
                         *dst = *src;
                     }
@@ -87,6 +90,9 @@ impl<
                     AbsorptionMode::Addition => {
                         dst.add_assign(src);
                     }
+                    AbsorptionMode::Subtraction => {
+                        dst.sub_assign(src);
+                    }
                     AbsorptionMode::Overwrite => {
                         *dst = *src;
                     }
@@ -114,6 +120,9 @@ impl<
                     AbsorptionMode::Addition => {
                         dst.add_assign(src);
                     }
+                    AbsorptionMode::Subtraction => {
+                        dst.sub_assign(src);
+                    }
                     AbsorptionMode::Overwrite => {
                         *dst = *src;
                     }
@@ -121,7 +130,7 @@ impl<
             }
 
             match mode {
-                AbsorptionMode::Addition => {}
+                AbsorptionMode::Addition | AbsorptionMode::Subtraction => {}
                 AbsorptionMode::Overwrite => {
                     for dst in state[filled..AW].iter_mut() {
                         *dst = F::ZERO;
@@ -209,6 +218,28 @@ impl<
         }
     }
 
+    /// Starts from the given `state` instead of `R::initial_state()`, e.g. with an IV in the
+    /// capacity elements. Note that `finalize_reset` still resets to `R::initial_state()`.
+    #[inline]
+    pub fn new_with_initial_state(state: [P; SW], ctx: &mut P::Context) -> Self {
+        Self { buffer: [P::zero(ctx); AW], filled: 0, state, _marker: std::marker::PhantomData }
+    }
+
+    /// Puts the sponge back into the state of [`GenericAlgebraicSponge::new`] in place, so one
+    /// instance can hash a stream of messages. Buffered elements are discarded.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.reset_with_initial_state(R::initial_state());
+    }
+
+    /// Same as [`GenericAlgebraicSponge::reset`], but to the state of
+    /// [`GenericAlgebraicSponge::new_with_initial_state`].
+    #[inline]
+    pub fn reset_with_initial_state(&mut self, state: [P; SW]) {
+        self.state = state;
+        self.filled = 0;
+    }
+
     pub fn absorb_single(&mut self, value: &P) {
         debug_assert!(self.filled < AW);
         self.buffer[self.filled] = *value;
@@ -332,7 +363,12 @@ use crate::implementations::poseidon_goldilocks_naive::PoseidonGoldilocks;
 pub type GoldilocksPoseidonSponge<M> =
     SimpleAlgebraicSponge<GoldilocksField, 8, 12, 4, PoseidonGoldilocks, M>;
 
-use crate::implementations::poseidon2::Poseidon2Goldilocks;
+use crate::implementations::poseidon2::{Poseidon2Goldilocks, Poseidon2GoldilocksDigest};
 
 pub type GoldilocksPoseidon2Sponge<M> =
     SimpleAlgebraicSponge<GoldilocksField, 8, 12, 4, Poseidon2Goldilocks, M>;
+
+/// Poseidon2 sponge with `D` (4 or 8) element outputs, e.g. for Merkle trees with 8 element
+/// digests.
+pub type GoldilocksPoseidon2SpongeWithDigest<M, const D: usize> =
+    SimpleAlgebraicSponge<GoldilocksField, 8, 12, D, Poseidon2GoldilocksDigest<D>, M>;
@@ -0,0 +1,219 @@
+//! Boundary between the high-level entry points and the code doing the heavy lifting, so the FFT
+//! and hashing can be offloaded to an external accelerator (e.g. a GPU) by passing another
+//! implementation of [`FftBackend`] or [`HashBackend`] to the `_with_backend` variants of the entry
+//! points, e.g. [`poly::interpolate_with_backend`]. [`CpuBackend`] runs the `MixedGL` code
+//! of this crate and is what the functions without a backend argument use.
+use crate::{
+    algebraic_props::{round_function::AbsorptionModeOverwrite, sponge::GoldilocksPoseidon2Sponge},
+    cs::oracle::TreeHasher,
+    fft::{bitreverse_enumeration_inplace, distribute_powers},
+    field::{
+        goldilocks::{poly, GoldilocksField},
+        Field, PrimeField,
+    },
+    worker::Worker,
+};
+
+/// Forward and inverse FFT over unpacked slices of any length.
+pub trait FftBackend {
+    /// Same as [`poly::fft`]: evaluations of `input` (zero-extended to the next power of
+    /// two) over `coset * <omega_n>` in bitreversed order.
+    fn fft(
+        &self,
+        input: &[GoldilocksField],
+        coset: GoldilocksField,
+        worker: &Worker,
+    ) -> Vec<GoldilocksField>;
+
+    /// Inverse of [`FftBackend::fft`]: coefficients in natural order from evaluations over
+    /// `coset * <omega_n>` in bitreversed order. The length must be a power of two.
+    fn ifft(
+        &self,
+        input: &[GoldilocksField],
+        coset: GoldilocksField,
+        worker: &Worker,
+    ) -> Vec<GoldilocksField>;
+}
+
+/// Hashing of many independent leaves, the bulk of the work of building a Merkle tree.
+pub trait HashBackend {
+    /// Poseidon2 leaf hashes (as [`GoldilocksPoseidon2Sponge::hash_into_leaf`] in overwrite mode)
+    /// of every `leaf_size` consecutive elements of `leaves`. The last leaf may be shorter.
+    fn hash_leaves(
+        &self,
+        leaves: &[GoldilocksField],
+        leaf_size: usize,
+        worker: &Worker,
+    ) -> Vec<[GoldilocksField; 4]>;
+}
+
+/// Default backend, running on the worker threads.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuBackend;
+
+impl FftBackend for CpuBackend {
+    fn fft(
+        &self,
+        input: &[GoldilocksField],
+        coset: GoldilocksField,
+        worker: &Worker,
+    ) -> Vec<GoldilocksField> {
+        poly::fft(input, coset, worker)
+    }
+
+    fn ifft(
+        &self,
+        input: &[GoldilocksField],
+        coset: GoldilocksField,
+        worker: &Worker,
+    ) -> Vec<GoldilocksField> {
+        // evaluations over `coset * <omega_n>` are the ones of `p(coset * x)` over `<omega_n>`, so
+        // the coefficients of `p` are the ones interpolated over the subgroup, divided by powers
+        // of the coset
+        let mut result = input.to_vec();
+        bitreverse_enumeration_inplace(&mut result);
+        let mut result = poly::interpolate(&result, worker);
+        if coset != GoldilocksField::ONE {
+            let coset_inverse = coset.inverse().expect("coset must be non-zero");
+            distribute_powers(&mut result, coset_inverse);
+        }
+
+        result
+    }
+}
+
+impl HashBackend for CpuBackend {
+    fn hash_leaves(
+        &self,
+        leaves: &[GoldilocksField],
+        leaf_size: usize,
+        worker: &Worker,
+    ) -> Vec<[GoldilocksField; 4]> {
+        type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        assert!(leaf_size > 0, "leaf size must be positive");
+        let num_leafs = leaves.len().div_ceil(leaf_size);
+        let mut result = vec![<H as TreeHasher<GoldilocksField>>::placeholder_output(); num_leafs];
+
+        worker.scope(num_leafs, |scope, chunk_size| {
+            for (dst, src) in result
+                .chunks_mut(chunk_size)
+                .zip(leaves.chunks(chunk_size * leaf_size))
+            {
+                scope.spawn(move |_| {
+                    for (dst, src) in dst.iter_mut().zip(src.chunks(leaf_size)) {
+                        *dst = <H as TreeHasher<GoldilocksField>>::hash_into_leaf(src);
+                    }
+                });
+            }
+        });
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{
+        field::{rand_from_rng, PrimeField},
+        implementations::poseidon2::Poseidon2Goldilocks,
+    };
+
+    // counts the calls and forwards them to the CPU
+    #[derive(Default)]
+    struct MockBackend {
+        num_calls: AtomicUsize,
+    }
+
+    impl FftBackend for MockBackend {
+        fn fft(
+            &self,
+            input: &[GoldilocksField],
+            coset: GoldilocksField,
+            worker: &Worker,
+        ) -> Vec<GoldilocksField> {
+            self.num_calls.fetch_add(1, Ordering::Relaxed);
+            CpuBackend.fft(input, coset, worker)
+        }
+
+        fn ifft(
+            &self,
+            input: &[GoldilocksField],
+            coset: GoldilocksField,
+            worker: &Worker,
+        ) -> Vec<GoldilocksField> {
+            self.num_calls.fetch_add(1, Ordering::Relaxed);
+            CpuBackend.ifft(input, coset, worker)
+        }
+    }
+
+    impl HashBackend for MockBackend {
+        fn hash_leaves(
+            &self,
+            leaves: &[GoldilocksField],
+            leaf_size: usize,
+            worker: &Worker,
+        ) -> Vec<[GoldilocksField; 4]> {
+            self.num_calls.fetch_add(1, Ordering::Relaxed);
+            CpuBackend.hash_leaves(leaves, leaf_size, worker)
+        }
+    }
+
+    fn fft_roundtrip<B: FftBackend>(backend: &B, input: &[GoldilocksField], worker: &Worker) {
+        let coset = GoldilocksField::multiplicative_generator();
+        let forward = backend.fft(input, coset, worker);
+        assert_eq!(forward, poly::fft(input, coset, worker));
+        assert_eq!(backend.ifft(&forward, coset, worker), input);
+    }
+
+    #[test]
+    fn test_cpu_backend() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        for log_n in [0, 2, 4, 5, 10] {
+            let input: Vec<GoldilocksField> =
+                (0..1 << log_n).map(|_| rand_from_rng(&mut rng)).collect();
+            fft_roundtrip(&CpuBackend, &input, &worker);
+        }
+
+        let witness_len = Poseidon2Goldilocks::WITNESS_HASH_CHUNK_SIZE * 3 + 5;
+        let witness: Vec<GoldilocksField> =
+            (0..witness_len).map(|_| rand_from_rng(&mut rng)).collect();
+        let leaves = CpuBackend.hash_leaves(&witness, 100, &worker);
+        assert_eq!(leaves.len(), witness.len().div_ceil(100));
+        for (leaf, src) in leaves.iter().zip(witness.chunks(100)) {
+            let expected = <GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite> as TreeHasher<
+                GoldilocksField,
+            >>::hash_into_leaf(src);
+            assert_eq!(*leaf, expected);
+        }
+    }
+
+    #[test]
+    fn test_custom_backend_is_used() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        let backend = MockBackend::default();
+
+        let input: Vec<GoldilocksField> = (0..64).map(|_| rand_from_rng(&mut rng)).collect();
+        fft_roundtrip(&backend, &input, &worker);
+        assert_eq!(backend.num_calls.load(Ordering::Relaxed), 2);
+
+        let evals = poly::evaluate_over_subgroup_with_backend(&input, &backend, &worker);
+        assert_eq!(evals, poly::evaluate_over_subgroup(&input, &worker));
+        assert_eq!(poly::interpolate_with_backend(&evals, &backend, &worker), input);
+        assert_eq!(backend.num_calls.load(Ordering::Relaxed), 4);
+
+        let witness_len = Poseidon2Goldilocks::WITNESS_HASH_CHUNK_SIZE * 3 + 5;
+        let witness: Vec<GoldilocksField> =
+            (0..witness_len).map(|_| rand_from_rng(&mut rng)).collect();
+        assert_eq!(
+            Poseidon2Goldilocks::hash_witness_with_backend(&witness, &backend, &worker),
+            Poseidon2Goldilocks::hash_witness(&witness, &worker)
+        );
+        assert_eq!(backend.num_calls.load(Ordering::Relaxed), 5);
+    }
+}
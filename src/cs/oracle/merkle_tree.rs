@@ -1,4 +1,4 @@
-use std::alloc::Global;
+use std::{alloc::Global, mem::MaybeUninit};
 
 use super::*;
 use crate::{
@@ -12,6 +12,74 @@ use crate::{
     field::traits::field_like::Flattener,
 };
 
+/// Reusable buffers for the layers of [`MerkleTreeWithCap`], see
+/// [`MerkleTreeWithCap::construct_by_chunking_from_flat_sources_with_scratch`]. A tree built from
+/// the scratch owns its buffers until they are given back with [`MerkleScratch::recycle`]. Buffers
+/// only grow, so a single scratch serves trees of any size up to the largest one it was used for.
+pub struct MerkleScratch<T, A: GoodAllocator = Global> {
+    // leaf hashes, and then node layers enumerated from the leafs
+    layers: Vec<Vec<T, A>>,
+}
+
+impl<T, A: GoodAllocator> MerkleScratch<T, A> {
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    /// Preallocates all the layers of a tree with `max_tree_size` leafs and a cap of size 1.
+    pub fn with_capacity(max_tree_size: usize) -> Self {
+        assert!(max_tree_size.is_power_of_two());
+        let mut new = Self::new();
+        new.prepare(max_tree_size, max_tree_size.trailing_zeros() as usize + 1);
+
+        new
+    }
+
+    /// Number of leafs that fit into the scratch without reallocation. Buffers of a tree that is
+    /// not recycled yet are not counted.
+    pub fn capacity(&self) -> usize {
+        self.layers.first().map(|el| el.capacity()).unwrap_or(0)
+    }
+
+    /// Takes back the buffers of `tree`, so the next tree built from the scratch reuses them.
+    /// Buffers smaller than the ones the scratch already holds for the same layer are dropped.
+    pub fn recycle<F: PrimeField, H: TreeHasher<F, Output = T>, B: GoodAllocator>(
+        &mut self,
+        tree: MerkleTreeWithCap<F, H, A, B>,
+    ) {
+        let layers =
+            std::iter::once(tree.leaf_hashes).chain(tree.node_hashes_enumerated_from_leafs);
+        for (idx, mut layer) in layers.enumerate() {
+            layer.clear();
+            match self.layers.get_mut(idx) {
+                Some(current) if current.capacity() < layer.capacity() => *current = layer,
+                Some(_) => {}
+                None => self.layers.push(layer),
+            }
+        }
+    }
+
+    fn take_layer(&mut self, idx: usize) -> Vec<T, A> {
+        std::mem::replace(&mut self.layers[idx], Vec::new_in(A::default()))
+    }
+
+    fn prepare(&mut self, tree_size: usize, num_layers: usize) {
+        while self.layers.len() < num_layers {
+            self.layers.push(Vec::new_in(A::default()));
+        }
+        for (idx, layer) in self.layers[..num_layers].iter_mut().enumerate() {
+            layer.clear();
+            layer.reserve_exact(tree_size >> idx);
+        }
+    }
+}
+
+impl<T, A: GoodAllocator> Default for MerkleScratch<T, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Debug, PartialEq(bound = ""), Eq)]
 pub struct MerkleTreeWithCap<
@@ -311,7 +379,6 @@ impl<F: PrimeField, H: TreeHasher<F>, A: GoodAllocator, B: GoodAllocator>
 
         let now = std::time::Instant::now();
 
-        let num_sources = leafs_sources.len();
         let poly_size = leafs_sources[0].len();
         assert!(poly_size % elements_to_take_per_leaf == 0);
         let tree_size = poly_size / elements_to_take_per_leaf;
@@ -332,12 +399,123 @@ impl<F: PrimeField, H: TreeHasher<F>, A: GoodAllocator, B: GoodAllocator>
 
         let mut leaf_hashes = Vec::with_capacity_in(tree_size, A::default());
 
-        worker.scope(tree_size, |scope, dst_chunk_size| {
+        Self::hash_leafs_from_flat_sources(
+            leafs_sources,
+            elements_to_take_per_leaf,
+            &mut leaf_hashes.spare_capacity_mut()[..tree_size],
+            worker,
+        );
+
+        unsafe { leaf_hashes.set_len(tree_size) };
+
+        log!(
+            "Merkle tree of size 2^{} leaf hashes taken {:?}",
+            tree_size.trailing_zeros(),
+            now.elapsed()
+        );
+
+        let num_layers_to_construct = tree_depth - layers_to_skip;
+
+        Self::continue_from_leaf_hashes(leaf_hashes, num_layers_to_construct, cap_size, worker)
+    }
+
+    /// Same as [`Self::construct_by_chunking_from_flat_sources`], but leaf hashes and all the node
+    /// layers are placed into the buffers of `scratch` instead of fresh allocations. Once the
+    /// scratch has grown to the largest tree in use and the trees are given back with
+    /// [`MerkleScratch::recycle`], repeated calls only allocate the short list of node layers.
+    pub fn construct_by_chunking_from_flat_sources_with_scratch(
+        leafs_sources: &Vec<&Vec<F, A>, B>,
+        elements_to_take_per_leaf: usize,
+        cap_size: usize,
+        scratch: &mut MerkleScratch<H::Output, A>,
+        worker: &Worker,
+    ) -> Self {
+        debug_assert!(cap_size > 0);
+        debug_assert!(cap_size.is_power_of_two());
+        debug_assert!(elements_to_take_per_leaf.is_power_of_two());
+
+        let poly_size = leafs_sources[0].len();
+        assert!(poly_size % elements_to_take_per_leaf == 0);
+        let tree_size = poly_size / elements_to_take_per_leaf;
+
+        debug_assert!(tree_size.is_power_of_two());
+        assert!(
+            tree_size >= cap_size,
+            "trying to make tree of size {}, while requested cap size is {}",
+            tree_size,
+            cap_size
+        );
+        let num_layers_to_construct =
+            (tree_size.trailing_zeros() - cap_size.trailing_zeros()) as usize;
+
+        scratch.prepare(tree_size, num_layers_to_construct + 1);
+
+        let mut leaf_hashes = scratch.take_layer(0);
+        Self::hash_leafs_from_flat_sources(
+            leafs_sources,
+            elements_to_take_per_leaf,
+            &mut leaf_hashes.spare_capacity_mut()[..tree_size],
+            worker,
+        );
+        unsafe { leaf_hashes.set_len(tree_size) };
+
+        let mut node_hashes_enumerated_from_leafs =
+            Vec::with_capacity_in(num_layers_to_construct, B::default());
+        for layer_idx in 1..=num_layers_to_construct {
+            let previous = node_hashes_enumerated_from_leafs
+                .last()
+                .unwrap_or(&leaf_hashes);
+            let mut next = scratch.take_layer(layer_idx);
+            let next_layer_len = previous.len() / 2;
+            Self::hash_nodes_layer(
+                previous,
+                &mut next.spare_capacity_mut()[..next_layer_len],
+                worker,
+            );
+            unsafe { next.set_len(next_layer_len) };
+
+            node_hashes_enumerated_from_leafs.push(next);
+        }
+
+        Self { cap_size, leaf_hashes, node_hashes_enumerated_from_leafs }
+    }
+
+    /// Same as [`Self::construct_by_chunking_from_flat_sources_with_scratch`] followed by
+    /// [`Self::get_cap`], for when only the cap is needed. The tree is given back to `scratch`
+    /// right away.
+    pub fn compute_cap_by_chunking_from_flat_sources_with_scratch(
+        leafs_sources: &Vec<&Vec<F, A>, B>,
+        elements_to_take_per_leaf: usize,
+        cap_size: usize,
+        scratch: &mut MerkleScratch<H::Output, A>,
+        worker: &Worker,
+    ) -> Vec<H::Output, A> {
+        let tree = Self::construct_by_chunking_from_flat_sources_with_scratch(
+            leafs_sources,
+            elements_to_take_per_leaf,
+            cap_size,
+            scratch,
+            worker,
+        );
+        let cap = tree.get_cap();
+        scratch.recycle(tree);
+
+        cap
+    }
+
+    fn hash_leafs_from_flat_sources(
+        leafs_sources: &[&Vec<F, A>],
+        elements_to_take_per_leaf: usize,
+        dst: &mut [MaybeUninit<H::Output>],
+        worker: &Worker,
+    ) {
+        let num_sources = leafs_sources.len();
+        let poly_size = leafs_sources[0].len();
+        debug_assert_eq!(poly_size, dst.len() * elements_to_take_per_leaf);
+
+        worker.scope(dst.len(), |scope, dst_chunk_size| {
             let src_chunk_size = dst_chunk_size * elements_to_take_per_leaf;
-            for (dst_idx, dst) in leaf_hashes.spare_capacity_mut()[..tree_size]
-                .chunks_mut(dst_chunk_size)
-                .enumerate()
-            {
+            for (dst_idx, dst) in dst.chunks_mut(dst_chunk_size).enumerate() {
                 scope.spawn(move |_| {
                     let mut sources = Vec::with_capacity(num_sources);
                     let start = dst_idx * src_chunk_size;
@@ -365,18 +543,27 @@ impl<F: PrimeField, H: TreeHasher<F>, A: GoodAllocator, B: GoodAllocator>
                 })
             }
         });
+    }
 
-        unsafe { leaf_hashes.set_len(tree_size) };
-
-        log!(
-            "Merkle tree of size 2^{} leaf hashes taken {:?}",
-            tree_size.trailing_zeros(),
-            now.elapsed()
-        );
-
-        let num_layers_to_construct = tree_depth - layers_to_skip;
-
-        Self::continue_from_leaf_hashes(leaf_hashes, num_layers_to_construct, cap_size, worker)
+    fn hash_nodes_layer(
+        previous: &[H::Output],
+        dst: &mut [MaybeUninit<H::Output>],
+        worker: &Worker,
+    ) {
+        debug_assert_eq!(previous.len(), dst.len() * 2);
+        worker.scope(dst.len(), |scope, chunk_size| {
+            for (dst, src) in dst
+                .chunks_mut(chunk_size)
+                .zip(previous.chunks(chunk_size * 2))
+            {
+                scope.spawn(move |_| {
+                    for (dst, src) in dst.iter_mut().zip(src.array_chunks::<2>()) {
+                        let [left, right] = src;
+                        dst.write(H::hash_into_node(left, right, 0));
+                    }
+                });
+            }
+        });
     }
 
     fn continue_from_leaf_hashes(
@@ -407,19 +594,11 @@ impl<F: PrimeField, H: TreeHasher<F>, A: GoodAllocator, B: GoodAllocator>
             let mut new_layer_node_hashes: Vec<H::Output, A> =
                 Vec::with_capacity_in(next_layer_len, A::default());
 
-            worker.scope(next_layer_len, |scope, chunk_size| {
-                for (dst, src) in new_layer_node_hashes.spare_capacity_mut()[..next_layer_len]
-                    .chunks_mut(chunk_size)
-                    .zip(previous.chunks(chunk_size * 2))
-                {
-                    scope.spawn(move |_| {
-                        for (dst, src) in dst.iter_mut().zip(src.array_chunks::<2>()) {
-                            let [left, right] = src;
-                            dst.write(H::hash_into_node(left, right, 0));
-                        }
-                    });
-                }
-            });
+            Self::hash_nodes_layer(
+                previous,
+                &mut new_layer_node_hashes.spare_capacity_mut()[..next_layer_len],
+                worker,
+            );
 
             unsafe { new_layer_node_hashes.set_len(next_layer_len) };
 
@@ -493,3 +672,72 @@ impl<F: PrimeField, H: TreeHasher<F>, A: GoodAllocator, B: GoodAllocator>
         cap_el == &current
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        algebraic_props::{
+            round_function::AbsorptionModeOverwrite, sponge::GoldilocksPoseidon2Sponge,
+        },
+        field::{goldilocks::GoldilocksField, rand_from_rng},
+    };
+
+    type F = GoldilocksField;
+    type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+    #[test]
+    fn test_cap_with_scratch() {
+        let worker = Worker::new_with_num_threads(4);
+        let mut rng = rand::thread_rng();
+
+        let mut scratch = MerkleScratch::default();
+        // larger tree first, so the second one fits into already grown buffers
+        for (tree_size_log_2, cap_size) in [(10, 4), (6, 1), (10, 1)] {
+            let elements_per_leaf = 4;
+            let poly_size = (1 << tree_size_log_2) * elements_per_leaf;
+            let sources: Vec<Vec<F>> = (0..3)
+                .map(|_| (0..poly_size).map(|_| rand_from_rng(&mut rng)).collect())
+                .collect();
+            let sources: Vec<&Vec<F>> = sources.iter().collect();
+
+            let tree = MerkleTreeWithCap::<F, H>::construct_by_chunking_from_flat_sources(
+                &sources,
+                elements_per_leaf,
+                cap_size,
+                &worker,
+            );
+            let cap =
+                MerkleTreeWithCap::<F, H>::compute_cap_by_chunking_from_flat_sources_with_scratch(
+                    &sources,
+                    elements_per_leaf,
+                    cap_size,
+                    &mut scratch,
+                    &worker,
+                );
+
+            assert_eq!(cap, tree.get_cap());
+            assert!(scratch.capacity() >= 1 << 10);
+
+            // the tree built in the scratch buffers gives the same proofs
+            let scratch_tree =
+                MerkleTreeWithCap::<F, H>::construct_by_chunking_from_flat_sources_with_scratch(
+                    &sources,
+                    elements_per_leaf,
+                    cap_size,
+                    &mut scratch,
+                    &worker,
+                );
+            assert_eq!(scratch_tree, tree);
+            for idx in [0, 1, (1 << tree_size_log_2) - 1] {
+                let (leaf_hash, proof) = scratch_tree.get_proof::<Global>(idx);
+                assert_eq!((leaf_hash, proof.clone()), tree.get_proof::<Global>(idx));
+                assert!(MerkleTreeWithCap::<F, H>::verify_proof_over_cap(
+                    &proof, &cap, leaf_hash, idx
+                ));
+            }
+            scratch.recycle(scratch_tree);
+            assert!(scratch.capacity() >= 1 << 10);
+        }
+    }
+}
@@ -117,6 +117,35 @@ impl From<usize> for CircuitResolverOpts {
     }
 }
 
+/// Structural problems of the resolution graph, that make it impossible to resolve all the
+/// registered resolutions. Resolutions (nodes) are identified by the order in which they were added
+/// to the resolver.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResolverError {
+    /// Resolutions that depend on each other's outputs. The nodes are listed in the dependency
+    /// order, so every node consumes an output of the previous one, and the first one consumes an
+    /// output of the last one.
+    Cycle { nodes: Vec<usize> },
+    /// The resolution waits for an input, that is neither set nor produced by any resolution.
+    MissingInput { node: usize, input: Place },
+    /// The variable is an output of more than one resolution, or of a resolution and a set value.
+    DuplicateOutput { var: Place },
+}
+
+impl std::fmt::Display for ResolverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle { nodes } => write!(f, "resolutions {:?} form a cycle", nodes),
+            Self::MissingInput { node, input } => {
+                write!(f, "resolution {} waits for {:?} that is never produced", node, input)
+            }
+            Self::DuplicateOutput { var } => write!(f, "{:?} is produced more than once", var),
+        }
+    }
+}
+
+impl std::error::Error for ResolverError {}
+
 pub trait TrackId:
     From<u64> + Into<u64> + Into<usize> + Eq + Ord + Debug + Default + Clone + Copy
 {
@@ -133,6 +162,13 @@ pub trait CircuitResolver<F: SmallField, Cfg: CSResolverConfig>:
     where
         Fn: FnOnce(&[F], &mut DstBuffer<'_, '_, F>) + Send + Sync;
     fn wait_till_resolved(&mut self);
+    /// Same as `wait_till_resolved`, but reports a malformed resolution graph as an error instead
+    /// of panicking or hanging, if the resolver is able to detect it.
+    fn try_wait_till_resolved(&mut self) -> Result<(), ResolverError> {
+        self.wait_till_resolved();
+
+        Ok(())
+    }
     fn clear(&mut self);
 }
 
@@ -0,0 +1,584 @@
+//! Batched resolutions: instances of the same gate type are collected instead of being registered
+//! one by one, split into layers of instances that don't depend on each other, and every chunk of
+//! a layer is registered as a single resolution that computes all of its instances with
+//! [`BatchedResolution::evaluate_batch`]. This is a fast lane next to the per-resolution path for
+//! wide layers of identical gates, where a vectorized batch is cheaper than many small closures.
+
+use std::any::{Any, TypeId};
+
+use super::{DeferredResolution, MtCircuitResolver};
+use crate::{
+    config::CSResolverConfig,
+    cs::{traits::cs::DstBuffer, Place},
+    dag::{resolvers::mt::sorters::ResolverSortingMode, ResolverError},
+    field::SmallField,
+};
+
+/// Maximum number of instances computed by a single resolution, so a wide layer is still spread
+/// between the resolution window threads.
+pub const BATCH_CHUNK_SIZE: usize = 256;
+
+/// Resolution function of a gate type that can compute many independent instances at once. A value
+/// of the implementing type describes one instance (e.g. holds its constants), and all instances of
+/// the type must have `NUM_INPUTS` inputs and `NUM_OUTPUTS` outputs.
+pub trait BatchedResolution<V: SmallField>: Sized + Send + Sync + 'static {
+    const NUM_INPUTS: usize;
+    const NUM_OUTPUTS: usize;
+
+    /// Computes the outputs of a single instance.
+    fn evaluate(&self, inputs: &[V], outputs: &mut [V]);
+
+    /// Computes `instances.len()` instances, none of which depends on another. `inputs` and
+    /// `outputs` hold the values of the instances one after another, `NUM_INPUTS` and
+    /// `NUM_OUTPUTS` per instance. The default computes them one by one, implementations override
+    /// it to vectorize, e.g. over the lanes of `MixedGL`.
+    fn evaluate_batch(instances: &[Self], inputs: &[V], outputs: &mut [V]) {
+        for ((instance, inputs), outputs) in instances
+            .iter()
+            .zip(inputs.chunks_exact(Self::NUM_INPUTS))
+            .zip(outputs.chunks_exact_mut(Self::NUM_OUTPUTS))
+        {
+            instance.evaluate(inputs, outputs);
+        }
+    }
+}
+
+/// Type erased instances of a single `BatchedResolution` type that are not registered yet.
+pub(super) trait PendingBatch<V>: Send + Sync {
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn len(&self) -> usize;
+    /// Inputs and outputs of the instance `ix`.
+    fn places(&self, ix: usize) -> (&[Place], &[Place]);
+    /// Number of the instance `ix` among all the batched instances, in the order they were added.
+    fn added_at(&self, ix: usize) -> usize;
+    /// Takes out all the instances as resolutions computing the instances of the same layer,
+    /// `layers[ix]` for the instance `ix`, at most [`BATCH_CHUNK_SIZE`] at a time.
+    fn take_resolutions(&mut self, layers: &[usize]) -> Vec<DeferredResolution<V>>;
+}
+
+struct Instances<G> {
+    gates: Vec<G>,
+    inputs: Vec<Place>,
+    outputs: Vec<Place>,
+    added_at: Vec<usize>,
+}
+
+impl<V: SmallField, G: BatchedResolution<V>> PendingBatch<V> for Instances<G> {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.gates.len()
+    }
+
+    fn places(&self, ix: usize) -> (&[Place], &[Place]) {
+        (
+            &self.inputs[ix * G::NUM_INPUTS..][..G::NUM_INPUTS],
+            &self.outputs[ix * G::NUM_OUTPUTS..][..G::NUM_OUTPUTS],
+        )
+    }
+
+    fn added_at(&self, ix: usize) -> usize {
+        self.added_at[ix]
+    }
+
+    fn take_resolutions(&mut self, layers: &[usize]) -> Vec<DeferredResolution<V>> {
+        assert_eq!(layers.len(), self.gates.len());
+        let gates = std::mem::take(&mut self.gates);
+        let inputs = std::mem::take(&mut self.inputs);
+        let outputs = std::mem::take(&mut self.outputs);
+        self.added_at.clear();
+
+        let mut order: Vec<usize> = (0..gates.len()).collect();
+        order.sort_by_key(|&ix| layers[ix]);
+
+        let mut gates: Vec<Option<G>> = gates.into_iter().map(Some).collect();
+        let mut result = vec![];
+        for layer in order.chunk_by(|a, b| layers[*a] == layers[*b]) {
+            for chunk in layer.chunks(BATCH_CHUNK_SIZE) {
+                let chunk_gates: Vec<G> = chunk
+                    .iter()
+                    .map(|&ix| gates[ix].take().expect("instance is in a single layer"))
+                    .collect();
+                let chunk_inputs = chunk
+                    .iter()
+                    .flat_map(|&ix| &inputs[ix * G::NUM_INPUTS..][..G::NUM_INPUTS])
+                    .copied()
+                    .collect();
+                let chunk_outputs = chunk
+                    .iter()
+                    .flat_map(|&ix| &outputs[ix * G::NUM_OUTPUTS..][..G::NUM_OUTPUTS])
+                    .copied()
+                    .collect();
+
+                result.push(DeferredResolution {
+                    inputs: chunk_inputs,
+                    outputs: chunk_outputs,
+                    f: Box::new(move |ins: &[V], out: &mut DstBuffer<'_, '_, V>| {
+                        let mut values = vec![V::ZERO; chunk_gates.len() * G::NUM_OUTPUTS];
+                        G::evaluate_batch(&chunk_gates, ins, &mut values);
+                        out.extend(values);
+                    }),
+                });
+            }
+        }
+
+        result
+    }
+}
+
+/// Assigns a layer to every batched instance, so that no instance depends on another one of the
+/// same or a later layer. Nodes are given by their inputs and outputs, `instances` first, then
+/// `others`: the resolutions that are not resolved yet and are not batched. Those are not merged,
+/// but they pass dependencies on, so an instance consuming the output of another one through them
+/// is still placed in a later layer. Instances that depend on each other in a cycle are returned
+/// as an error, as their indices in `instances` in the dependency order. Instances stuck behind a
+/// cycle that goes through `others` get a layer of their own each, so the resolver reports it.
+fn split_into_layers(
+    instances: &[(&[Place], &[Place])],
+    others: &[(&[Place], &[Place])],
+) -> Result<Vec<usize>, Vec<usize>> {
+    let num_instances = instances.len();
+    let nodes = instances.iter().chain(others.iter()).collect::<Vec<_>>();
+    let mut producers = std::collections::HashMap::new();
+    for (node, (_, outputs)) in nodes.iter().enumerate() {
+        for place in outputs.iter() {
+            producers.insert(*place, node);
+        }
+    }
+    let dependencies: Vec<Vec<usize>> = nodes
+        .iter()
+        .map(|(inputs, _)| {
+            inputs
+                .iter()
+                .filter_map(|place| producers.get(place))
+                .copied()
+                .collect()
+        })
+        .collect();
+
+    // direct cycles between the instances, these can only be reported here
+    let instance_dependencies: Vec<Vec<usize>> = dependencies[..num_instances]
+        .iter()
+        .map(|x| x.iter().copied().filter(|&x| x < num_instances).collect())
+        .collect();
+    let (_, remaining) = topological_order(&instance_dependencies);
+    if let Some(start) = (0..num_instances).find(|&ix| remaining[ix] > 0) {
+        // Every instance that is not placed still has a dependency that is not placed, so walking
+        // over those backwards we eventually come back to an instance that was already visited.
+        let mut path = vec![start];
+        let mut ix = start;
+        loop {
+            ix = instance_dependencies[ix]
+                .iter()
+                .copied()
+                .find(|&dependency| remaining[dependency] > 0)
+                .expect("instance that is not placed must have a dependency that is not placed");
+
+            if let Some(pos) = path.iter().position(|&x| x == ix) {
+                path.drain(..pos);
+                break;
+            }
+            path.push(ix);
+        }
+        path.reverse();
+
+        return Err(path);
+    }
+
+    // a node can run after all the layers its instance dependencies are in, transitively, and an
+    // instance is in the first layer it can run after
+    let (order, remaining) = topological_order(&dependencies);
+    let mut num_layers_before = vec![0; nodes.len()];
+    for node in order {
+        num_layers_before[node] = dependencies[node]
+            .iter()
+            .map(|&x| num_layers_before[x] + (x < num_instances) as usize)
+            .max()
+            .unwrap_or(0);
+    }
+    let num_layers = num_layers_before[..num_instances]
+        .iter()
+        .max()
+        .map_or(0, |x| x + 1);
+    let mut layers = num_layers_before;
+    layers.truncate(num_instances);
+    for (ix, layer) in (0..num_instances)
+        .filter(|&ix| remaining[ix] > 0)
+        .zip(num_layers..)
+    {
+        layers[ix] = layer;
+    }
+
+    Ok(layers)
+}
+
+/// Kahn's algorithm over nodes given by their dependencies. Returns the nodes in a dependency
+/// order, and for every node the number of its dependencies that are not in the order, which is
+/// positive for the nodes on or behind a cycle.
+fn topological_order(dependencies: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let mut remaining: Vec<usize> = dependencies.iter().map(|x| x.len()).collect();
+    let mut dependants = vec![vec![]; dependencies.len()];
+    for (node, dependencies) in dependencies.iter().enumerate() {
+        for &dependency in dependencies.iter() {
+            dependants[dependency].push(node);
+        }
+    }
+
+    let mut order: Vec<usize> = (0..dependencies.len())
+        .filter(|&x| remaining[x] == 0)
+        .collect();
+    let mut next = 0;
+    while next < order.len() {
+        let node = order[next];
+        next += 1;
+        for &dependant in dependants[node].iter() {
+            remaining[dependant] -= 1;
+            if remaining[dependant] == 0 {
+                order.push(dependant);
+            }
+        }
+    }
+
+    (order, remaining)
+}
+
+impl<V: SmallField, RS: ResolverSortingMode<V>, CFG: CSResolverConfig>
+    MtCircuitResolver<V, RS, CFG>
+{
+    /// Adds an instance of the gate type `G` computing `outputs` from `inputs`. It's not
+    /// registered right away, but kept with the other instances of `G` until
+    /// `flush_batched_resolutions` (called by `wait_till_resolved` too), which registers them in
+    /// layers of independent instances, [`BATCH_CHUNK_SIZE`] instances per resolution. So values
+    /// of the outputs are only available after the flush, and awaiting them before it blocks.
+    pub fn add_batched_resolution<G: BatchedResolution<V>>(
+        &mut self,
+        gate: G,
+        inputs: &[Place],
+        outputs: &[Place],
+    ) {
+        assert!(
+            G::NUM_INPUTS > 0 && G::NUM_OUTPUTS > 0,
+            "batched gates must have inputs and outputs"
+        );
+        assert_eq!(inputs.len(), G::NUM_INPUTS, "invalid number of inputs");
+        assert_eq!(outputs.len(), G::NUM_OUTPUTS, "invalid number of outputs");
+
+        let added_at = self.batches.iter().map(|(_, batch)| batch.len()).sum();
+        let type_id = TypeId::of::<G>();
+        let ix = match self.batches.iter().position(|(id, _)| *id == type_id) {
+            Some(ix) => ix,
+            None => {
+                let instances: Instances<G> =
+                    Instances { gates: vec![], inputs: vec![], outputs: vec![], added_at: vec![] };
+                self.batches.push((type_id, Box::new(instances)));
+                self.batches.len() - 1
+            }
+        };
+        let instances = self.batches[ix]
+            .1
+            .as_any_mut()
+            .downcast_mut::<Instances<G>>()
+            .expect("batch holds instances of its type");
+        instances.gates.push(gate);
+        instances.inputs.extend_from_slice(inputs);
+        instances.outputs.extend_from_slice(outputs);
+        instances.added_at.push(added_at);
+    }
+
+    /// Registers all the instances added with `add_batched_resolution` so far, gate types in the
+    /// order they were first added. Overrides apply to them as to any other resolution. Instances
+    /// are layered together with the resolutions that wait for an input or are deferred, so an
+    /// instance that depends on another one through those is never computed together with it. If
+    /// instances depend on each other in a cycle, nothing is registered, all the instances stay
+    /// pending and the cycle is returned as [`ResolverError::Cycle`], with the instances
+    /// identified by the order in which they were added since the last flush.
+    pub fn flush_batched_resolutions(&mut self) -> Result<(), ResolverError> {
+        if self.batches.iter().all(|(_, batch)| batch.len() == 0) {
+            return Ok(());
+        }
+
+        let pending = self.sorter.pending_resolutions();
+        let layers = {
+            let instances = self
+                .batches
+                .iter()
+                .flat_map(|(_, batch)| (0..batch.len()).map(move |ix| batch.places(ix)))
+                .collect::<Vec<_>>();
+            let others = pending
+                .iter()
+                .map(|x| (&x.inputs[..], &x.outputs[..]))
+                .chain(
+                    self.deferred
+                        .iter()
+                        .map(|x| (&x.inputs[..], &x.outputs[..])),
+                )
+                .collect::<Vec<_>>();
+
+            split_into_layers(&instances, &others).map_err(|cycle| {
+                let added_at = self
+                    .batches
+                    .iter()
+                    .flat_map(|(_, batch)| (0..batch.len()).map(move |ix| batch.added_at(ix)))
+                    .collect::<Vec<_>>();
+                ResolverError::Cycle { nodes: cycle.into_iter().map(|x| added_at[x]).collect() }
+            })?
+        };
+
+        let mut resolutions = vec![];
+        let mut start = 0;
+        for (_, batch) in self.batches.iter_mut() {
+            let len = batch.len();
+            resolutions.extend(batch.take_resolutions(&layers[start..][..len]));
+            start += len;
+        }
+        for DeferredResolution { inputs, outputs, f } in resolutions {
+            self.add_resolution(&inputs, &outputs, move |ins, out| f(ins, out));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+    use crate::{
+        config::{DoPerformRuntimeAsserts, Resolver},
+        cs::Variable,
+        dag::{
+            resolvers::mt::sorters::sorter_live::LiveResolverSorter, CircuitResolverOpts,
+            WitnessSource as _,
+        },
+        field::{
+            goldilocks::{GoldilocksField, MixedGL},
+            traits::field_like::{PrimeFieldLike, PrimeFieldLikeVectorized},
+            Field,
+        },
+    };
+
+    type F = GoldilocksField;
+    type Cfg = Resolver<DoPerformRuntimeAsserts>;
+
+    static NUM_VECTORIZED: AtomicUsize = AtomicUsize::new(0);
+
+    // `out = q * a * b + l * c`, with `MixedGL` over every full group of lanes
+    struct FmaGate {
+        q: F,
+        l: F,
+    }
+
+    impl BatchedResolution<F> for FmaGate {
+        const NUM_INPUTS: usize = 3;
+        const NUM_OUTPUTS: usize = 1;
+
+        fn evaluate(&self, inputs: &[F], outputs: &mut [F]) {
+            let mut result = inputs[0];
+            Field::mul_assign(&mut result, &inputs[1]);
+            Field::mul_assign(&mut result, &self.q);
+            let mut linear = inputs[2];
+            Field::mul_assign(&mut linear, &self.l);
+            Field::add_assign(&mut result, &linear);
+            outputs[0] = result;
+        }
+
+        fn evaluate_batch(instances: &[Self], inputs: &[F], outputs: &mut [F]) {
+            fn lanes(f: impl FnMut(usize) -> F) -> MixedGL {
+                MixedGL::from_array(std::array::from_fn(f))
+            }
+
+            let width = MixedGL::SIZE_FACTOR;
+            let mut chunks = instances.chunks_exact(width);
+            for (ix, chunk) in (&mut chunks).enumerate() {
+                let start = ix * width;
+                let a = lanes(|j| inputs[(start + j) * 3]);
+                let b = lanes(|j| inputs[(start + j) * 3 + 1]);
+                let c = lanes(|j| inputs[(start + j) * 3 + 2]);
+                let q = lanes(|j| chunk[j].q);
+                let l = lanes(|j| chunk[j].l);
+
+                let mut result = a;
+                result.mul_assign(&b, &mut ()).mul_assign(&q, &mut ());
+                let mut linear = c;
+                linear.mul_assign(&l, &mut ());
+                result.add_assign(&linear, &mut ());
+                outputs[start..][..width].copy_from_slice(&result.0);
+            }
+            NUM_VECTORIZED.fetch_add(instances.len() / width, Ordering::Relaxed);
+
+            let start = instances.len() - chunks.remainder().len();
+            for (ix, instance) in chunks.remainder().iter().enumerate() {
+                let ix = start + ix;
+                instance.evaluate(&inputs[ix * 3..][..3], &mut outputs[ix..][..1]);
+            }
+        }
+    }
+
+    #[test]
+    fn batched_layer_matches_per_node() {
+        let num_gates = 1000;
+        let num_inputs = 3 * num_gates;
+        let max_variables = num_inputs + 2 * num_gates;
+        let opts = CircuitResolverOpts { max_variables, desired_parallelism: 16 };
+        let mut batched = MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(opts);
+        let mut per_node = MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(opts);
+
+        let place = |ix: usize| Place::from_variable(Variable::from_variable_index(ix as u64));
+        for ix in 0..num_inputs {
+            let value = F::from_u64_with_reduction(ix as u64 * 0x1234567 + 1);
+            batched.set_value(place(ix), value);
+            per_node.set_value(place(ix), value);
+        }
+
+        let num_calls = Arc::new(AtomicUsize::new(0));
+        // a wide layer of independent gates, and a second one consuming its outputs
+        for layer in 0..2 {
+            for ix in 0..num_gates {
+                let gate = || FmaGate {
+                    q: F::from_u64_with_reduction(ix as u64 + 2),
+                    l: F::from_u64_with_reduction(layer as u64 + 3),
+                };
+                let inputs = match layer {
+                    0 => [place(3 * ix), place(3 * ix + 1), place(3 * ix + 2)],
+                    _ => [
+                        place(num_inputs + ix),
+                        place(3 * ix),
+                        place(num_inputs + (ix + 1) % num_gates),
+                    ],
+                };
+                let outputs = [place(num_inputs + layer * num_gates + ix)];
+
+                batched.add_batched_resolution(gate(), &inputs, &outputs);
+                let gate = gate();
+                let num_calls = num_calls.clone();
+                per_node.add_resolution(
+                    &inputs,
+                    &outputs,
+                    move |ins: &[F], out: &mut DstBuffer<F>| {
+                        num_calls.fetch_add(1, Ordering::Relaxed);
+                        let mut value = [F::ZERO];
+                        gate.evaluate(ins, &mut value);
+                        out.push(value[0]);
+                    },
+                );
+            }
+        }
+
+        NUM_VECTORIZED.store(0, Ordering::Relaxed);
+        batched.wait_till_resolved();
+        per_node.wait_till_resolved();
+
+        assert_eq!(num_calls.load(Ordering::Relaxed), 2 * num_gates);
+        assert_eq!(
+            NUM_VECTORIZED.load(Ordering::Relaxed),
+            2 * (num_gates / BATCH_CHUNK_SIZE * (BATCH_CHUNK_SIZE / MixedGL::SIZE_FACTOR)
+                + num_gates % BATCH_CHUNK_SIZE / MixedGL::SIZE_FACTOR)
+        );
+        for ix in num_inputs..max_variables {
+            assert_eq!(
+                batched.get_value_unchecked(place(ix)),
+                per_node.get_value_unchecked(place(ix)),
+                "invalid value of variable {}",
+                ix
+            );
+        }
+    }
+
+    fn instances(inputs: &[Place], outputs: &[Place]) -> Vec<(&[Place], &[Place])> {
+        let num_inputs = inputs.len() / outputs.len();
+        inputs
+            .chunks_exact(num_inputs)
+            .zip(outputs.chunks_exact(1))
+            .collect()
+    }
+
+    #[test]
+    fn split_into_layers_respects_dependencies() {
+        let place = |ix: usize| Place::from_variable(Variable::from_variable_index(ix as u64));
+        // instance 0 consumes the output of instance 2, instance 1 consumes outputs of 0 and 3
+        let inputs =
+            [place(12), place(0), place(10), place(13), place(1), place(2), place(3), place(4)];
+        let outputs = [place(10), place(11), place(12), place(13)];
+
+        let layers = split_into_layers(&instances(&inputs, &outputs), &[]);
+        assert_eq!(layers, Ok(vec![1, 2, 0, 0]));
+    }
+
+    #[test]
+    fn split_into_layers_follows_other_resolutions() {
+        let place = |ix: usize| Place::from_variable(Variable::from_variable_index(ix as u64));
+        // instance 1 consumes the output of instance 0 only through two other resolutions, while
+        // instance 2 waits behind a cycle of other resolutions
+        let inputs = [place(0), place(12), place(21)];
+        let outputs = [place(10), place(13), place(14)];
+        let others: [(&[Place], &[Place]); 4] = [
+            (&[place(10)], &[place(11)]),
+            (&[place(11)], &[place(12)]),
+            (&[place(20)], &[place(21)]),
+            (&[place(21)], &[place(20)]),
+        ];
+
+        let layers = split_into_layers(&instances(&inputs, &outputs), &others);
+        assert_eq!(layers, Ok(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn split_into_layers_reports_cycle() {
+        let place = |ix: usize| Place::from_variable(Variable::from_variable_index(ix as u64));
+        // instance 0 only depends on outside places, while 1 consumes the outputs of 0 and 3,
+        // 2 consumes the output of 1 and 3 consumes the output of 2
+        let inputs =
+            [place(0), place(1), place(10), place(13), place(11), place(0), place(12), place(0)];
+        let outputs = [place(10), place(11), place(12), place(13)];
+
+        let layers = split_into_layers(&instances(&inputs, &outputs), &[]);
+        assert_eq!(layers, Err(vec![2, 3, 1]));
+    }
+
+    #[test]
+    fn batched_dependency_through_regular_resolution() {
+        let opts = CircuitResolverOpts { max_variables: 100, desired_parallelism: 16 };
+        let mut resolver = MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(opts);
+
+        let place = |ix: usize| Place::from_variable(Variable::from_variable_index(ix as u64));
+        let gate = || FmaGate { q: F::ONE, l: F::ONE };
+        // A computes 1 from 0, a regular resolution doubles it into 2, and B computes 3 from 2, so
+        // A and B are independent within the batch, but must not be computed together
+        resolver.set_value(place(0), F::from_u64_with_reduction(3));
+        resolver.add_batched_resolution(gate(), &[place(0), place(0), place(0)], &[place(1)]);
+        resolver.add_resolution(&[place(1)], &[place(2)], |ins: &[F], out: &mut DstBuffer<F>| {
+            let mut value = ins[0];
+            Field::double(&mut value);
+            out.push(value);
+        });
+        resolver.add_batched_resolution(gate(), &[place(2), place(0), place(0)], &[place(3)]);
+        resolver.wait_till_resolved();
+
+        // A: 3 * 3 + 3 = 12, doubled to 24, B: 24 * 3 + 3 = 75
+        assert_eq!(resolver.get_value_unchecked(place(3)), F::from_u64_with_reduction(75));
+    }
+
+    #[test]
+    fn batched_cycle_is_reported() {
+        let opts = CircuitResolverOpts { max_variables: 100, desired_parallelism: 16 };
+        let mut resolver = MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(opts);
+
+        let place = |ix: usize| Place::from_variable(Variable::from_variable_index(ix as u64));
+        let gate = || FmaGate { q: F::ONE, l: F::ONE };
+        resolver.set_value(place(0), F::ONE);
+        resolver.add_batched_resolution(gate(), &[place(0), place(0), place(2)], &[place(1)]);
+        resolver.add_batched_resolution(gate(), &[place(0), place(0), place(1)], &[place(2)]);
+
+        // the instances stay pending, so the cycle is reported again
+        let cycle = Err(ResolverError::Cycle { nodes: vec![1, 0] });
+        assert_eq!(resolver.flush_batched_resolutions(), cycle);
+        assert_eq!(resolver.batches[0].1.len(), 2);
+        assert_eq!(resolver.try_wait_till_resolved(), cycle);
+    }
+}
@@ -0,0 +1,151 @@
+//! Constraint checks: relations over places that must evaluate to zero on the resolved values,
+//! registered next to the resolutions and evaluated only on request with
+//! `MtCircuitResolver::check_constraints`. The resolver only holds resolution functions and not
+//! the gates they came from, so the relations are supplied by the caller, e.g. a gate registers
+//! its constraint together with its resolution when the check is enabled.
+
+use super::MtCircuitResolver;
+use crate::{
+    config::CSResolverConfig,
+    cs::Place,
+    dag::{resolvers::mt::sorters::ResolverSortingMode, WitnessSource as _},
+    field::SmallField,
+};
+
+/// A constraint check that doesn't evaluate to zero on the resolved values. The check is
+/// identified by the order in which it was added, and `residual` is the canonical value it
+/// evaluates to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConstraintViolation {
+    pub check: usize,
+    pub residual: u64,
+}
+
+impl std::fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "constraint check {} is violated with residual {}", self.check, self.residual)
+    }
+}
+
+impl std::error::Error for ConstraintViolation {}
+
+pub(super) struct ConstraintCheck<V> {
+    inputs: Vec<Place>,
+    relation: Box<dyn Fn(&[V]) -> V + Send + Sync>,
+}
+
+impl<V: SmallField, RS: ResolverSortingMode<V> + 'static, CFG: CSResolverConfig>
+    MtCircuitResolver<V, RS, CFG>
+{
+    /// Adds a check that `relation` evaluates to zero on the values of `inputs`. Checks are
+    /// identified by the order in which they were added, and are never evaluated during the
+    /// resolution, so they cost nothing unless `check_constraints` is called.
+    pub fn add_constraint_check<F>(&mut self, inputs: &[Place], relation: F)
+    where
+        F: Fn(&[V]) -> V + Send + Sync + 'static,
+    {
+        self.checks
+            .push(ConstraintCheck { inputs: inputs.to_vec(), relation: Box::new(relation) });
+    }
+
+    /// Evaluates the checks added with `add_constraint_check` on the resolved values, in the order
+    /// they were added, and returns the first one that doesn't hold. Must be called after
+    /// `wait_till_resolved`, and panics if an input of a check is not resolved.
+    pub fn check_constraints(&self) -> Result<(), ConstraintViolation> {
+        let mut values = vec![];
+        for (ix, check) in self.checks.iter().enumerate() {
+            values.clear();
+            values.extend(check.inputs.iter().map(|input| {
+                self.try_get_value(*input).unwrap_or_else(|| {
+                    panic!("input {:?} of constraint check {} is not resolved", input, ix)
+                })
+            }));
+
+            let residual = (check.relation)(&values);
+            if !residual.is_zero() {
+                return Err(ConstraintViolation { check: ix, residual: residual.as_u64_reduced() });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        config::{DoPerformRuntimeAsserts, Resolver},
+        cs::{traits::cs::DstBuffer, Variable},
+        dag::{resolvers::mt::sorters::sorter_live::LiveResolverSorter, CircuitResolverOpts},
+        field::{goldilocks::GoldilocksField, Field},
+    };
+
+    type F = GoldilocksField;
+    type Cfg = Resolver<DoPerformRuntimeAsserts>;
+
+    // `c = a * b` followed by `d = c + a`, with the constraints of both gates
+    fn populate(resolver: &mut MtCircuitResolver<F, LiveResolverSorter<F, Cfg>, Cfg>) {
+        let place = |ix: u64| Place::from_variable(Variable::from_variable_index(ix));
+        let (a, b, c, d) = (place(0), place(1), place(2), place(3));
+
+        resolver.set_value(a, F::from_u64_with_reduction(3));
+        resolver.set_value(b, F::from_u64_with_reduction(5));
+        resolver.add_resolution(&[a, b], &[c], |ins: &[F], outs: &mut DstBuffer<F>| {
+            let mut value = ins[0];
+            value.mul_assign(&ins[1]);
+            outs.push(value);
+        });
+        resolver.add_resolution(&[c, a], &[d], |ins: &[F], outs: &mut DstBuffer<F>| {
+            let mut value = ins[0];
+            value.add_assign(&ins[1]);
+            outs.push(value);
+        });
+
+        resolver.add_constraint_check(&[a, b, c], |values: &[F]| {
+            let mut residual = values[0];
+            residual.mul_assign(&values[1]).sub_assign(&values[2]);
+            residual
+        });
+        resolver.add_constraint_check(&[c, a, d], |values: &[F]| {
+            let mut residual = values[0];
+            residual.add_assign(&values[1]).sub_assign(&values[2]);
+            residual
+        });
+    }
+
+    #[test]
+    fn consistent_witness_passes_checks() {
+        let mut resolver =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+        populate(&mut resolver);
+        resolver.wait_till_resolved();
+
+        assert_eq!(resolver.check_constraints(), Ok(()));
+    }
+
+    #[test]
+    fn checks_pinpoint_inconsistent_witness() {
+        let mut resolver =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+        // `c` is 16 instead of 15, so the first gate is violated, while the second one is
+        // consistent with the wrong value
+        resolver.set_override(
+            Place::from_variable(Variable::from_variable_index(2)),
+            F::from_u64_with_reduction(16),
+        );
+        populate(&mut resolver);
+        resolver.wait_till_resolved();
+
+        assert_eq!(
+            resolver.check_constraints(),
+            Err(ConstraintViolation { check: 0, residual: F::ORDER - 1 })
+        );
+    }
+}
@@ -1,10 +1,13 @@
+pub mod batched;
+pub mod checks;
 mod registrar;
 mod resolution_window;
 pub mod sorters;
 
 use std::{
-    any::Any,
+    any::{Any, TypeId},
     cell::{Cell, UnsafeCell},
+    collections::HashMap,
     marker::PhantomData,
     panic::resume_unwind,
     sync::{
@@ -14,21 +17,25 @@ use std::{
     thread::JoinHandle,
 };
 
+use itertools::Itertools as _;
+
 use self::{
+    batched::PendingBatch,
+    checks::ConstraintCheck,
     resolution_window::ResolutionWindow,
-    sorters::{ResolutionRecord, ResolverSortingMode},
+    sorters::{PendingResolution, ResolutionRecord, ResolverSortingMode},
 };
 use crate::{
     config::CSResolverConfig,
     cs::{
         traits::cs::{CSWitnessSource, DstBuffer},
-        Place,
+        Place, Variable, Witness,
     },
     dag::{
         awaiters::{self, AwaitersBroker},
-        primitives::{ExecOrder, Values},
+        primitives::{ExecOrder, ResolverIxType, Values},
         resolver_box::ResolverBox,
-        CircuitResolver, WitnessSource, WitnessSourceAwaitable,
+        CircuitResolver, ResolverError, WitnessSource, WitnessSourceAwaitable,
     },
     field::SmallField,
     log,
@@ -96,6 +103,15 @@ pub struct ResolverCommonData<V, T: Default> {
 ///    Those indicies are not monotonic and act akin to pointers, and thus are
 ///    Unsafe to work with.
 
+/// Resolution added with `add_deferred_resolution`, that is not handed to the sorter until one of
+/// the targets of `resolve_targets` depends on it. Also a chunk of batched resolutions waiting for
+/// `flush_batched_resolutions`.
+struct DeferredResolution<V> {
+    inputs: Vec<Place>,
+    outputs: Vec<Place>,
+    f: Box<dyn FnOnce(&[V], &mut DstBuffer<'_, '_, V>) + Send + Sync>,
+}
+
 pub struct MtCircuitResolver<V: SmallField, RS: ResolverSortingMode<V>, CFG: CSResolverConfig> {
     sorter: RS,
 
@@ -106,6 +122,10 @@ pub struct MtCircuitResolver<V: SmallField, RS: ResolverSortingMode<V>, CFG: CSR
     stats: Stats,
     call_count: u32,
     debug_track: Vec<Place>,
+    overrides: HashMap<Place, V>,
+    deferred: Vec<DeferredResolution<V>>,
+    batches: Vec<(TypeId, Box<dyn PendingBatch<V>>)>,
+    checks: Vec<ConstraintCheck<V>>,
     phantom: PhantomData<CFG>,
 }
 
@@ -152,6 +172,10 @@ where
         self.wait_till_resolved()
     }
 
+    fn try_wait_till_resolved(&mut self) -> Result<(), ResolverError> {
+        self.try_wait_till_resolved()
+    }
+
     fn clear(&mut self) {
         self.clear()
     }
@@ -198,11 +222,16 @@ impl<V: SmallField, RS: ResolverSortingMode<V>, CFG: CSResolverConfig>
             common,
             stats: Stats::new(),
             debug_track,
+            overrides: HashMap::new(),
+            deferred: vec![],
+            batches: vec![],
+            checks: vec![],
             phantom: PhantomData,
         }
     }
 
     pub fn set_value(&mut self, key: Place, value: V) {
+        let value = self.overrides.get(&key).copied().unwrap_or(value);
         self.sorter.set_value(key, value)
     }
 
@@ -210,13 +239,165 @@ impl<V: SmallField, RS: ResolverSortingMode<V>, CFG: CSResolverConfig>
     where
         F: FnOnce(&[V], &mut DstBuffer<'_, '_, V>) + Send + Sync,
     {
-        self.sorter.add_resolution(inputs, outputs, f)
+        if self.overrides.is_empty() {
+            return self.sorter.add_resolution(inputs, outputs, f);
+        }
+
+        let overridden: Vec<(usize, V)> = outputs
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, key)| self.overrides.get(key).map(|value| (idx, *value)))
+            .collect();
+
+        if overridden.is_empty() {
+            return self.sorter.add_resolution(inputs, outputs, f);
+        }
+
+        let num_outputs = outputs.len();
+        self.sorter.add_resolution(
+            inputs,
+            outputs,
+            move |ins: &[V], out: &mut DstBuffer<'_, '_, V>| {
+                let mut values = Vec::with_capacity(num_outputs);
+                f(ins, &mut DstBuffer::Vector(&mut values));
+                for (idx, value) in overridden.iter() {
+                    values[*idx] = *value;
+                }
+                out.extend(values);
+            },
+        )
+    }
+
+    /// Pins the value of `key`, for debugging and fault injection. Everything that depends on
+    /// `key` is resolved from the pinned value. The override applies to values set and
+    /// resolutions added after this call:
+    /// - if `key` is set with `set_value`, the set value is ignored;
+    /// - if `key` is an output of a resolution, the resolution still runs, as its other outputs may
+    ///   be needed, but its value for `key` is replaced.
+    ///
+    /// So overrides must be set before `key` is set or produced, and this panics if `key` is
+    /// already tracked by the resolver. Overriding the same key again replaces the value.
+    pub fn set_override(&mut self, key: Place, value: V) {
+        // Safety: Dereferencing as & in &mut self context, only metadata is accessed.
+        let values = unsafe { self.common.values.u_deref() };
+        assert!(
+            key.raw_ix() >= values.variables.len() || !values.get_item_ref(key).1.is_tracked(),
+            "can not override {:?}, its value is already set or produced",
+            key
+        );
+
+        self.overrides.insert(key, value);
+    }
+
+    /// Same as `add_resolution`, but the resolution is only kept aside, and is added (and so
+    /// computed) once a target of `resolve_targets` depends on it. Deferred resolutions that no
+    /// target depends on are never computed, so their outputs stay unresolved after
+    /// `wait_till_resolved`.
+    pub fn add_deferred_resolution<F>(&mut self, inputs: &[Place], outputs: &[Place], f: F)
+    where
+        F: FnOnce(&[V], &mut DstBuffer<'_, '_, V>) + Send + Sync + 'static,
+    {
+        self.deferred.push(DeferredResolution {
+            inputs: inputs.to_vec(),
+            outputs: outputs.to_vec(),
+            f: Box::new(f),
+        });
+    }
+
+    /// Adds the deferred resolutions that `targets` transitively depend on, found with a backward
+    /// pass from the targets over their producers, in the order they were deferred. The other ones
+    /// stay deferred, so this can be called again for more targets. Overrides apply to the added
+    /// resolutions as to any other. Values are available as usual, e.g. after
+    /// `wait_till_resolved`.
+    pub fn resolve_targets(&mut self, targets: &[Place]) {
+        let mut producers = HashMap::new();
+        for (ix, resolution) in self.deferred.iter().enumerate() {
+            for output in resolution.outputs.iter() {
+                producers.insert(*output, ix);
+            }
+        }
+
+        let mut needed = vec![false; self.deferred.len()];
+        let mut stack = targets
+            .iter()
+            .filter_map(|x| producers.get(x))
+            .copied()
+            .collect_vec();
+        while let Some(ix) = stack.pop() {
+            if std::mem::replace(&mut needed[ix], true) {
+                continue;
+            }
+            stack.extend(
+                self.deferred[ix]
+                    .inputs
+                    .iter()
+                    .filter_map(|x| producers.get(x))
+                    .copied(),
+            );
+        }
+
+        for (resolution, needed) in std::mem::take(&mut self.deferred).into_iter().zip(needed) {
+            if needed {
+                let DeferredResolution { inputs, outputs, f } = resolution;
+                self.add_resolution(&inputs, &outputs, move |ins, out| f(ins, out));
+            } else {
+                self.deferred.push(resolution);
+            }
+        }
     }
 
     pub fn wait_till_resolved(&mut self) {
+        if !self
+            .comms
+            .registration_complete
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            if let Err(error) = self.flush_batched_resolutions() {
+                panic!("{}", error);
+            }
+        }
+
         self.wait_till_resolved_impl(true);
     }
 
+    /// Same as `wait_till_resolved`, but checks the registrations that are still delayed at this
+    /// point first. Those can't ever be resolved, so instead of panicking an error describing the
+    /// reason is returned, and the resolver is shut down after resolving everything else.
+    pub fn try_wait_till_resolved(&mut self) -> Result<(), ResolverError> {
+        if self
+            .comms
+            .registration_complete
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return Ok(());
+        }
+
+        let error = match self.flush_batched_resolutions() {
+            Ok(()) => {
+                let pending = self.sorter.pending_resolutions();
+
+                if pending.is_empty() {
+                    self.wait_till_resolved_impl(true);
+
+                    return Ok(());
+                }
+
+                // Safety: Dereferencing as & in &mut self context, only metadata is accessed.
+                let values = unsafe { self.common.values.u_deref() };
+                find_resolution_error(&pending, |x| {
+                    x.raw_ix() < values.variables.len() && values.get_item_ref(x).1.is_tracked()
+                })
+            }
+            Err(error) => error,
+        };
+
+        // The delayed registrations stay in the registrar, so only the accepted ones are flushed.
+        self.sorter.flush();
+        self.complete_registration();
+
+        Err(error)
+    }
+
     pub fn wait_till_resolved_impl(&mut self, report: bool) {
         if self
             .comms
@@ -228,6 +409,31 @@ impl<V: SmallField, RS: ResolverSortingMode<V>, CFG: CSResolverConfig>
 
         self.sorter.final_flush();
 
+        if !self.complete_registration() {
+            return;
+        }
+
+        match report {
+            true => {
+                log!("CR stats {:#?}", self.stats);
+            }
+            false if cfg!(test) || cfg!(debug_assertions) => {
+                print!(" resolution time {:?}...", self.stats.total_resolution_time);
+            }
+            _ => {}
+        }
+
+        self.sorter.write_sequence();
+
+        if cfg!(feature = "cr_paranoia_mode") || PARANOIA {
+            log!("CR {:?}", unsafe { self.common.awaiters_broker.stats.u_deref() });
+        }
+    }
+
+    /// Marks the registration as complete, waits for the resolution window to resolve everything
+    /// that was handed to it and records the timings. A panic of the resolution window is resumed
+    /// here, and `false` is returned if it panicked without a stored payload.
+    fn complete_registration(&mut self) -> bool {
         self.stats.registration_time = self.stats.started_at.elapsed();
 
         self.comms
@@ -252,25 +458,11 @@ impl<V: SmallField, RS: ResolverSortingMode<V>, CFG: CSResolverConfig>
                 resume_unwind(e);
             } else {
                 log!("Resolution window panicked, but no panic payload stored.");
-                return;
-            }
-        }
-
-        match report {
-            true => {
-                log!("CR stats {:#?}", self.stats);
-            }
-            false if cfg!(test) || cfg!(debug_assertions) => {
-                print!(" resolution time {:?}...", self.stats.total_resolution_time);
+                return false;
             }
-            _ => {}
         }
 
-        self.sorter.write_sequence();
-
-        if cfg!(feature = "cr_paranoia_mode") || PARANOIA {
-            log!("CR {:?}", unsafe { self.common.awaiters_broker.stats.u_deref() });
-        }
+        true
     }
 
     pub fn retrieve_sequence(&mut self) -> &ResolutionRecord {
@@ -282,6 +474,77 @@ impl<V: SmallField, RS: ResolverSortingMode<V>, CFG: CSResolverConfig>
         self.sorter.retrieve_sequence()
     }
 
+    /// Writes the dependency graph of the resolutions in the DOT format, for debugging with
+    /// graphviz. Every resolution is a box node `r<order index>` labeled with its registration
+    /// number, with edges from its inputs and to its outputs, and places are nodes `v<index>` for
+    /// variables and `w<index>` for witnesses. Resolutions are written in the execution order.
+    ///
+    /// With `center` only the resolutions that consume or produce that place are written. With
+    /// `max_resolutions` the output is truncated after that many resolutions, which is noted in a
+    /// comment. Can only be used once the registration is complete, i.e. after
+    /// `wait_till_resolved`.
+    pub fn export_graphviz(
+        &self,
+        writer: &mut impl std::io::Write,
+        center: Option<Place>,
+        max_resolutions: Option<usize>,
+    ) -> std::io::Result<()> {
+        assert!(
+            self.comms
+                .registration_complete
+                .load(std::sync::atomic::Ordering::Relaxed),
+            "the graph can only be exported after `wait_till_resolved`"
+        );
+
+        let node = |place: &Place| {
+            if place.is_witness() {
+                format!("w{}", place.raw_ix())
+            } else {
+                format!("v{}", place.raw_ix())
+            }
+        };
+
+        let exec_order = self.common.exec_order.lock().unwrap();
+        // Safety: Registration is complete, so the box is not modified anymore, and only the
+        // inputs and outputs are accessed.
+        let resolvers = unsafe { self.common.resolvers.u_deref() };
+
+        writeln!(writer, "digraph resolutions {{")?;
+        let mut written = 0;
+        for (order_ix, item) in exec_order.items[..exec_order.size].iter().enumerate() {
+            if let ResolverIxType::Jump = item.value.get_type() {
+                continue;
+            }
+            // Safety: The order only contains indices returned by the box.
+            let resolver = unsafe { resolvers.get(item.value) };
+            if let Some(center) = center {
+                if !resolver.inputs().contains(&center) && !resolver.outputs().contains(&center) {
+                    continue;
+                }
+            }
+            if max_resolutions.is_some_and(|max| written == max) {
+                writeln!(writer, "    // truncated after {} resolutions", written)?;
+                break;
+            }
+
+            writeln!(
+                writer,
+                "    r{} [shape=box, label=\"resolution {}\"];",
+                order_ix,
+                resolver.added_at()
+            )?;
+            for input in resolver.inputs() {
+                writeln!(writer, "    {} -> r{};", node(input), order_ix)?;
+            }
+            for output in resolver.outputs() {
+                writeln!(writer, "    r{} -> {};", order_ix, node(output))?;
+            }
+            written += 1;
+        }
+
+        writeln!(writer, "}}")
+    }
+
     pub fn clear(&mut self) {
         // TODO: implement
     }
@@ -354,6 +617,125 @@ impl<V: SmallField, RS: ResolverSortingMode<V> + 'static, CFG: CSResolverConfig>
     }
 }
 
+/// Finds the reason why the pending resolutions can't proceed. A dependency count sweep
+/// repeatedly removes resolutions whose inputs are all available, so whatever is left after it
+/// waits on a cycle.
+fn find_resolution_error(
+    pending: &[PendingResolution],
+    is_tracked: impl Fn(Place) -> bool,
+) -> ResolverError {
+    let mut producers = HashMap::new();
+    for (node, resolution) in pending.iter().enumerate() {
+        for output in resolution.outputs.iter() {
+            if is_tracked(*output) || producers.insert(*output, node).is_some() {
+                return ResolverError::DuplicateOutput { var: *output };
+            }
+        }
+    }
+
+    let mut dependants = vec![vec![]; pending.len()];
+    let mut num_dependencies = vec![0usize; pending.len()];
+    for (node, resolution) in pending.iter().enumerate() {
+        for input in resolution.inputs.iter() {
+            if is_tracked(*input) {
+                continue;
+            }
+
+            match producers.get(input) {
+                Some(producer) => {
+                    dependants[*producer].push(node);
+                    num_dependencies[node] += 1;
+                }
+                None => {
+                    return ResolverError::MissingInput {
+                        node: resolution.added_at as usize,
+                        input: *input,
+                    };
+                }
+            }
+        }
+    }
+
+    let mut ready = (0..pending.len())
+        .filter(|x| num_dependencies[*x] == 0)
+        .collect_vec();
+    while let Some(node) = ready.pop() {
+        for dependant in dependants[node].iter() {
+            num_dependencies[*dependant] -= 1;
+            if num_dependencies[*dependant] == 0 {
+                ready.push(*dependant);
+            }
+        }
+    }
+
+    match (0..pending.len()).find(|x| num_dependencies[*x] > 0) {
+        Some(start) => {
+            // Every remaining node still has a remaining dependency, so walking over those
+            // backwards we eventually come back to a node that was already visited.
+            let mut path = vec![start];
+            let mut node = start;
+            loop {
+                node = pending[node]
+                    .inputs
+                    .iter()
+                    .filter_map(|x| producers.get(x))
+                    .copied()
+                    .find(|x| num_dependencies[*x] > 0)
+                    .expect("remaining node must have a remaining dependency");
+
+                if let Some(pos) = path.iter().position(|x| *x == node) {
+                    path.drain(..pos);
+                    break;
+                }
+                path.push(node);
+            }
+
+            let nodes = path
+                .into_iter()
+                .rev()
+                .map(|x| pending[x].added_at as usize)
+                .collect_vec();
+
+            ResolverError::Cycle { nodes }
+        }
+        None => {
+            // No input is missing and there is no cycle, so following the producers of the inputs
+            // that are not tracked, starting from the earliest pending resolution, leads to one
+            // with all of its inputs tracked. It's still delayed, because a resolution is only
+            // accepted once every place up to its largest input is tracked, so it waits for the
+            // first place of the same kind below that input that is never tracked.
+            let mut node = (0..pending.len())
+                .min_by_key(|x| pending[*x].added_at)
+                .unwrap();
+            while let Some(producer) = pending[node]
+                .inputs
+                .iter()
+                .filter(|x| !is_tracked(**x))
+                .find_map(|x| producers.get(x))
+            {
+                node = *producer;
+            }
+
+            let resolution = &pending[node];
+            let max_input = *resolution
+                .inputs
+                .iter()
+                .max_by_key(|x| x.0)
+                .expect("delayed resolution must have inputs");
+            let place = |ix: u64| match max_input.is_witness() {
+                true => Place::from_witness(Witness::from_witness_index(ix)),
+                false => Place::from_variable(Variable::from_variable_index(ix)),
+            };
+            let input = (0..max_input.raw_ix() as u64)
+                .map(place)
+                .find(|x| !is_tracked(*x))
+                .unwrap_or(max_input);
+
+            ResolverError::MissingInput { node: resolution.added_at as usize, input }
+        }
+    }
+}
+
 // impl Drop for CircuitResolver
 
 impl<V: SmallField, RS: ResolverSortingMode<V>, CFG: CSResolverConfig> Drop
@@ -363,6 +745,16 @@ impl<V: SmallField, RS: ResolverSortingMode<V>, CFG: CSResolverConfig> Drop
         if cfg!(test) || cfg!(debug_assertions) {
             print!("Starting drop of CircuitResolver (If this hangs, it's bad)...");
         }
+        // Errors are only reported by the explicit waits, panicking here could abort the process.
+        if !self
+            .comms
+            .registration_complete
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            if let Err(error) = self.flush_batched_resolutions() {
+                log!("Skipping batched resolutions on drop: {}", error);
+            }
+        }
         self.wait_till_resolved_impl(false);
 
         if cfg!(test) || cfg!(debug_assertions) {
@@ -391,7 +783,8 @@ mod test {
                 },
                 MtCircuitResolver,
             },
-            Awaiter, CircuitResolverOpts, WitnessSource as _, WitnessSourceAwaitable as _,
+            Awaiter, CircuitResolverOpts, ResolverError, WitnessSource as _,
+            WitnessSourceAwaitable as _,
         },
         field::{goldilocks::GoldilocksField, Field, SmallField},
         log,
@@ -530,6 +923,269 @@ mod test {
         storage.wait_till_resolved();
     }
 
+    #[test]
+    fn try_resolve_reports_cycle() {
+        let mut storage =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+
+        let res_fn = |ins: &[F], outs: &mut DstBuffer<F>| {
+            outs.push(ins[0]);
+        };
+
+        let var_a = Place::from_variable(Variable::from_variable_index(0));
+        let var_b = Place::from_variable(Variable::from_variable_index(1));
+
+        storage.add_resolution(&[var_b], &[var_a], res_fn);
+        storage.add_resolution(&[var_a], &[var_b], res_fn);
+
+        match storage.try_wait_till_resolved() {
+            Err(ResolverError::Cycle { nodes }) => {
+                assert_eq!(nodes.into_iter().sorted().collect_vec(), vec![0, 1]);
+            }
+            x => panic!("Expected a cycle, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn try_resolve_reports_missing_input() {
+        let mut storage =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+
+        let res_fn = |ins: &[F], outs: &mut DstBuffer<F>| {
+            outs.push(ins[0]);
+        };
+
+        let init_var = Place::from_variable(Variable::from_variable_index(0));
+        let dep_var = Place::from_variable(Variable::from_variable_index(1));
+        let missing_var = Place::from_variable(Variable::from_variable_index(3));
+        let out_var = Place::from_variable(Variable::from_variable_index(2));
+
+        storage.set_value(init_var, F::from_u64_with_reduction(123));
+        storage.add_resolution(&[init_var], &[dep_var], res_fn);
+        storage.add_resolution(&[missing_var], &[out_var], res_fn);
+
+        assert_eq!(
+            storage.try_wait_till_resolved(),
+            Err(ResolverError::MissingInput { node: 1, input: missing_var })
+        );
+        // everything that could be resolved still is
+        assert_eq!(storage.get_value_unchecked(init_var), storage.get_value_unchecked(dep_var));
+    }
+
+    #[test]
+    fn try_resolve_reports_untracked_place_below_inputs() {
+        let mut storage =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+
+        let res_fn = |ins: &[F], outs: &mut DstBuffer<F>| {
+            outs.push(ins[0]);
+        };
+
+        let place = |ix: u64| Place::from_variable(Variable::from_variable_index(ix));
+        // every input is set or produced, but the resolution of 3 from 2 waits for 0 to be
+        // tracked, and the resolution of 4 waits for that one
+        storage.set_value(place(1), F::ONE);
+        storage.set_value(place(2), F::ONE);
+        storage.add_resolution(&[place(3)], &[place(4)], res_fn);
+        storage.add_resolution(&[place(2)], &[place(3)], res_fn);
+
+        assert_eq!(
+            storage.try_wait_till_resolved(),
+            Err(ResolverError::MissingInput { node: 1, input: place(0) })
+        );
+    }
+
+    #[test]
+    fn try_resolve_succeeds() {
+        let mut storage =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+
+        let (init_var, dep_var) = resolves_populate(&mut storage);
+
+        assert_eq!(storage.try_wait_till_resolved(), Ok(()));
+        assert_eq!(storage.get_value_unchecked(init_var), storage.get_value_unchecked(dep_var));
+    }
+
+    #[test]
+    fn overrides_propagate() {
+        let mut storage =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+
+        let copy_fn = |ins: &[F], outs: &mut DstBuffer<F>| {
+            outs.push(ins[0]);
+        };
+        let inc_fn = |ins: &[F], outs: &mut DstBuffer<F>| {
+            let mut value = ins[0];
+            value.add_assign(&F::ONE);
+            outs.push(ins[0]);
+            outs.push(value);
+        };
+
+        let init_var = Place::from_variable(Variable::from_variable_index(0));
+        let dep_var = Place::from_variable(Variable::from_variable_index(1));
+        let copy_var = Place::from_variable(Variable::from_variable_index(2));
+        let inc_var = Place::from_variable(Variable::from_variable_index(3));
+        let input_var = Place::from_variable(Variable::from_variable_index(4));
+        let input_dep_var = Place::from_variable(Variable::from_variable_index(5));
+
+        // computed variable, the other output of the same resolution is not affected
+        storage.set_override(inc_var, F::from_u64_with_reduction(7));
+        // input only variable
+        storage.set_override(input_var, F::from_u64_with_reduction(5));
+
+        storage.set_value(init_var, F::from_u64_with_reduction(123));
+        storage.add_resolution(&[init_var], &[dep_var], copy_fn);
+        storage.add_resolution(&[dep_var], &[copy_var, inc_var], inc_fn);
+        storage.set_value(input_var, F::from_u64_with_reduction(321));
+        let add_fn = |ins: &[F], outs: &mut DstBuffer<F>| {
+            let mut value = ins[0];
+            value.add_assign(&ins[1]);
+            outs.push(value);
+        };
+        storage.add_resolution(&[input_var, inc_var], &[input_dep_var], add_fn);
+
+        storage.wait_till_resolved();
+
+        assert_eq!(storage.get_value_unchecked(copy_var), F::from_u64_with_reduction(123));
+        assert_eq!(storage.get_value_unchecked(inc_var), F::from_u64_with_reduction(7));
+        assert_eq!(storage.get_value_unchecked(input_var), F::from_u64_with_reduction(5));
+        assert_eq!(storage.get_value_unchecked(input_dep_var), F::from_u64_with_reduction(12));
+    }
+
+    #[test]
+    fn resolves_targets_only() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut storage =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+
+        let vars: Vec<Place> = (0..8)
+            .map(|i| Place::from_variable(Variable::from_variable_index(i)))
+            .collect();
+        let counters = [Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0))];
+
+        // two independent chains, vars[0..4] and vars[4..8], of increments
+        for (half, counter) in counters.iter().enumerate() {
+            let start = half * 4;
+            storage.set_value(vars[start], F::from_u64_with_reduction(10 * half as u64));
+            for i in start..start + 3 {
+                let counter = counter.clone();
+                storage.add_deferred_resolution(
+                    &[vars[i]],
+                    &[vars[i + 1]],
+                    move |ins: &[F], outs: &mut DstBuffer<F>| {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                        let mut value = ins[0];
+                        value.add_assign(&F::ONE);
+                        outs.push(value);
+                    },
+                );
+            }
+        }
+        // overridden values are used by the resolved dependants
+        storage.set_override(vars[1], F::from_u64_with_reduction(100));
+
+        storage.resolve_targets(&[vars[2]]);
+        storage.wait_till_resolved();
+
+        assert_eq!(storage.get_value_unchecked(vars[2]), F::from_u64_with_reduction(101));
+        assert_eq!(counters[0].load(Ordering::Relaxed), 2);
+        assert_eq!(counters[1].load(Ordering::Relaxed), 0);
+        assert_eq!(storage.try_get_value(vars[3]), None);
+        assert_eq!(storage.try_get_value(vars[5]), None);
+    }
+
+    #[test]
+    fn export_graphviz() {
+        let mut storage =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+
+        let copy_fn = |ins: &[F], outs: &mut DstBuffer<F>| {
+            outs.push(ins[0]);
+        };
+        let add_fn = |ins: &[F], outs: &mut DstBuffer<F>| {
+            let mut value = ins[0];
+            value.add_assign(&ins[1]);
+            outs.push(value);
+        };
+
+        let vars: Vec<Place> = (0..4)
+            .map(|i| Place::from_variable(Variable::from_variable_index(i)))
+            .collect();
+
+        storage.set_value(vars[0], F::from_u64_with_reduction(1));
+        storage.add_resolution(&[vars[0]], &[vars[1]], copy_fn);
+        storage.add_resolution(&[vars[1]], &[vars[2]], copy_fn);
+        storage.add_resolution(&[vars[1], vars[2]], &[vars[3]], add_fn);
+        storage.wait_till_resolved();
+
+        let export = |center, max| {
+            let mut buffer = vec![];
+            storage.export_graphviz(&mut buffer, center, max).unwrap();
+            String::from_utf8(buffer).unwrap()
+        };
+
+        let dot = export(None, None);
+        assert!(dot.starts_with("digraph resolutions {"));
+        assert!(dot.trim_end().ends_with('}'));
+        for edge in [
+            "v0 -> r0;",
+            "r0 -> v1;",
+            "v1 -> r1;",
+            "r1 -> v2;",
+            "v1 -> r2;",
+            "v2 -> r2;",
+            "r2 -> v3;",
+        ] {
+            assert!(dot.contains(edge), "missing {} in {}", edge, dot);
+        }
+        assert_eq!(dot.matches("[shape=box").count(), 3);
+
+        // only the resolutions that touch v2
+        let dot = export(Some(vars[2]), None);
+        assert!(!dot.contains("r0"));
+        assert!(dot.contains("r1 -> v2;") && dot.contains("v2 -> r2;"));
+
+        let dot = export(None, Some(1));
+        assert_eq!(dot.matches("[shape=box").count(), 1);
+        assert!(dot.contains("truncated after 1 resolutions"));
+    }
+
+    #[test]
+    #[should_panic(expected = "already set or produced")]
+    fn override_after_set_panics() {
+        let mut storage =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+
+        let var = Place::from_variable(Variable::from_variable_index(0));
+        storage.set_value(var, F::from_u64_with_reduction(123));
+        storage.set_override(var, F::from_u64_with_reduction(7));
+    }
+
     #[test]
     fn resolves_playback_mode() {
         let mut storage =
@@ -1273,6 +1929,37 @@ mod test {
         storage.wait_till_resolved();
     }
 
+    // Test that a panic in a resolution function is not replaced by the resolution error
+    // when using try waiting.
+    #[test]
+    #[should_panic(expected = "This is a test panic")]
+    fn panic_in_resolution_function_is_propagated_through_cr_try_waiting() {
+        let mut storage =
+            MtCircuitResolver::<F, LiveResolverSorter<F, Cfg>, Cfg>::new(CircuitResolverOpts {
+                max_variables: 100,
+                desired_parallelism: 16,
+            });
+
+        let res_fn = |_: &[F], _: &mut DstBuffer<F>| {
+            panic!("This is a test panic");
+        };
+        let copy_fn = |ins: &[F], outs: &mut DstBuffer<F>| {
+            outs.push(ins[0]);
+        };
+
+        let init_var = Place::from_variable(Variable::from_variable_index(0));
+        let dep_var = Place::from_variable(Variable::from_variable_index(1));
+        let missing_var = Place::from_variable(Variable::from_variable_index(3));
+        let out_var = Place::from_variable(Variable::from_variable_index(2));
+
+        storage.set_value(init_var, F::from_u64_with_reduction(123));
+
+        storage.add_resolution(&[init_var], &[dep_var], res_fn);
+        storage.add_resolution(&[missing_var], &[out_var], copy_fn);
+
+        let _ = storage.try_wait_till_resolved();
+    }
+
     // Test that panics in resolution functions are caught and propagated
     // when using awaiter.
     #[test]
@@ -50,6 +50,16 @@ pub trait ResolverSortingMode<F: SmallField>: Sized {
     fn write_sequence(&mut self);
 
     fn retrieve_sequence(&mut self) -> &ResolutionRecord;
+
+    /// Registrations that are delayed because some of their inputs are not tracked yet.
+    fn pending_resolutions(&self) -> Vec<PendingResolution>;
+}
+
+#[derive(Clone, Debug)]
+pub struct PendingResolution {
+    pub added_at: RegistrationNum,
+    pub inputs: Vec<Place>,
+    pub outputs: Vec<Place>,
 }
 
 #[derive(Default, Clone, Debug)]
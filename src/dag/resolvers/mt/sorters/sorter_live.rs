@@ -9,7 +9,7 @@ use std::{
 
 use itertools::Itertools;
 
-use super::{ResolutionRecord, ResolutionRecordWriter, ResolverSortingMode};
+use super::{PendingResolution, ResolutionRecord, ResolutionRecordWriter, ResolverSortingMode};
 use crate::{
     config::CSResolverConfig,
     cs::{traits::cs::DstBuffer, Place, Variable, VariableType},
@@ -124,6 +124,10 @@ impl<F: SmallField, Cfg: CSResolverConfig> ResolverSortingMode<F> for LiveResolv
     fn write_sequence(&mut self) {
         self.0.write_sequence()
     }
+
+    fn pending_resolutions(&self) -> Vec<PendingResolution> {
+        self.0.pending_resolutions()
+    }
 }
 
 pub struct LiveRecordingResolverSorter<
@@ -625,4 +629,21 @@ impl<F: SmallField, Cfg: CSResolverConfig, RW: ResolutionRecordWriter> ResolverS
     fn write_sequence(&mut self) {
         self.record_writer.store(&self.record)
     }
+
+    fn pending_resolutions(&self) -> Vec<PendingResolution> {
+        // Safety: Dereferencing as shared, not accessing `resolve_fn`.
+        let rb = unsafe { self.common.resolvers.u_deref() };
+
+        self.registrar
+            .peek_vars()
+            .values()
+            .flatten()
+            .map(|x| unsafe { rb.get(*x) })
+            .map(|r| PendingResolution {
+                added_at: r.added_at(),
+                inputs: r.inputs().to_vec(),
+                outputs: r.outputs().to_vec(),
+            })
+            .collect_vec()
+    }
 }
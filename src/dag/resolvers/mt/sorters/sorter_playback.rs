@@ -4,7 +4,10 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use super::{ResolutionRecord, ResolutionRecordItem, ResolutionRecordSource, ResolverSortingMode};
+use super::{
+    PendingResolution, ResolutionRecord, ResolutionRecordItem, ResolutionRecordSource,
+    ResolverSortingMode,
+};
 use crate::{
     config::CSResolverConfig,
     cs::Place,
@@ -223,4 +226,9 @@ impl<F: SmallField, Rrs: ResolutionRecordSource, Cfg: CSResolverConfig> Resolver
     }
 
     fn write_sequence(&mut self) {}
+
+    fn pending_resolutions(&self) -> Vec<PendingResolution> {
+        // The order is replayed from a record, so nothing is ever delayed.
+        Vec::new()
+    }
 }
@@ -4,7 +4,7 @@ use crate::{
     config::CSResolverConfig,
     cs::traits::cs::CSWitnessSource,
     dag::{
-        awaiters::Awaiter, primitives::OrderIx, CircuitResolver, WitnessSource,
+        awaiters::Awaiter, primitives::OrderIx, CircuitResolver, ResolverError, WitnessSource,
         WitnessSourceAwaitable,
     },
     field::SmallField,
@@ -67,4 +67,9 @@ impl<F: SmallField, CFG: CSResolverConfig> CircuitResolver<F, CFG> for NullCircu
     fn wait_till_resolved(&mut self) {
         panic!("Null resolver");
     }
+
+    fn try_wait_till_resolved(&mut self) -> Result<(), ResolverError> {
+        // nothing is ever registered
+        Ok(())
+    }
 }
@@ -461,6 +461,25 @@ pub fn ifft_natural_to_natural_cache_friendly<F: BaseField>(
 }
 
 pub fn ifft_natural_to_natural<F: BaseField>(input: &mut [F], coset: F, twiddles: &[F]) {
+    let n_inv = ifft_natural_to_natural_unscaled(input, coset, twiddles);
+
+    if input.len() > 1 {
+        let mut i = 0;
+        let work_size = input.len();
+        while i < work_size {
+            input[i].mul_assign(&n_inv);
+            i += 1;
+        }
+    }
+}
+
+/// Same as [`ifft_natural_to_natural`], but doesn't multiply the result by `1/n`. Instead the
+/// factor is returned, so the caller can fuse it into the next scaling pass.
+pub fn ifft_natural_to_natural_unscaled<F: BaseField>(
+    input: &mut [F],
+    coset: F,
+    twiddles: &[F],
+) -> F {
     debug_assert!(input.len().is_power_of_two());
     if input.len() > 16 {
         debug_assert!(input.len() == twiddles.len() * 2);
@@ -476,19 +495,78 @@ pub fn ifft_natural_to_natural<F: BaseField>(input: &mut [F], coset: F, twiddles
         distribute_powers(input, coset);
     }
 
-    if input.len() > 1 {
-        let n_inv = F::from_u64_with_reduction(input.len() as u64)
-            .inverse()
-            .unwrap();
-        let mut i = 0;
-        let work_size = input.len();
-        while i < work_size {
-            input[i].mul_assign(&n_inv);
-            i += 1;
-        }
+    F::from_u64_with_reduction(input.len() as u64)
+        .inverse()
+        .unwrap()
+}
+
+/// Computes forward FFTs of two polynomials of the same size `n` with a single transform of size
+/// `2n` over their interleaving `[a_0, b_0, a_1, b_1, ...]`, and then separates the results.
+///
+/// `twiddles` must be precomputed for size `2n`. Both outputs are in bitreversed order and are
+/// equal to the results of [`fft_natural_to_bitreversed`] applied to each polynomial with the same
+/// `coset`.
+pub fn fft_two_packed(
+    a: &[GoldilocksField],
+    b: &[GoldilocksField],
+    coset: GoldilocksField,
+    twiddles: &[GoldilocksField],
+) -> (Vec<GoldilocksField>, Vec<GoldilocksField>) {
+    assert_eq!(a.len(), b.len());
+    let n = a.len();
+    debug_assert!(n.is_power_of_two());
+    debug_assert!(twiddles.len() >= n);
+
+    // interleave, distributing powers of the coset on the way
+    let mut packed = Vec::with_capacity(2 * n);
+    let mut scale_by = GoldilocksField::ONE;
+    for (a, b) in a.iter().zip(b.iter()) {
+        let mut a = *a;
+        a.mul_assign(&scale_by);
+        let mut b = *b;
+        b.mul_assign(&scale_by);
+        packed.push(a);
+        packed.push(b);
+        scale_by.mul_assign(&coset);
     }
+
+    fft_natural_to_bitreversed(&mut packed, GoldilocksField::ONE, twiddles);
+
+    // For c(x) = a(x^2) + x * b(x^2) and x = omega_2n^k we have c(x) and c(-x) next to each other
+    // at positions 2j and 2j + 1 for k = bitreverse(j), and the twiddle at j is exactly x. So
+    // a(x^2) = (c(x) + c(-x)) / 2 and b(x^2) = (c(x) - c(-x)) / 2x
+    let mut denominators = Vec::with_capacity(n);
+    for x in twiddles[..n].iter() {
+        let mut tmp = *x;
+        tmp.double();
+        denominators.push(tmp);
+    }
+    let mut inverses = Vec::with_capacity(n);
+    crate::cs::implementations::utils::batch_inverse(&denominators, &mut inverses);
+
+    let two_inv = GoldilocksField::TWO.inverse().unwrap();
+
+    let mut a_result = Vec::with_capacity(n);
+    let mut b_result = Vec::with_capacity(n);
+    for (pair, inv) in packed.array_chunks::<2>().zip(inverses.iter()) {
+        let [u, v] = *pair;
+        let mut sum = u;
+        sum.add_assign(&v);
+        sum.mul_assign(&two_inv);
+        let mut diff = u;
+        diff.sub_assign(&v);
+        diff.mul_assign(inv);
+
+        a_result.push(sum);
+        b_result.push(diff);
+    }
+
+    (a_result, b_result)
 }
 
+/// Runs on the calling thread only, and the twiddles are exact field elements however many
+/// threads computed them, so the result doesn't depend on the number of worker threads used to
+/// prepare the inputs or to spread columns over.
 pub fn fft_natural_to_bitreversed_mixedgl(
     input: &mut [MixedGL],
     coset: GoldilocksField,
@@ -541,6 +619,26 @@ pub fn ifft_natural_to_natural_mixedgl(
     coset: GoldilocksField,
     twiddles: &[GoldilocksField],
 ) {
+    ifft_natural_to_natural_mixedgl_impl::<true>(input, coset, twiddles);
+}
+
+/// Same as [`ifft_natural_to_natural_mixedgl`], but doesn't multiply the result by `1/n`. Instead
+/// the factor is returned, so the caller can fuse it into the next scaling pass.
+pub fn ifft_natural_to_natural_unscaled_mixedgl(
+    input: &mut [MixedGL],
+    coset: GoldilocksField,
+    twiddles: &[GoldilocksField],
+) -> GoldilocksField {
+    ifft_natural_to_natural_mixedgl_impl::<false>(input, coset, twiddles)
+}
+
+// Returns the `1/n` factor that still has to be applied to the result, so it's always ONE if
+// NORMALIZE is set
+fn ifft_natural_to_natural_mixedgl_impl<const NORMALIZE: bool>(
+    input: &mut [MixedGL],
+    coset: GoldilocksField,
+    twiddles: &[GoldilocksField],
+) -> GoldilocksField {
     debug_assert!(input.len().is_power_of_two());
     if input.len() > 2 {
         debug_assert!(input.len() * 16 == twiddles.len() * 2);
@@ -557,13 +655,24 @@ pub fn ifft_natural_to_natural_mixedgl(
     bitreverse_enumeration_inplace(input);
     let input = crate::utils::cast_check_alignment_ref_mut_pack::<GoldilocksField, MixedGL>(input);
 
+    let n_inv = GoldilocksField::from_u64_with_reduction((input.len() * 16) as u64)
+        .inverse()
+        .unwrap();
+
+    if NORMALIZE == false {
+        if coset != GoldilocksField::ONE {
+            let coset = coset.inverse().expect("coset must be non-trivial");
+            distribute_powers_mixedgl(input, coset);
+        }
+
+        return n_inv;
+    }
+
     if coset != GoldilocksField::ONE {
         let coset = coset.inverse().expect("coset must be non-trivial");
+        // 1/n is folded into the powers of the coset
         distribute_powers_normalized_mixedgl(input, coset);
-    } else if input.len() > 1 {
-        let n_inv = GoldilocksField::from_u64_with_reduction((input.len() * 16) as u64)
-            .inverse()
-            .unwrap();
+    } else {
         let mut i = 0;
         let work_size = input.len();
         while i < work_size {
@@ -571,6 +680,125 @@ pub fn ifft_natural_to_natural_mixedgl(
             i += 1;
         }
     }
+
+    GoldilocksField::ONE
+}
+
+/// Decimation-in-frequency counterpart of [`fft_natural_to_bitreversed_mixedgl`], that takes the
+/// same (bitreversed) `twiddles`. If `omega` is the root of unity the twiddles were precomputed for
+/// and the input holds coefficients `a_i` in bitreversed order (`a_i` is at base field index
+/// `bitreverse(i)` over `log2(n)` bits), then the output in natural order is
+/// `output[j] = sum_i a_i * (coset * omega^j)^i`, where `coset_powers` are the ones from
+/// [`precompute_bitreversed_coset_powers_mixedgl`], or empty for the trivial coset.
+///
+/// With forward twiddles this is the same evaluation as [`fft_natural_to_bitreversed_mixedgl`],
+/// just with both orderings swapped. With inverse twiddles, trivial coset and an extra `1/n`
+/// scaling it undoes [`fft_natural_to_bitreversed_mixedgl`] without any explicit bitreversal.
+pub fn fft_bitreversed_to_natural_mixedgl(
+    input: &mut [MixedGL],
+    coset_powers: &[MixedGL],
+    twiddles: &[GoldilocksField],
+) {
+    debug_assert!(input.len().is_power_of_two());
+    debug_assert!(input.len() * 16 == twiddles.len() * 2);
+    debug_assert!(coset_powers.is_empty() || coset_powers.len() == input.len());
+
+    for (el, powers) in input.iter_mut().zip(coset_powers.iter()) {
+        crate::field::traits::field_like::PrimeFieldLike::mul_assign(el, powers, &mut ());
+    }
+
+    // Gentleman-Sande butterflies go in the reverse order of the Cooley-Tukey ones, so the
+    // first rounds work within a single MixedGL: elements at distance 1, 2, 4 and 8 are combined
+    // by the lane-wise butterflies, and then the differences are multiplied by the twiddles of
+    // their groups
+    for (idx, el) in input.iter_mut().enumerate() {
+        unsafe { el.butterfly_1x1_impl() };
+        let mut i = 0;
+        while i < 8 {
+            el.0[2 * i + 1].mul_assign(&twiddles[idx * 8 + i]);
+            i += 1;
+        }
+
+        unsafe { el.butterfly_2x2_impl() };
+        let mut i = 0;
+        while i < 4 {
+            let s = twiddles[idx * 4 + i];
+            el.0[4 * i + 2].mul_assign(&s);
+            el.0[4 * i + 3].mul_assign(&s);
+            i += 1;
+        }
+
+        unsafe { el.butterfly_4x4_impl() };
+        let mut i = 0;
+        while i < 2 {
+            let s = twiddles[idx * 2 + i];
+            el.0[8 * i + 4].mul_assign(&s);
+            el.0[8 * i + 5].mul_assign(&s);
+            el.0[8 * i + 6].mul_assign(&s);
+            el.0[8 * i + 7].mul_assign(&s);
+            i += 1;
+        }
+
+        unsafe {
+            let ptr1 = el.0.as_ptr() as *mut u64;
+            let ptr2 = ptr1.offset(8);
+            MixedGL::butterfly_8x8_impl(ptr1, ptr2);
+        }
+        let s = twiddles[idx];
+        let mut i = 8;
+        while i < 16 {
+            el.0[i].mul_assign(&s);
+            i += 1;
+        }
+    }
+
+    {
+        use crate::field::traits::field_like::PrimeFieldLike;
+        // and the rest is between full MixedGLs, where every group shares the twiddle
+        let mut num_groups = input.len() / 2;
+        let mut distance = 1;
+        while distance < input.len() {
+            for k in 0..num_groups {
+                let s = twiddles[k];
+                let start = k * 2 * distance;
+                for j in start..(start + distance) {
+                    let u = input[j];
+                    let v = input[j + distance];
+                    let mut sum = u;
+                    sum.add_assign(&v, &mut ());
+                    let mut diff = u;
+                    diff.sub_assign(&v, &mut ());
+                    if k != 0 {
+                        diff.mul_constant_assign(&s);
+                    }
+                    input[j] = sum;
+                    input[j + distance] = diff;
+                }
+            }
+            num_groups /= 2;
+            distance *= 2;
+        }
+    }
+}
+
+/// Powers of `coset` for [`fft_bitreversed_to_natural_mixedgl`] of size `n`, following the
+/// bitreversed enumeration of the coefficients: `coset^bitreverse(j)` is at base field index `j`.
+pub fn precompute_bitreversed_coset_powers_mixedgl(
+    coset: GoldilocksField,
+    n: usize,
+    worker: &crate::worker::Worker,
+) -> Vec<MixedGL> {
+    use std::alloc::Global;
+
+    use crate::field::traits::field_like::PrimeFieldLikeVectorized;
+
+    let mut powers =
+        crate::utils::allocate_in_with_alignment_of::<GoldilocksField, MixedGL, Global>(n, Global);
+    powers.resize(n, GoldilocksField::ZERO);
+    MixedGL::fill_powers(coset, &mut powers, worker);
+    bitreverse_enumeration_inplace(&mut powers);
+
+    MixedGL::vec_from_base_vec(powers)
 }
 
 #[cfg(all(
@@ -845,6 +1073,33 @@ pub(crate) fn cache_friendly_ntt_natural_to_bitreversed<F: PrimeField>(
     }
 }
 
+// How many butterflies ahead we prefetch in the rounds that don't fit into the cache. Those rounds
+// stream over two distant regions of memory with a trivial amount of work per element, so memory
+// latency dominates. One MixedGL is two cache lines, so 8 elements ahead is 16 lines in flight per
+// stream, enough to cover the DRAM latency at the speed of the butterfly, while 2 * 8 * 128 B is
+// small enough to not evict the lines that are still in use from L1
+const MIXEDGL_FFT_PREFETCH_DISTANCE: usize = 8;
+
+#[inline(always)]
+fn prefetch_mixedgl(a: &[MixedGL], idx: usize) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    if idx < a.len() {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::{_mm_prefetch, _MM_HINT_T0};
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+        let ptr = a[idx].0.as_ptr() as *const i8;
+        unsafe {
+            _mm_prefetch::<_MM_HINT_T0>(ptr);
+            _mm_prefetch::<_MM_HINT_T0>(ptr.add(64));
+        }
+    }
+
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    let _ = (a, idx);
+}
+
 #[inline(always)]
 pub(crate) fn mixedgl_cache_friendly_ntt_natural_to_bitreversed(
     a: &mut [MixedGL],
@@ -879,6 +1134,8 @@ pub(crate) fn mixedgl_cache_friendly_ntt_natural_to_bitreversed(
 
         let mut j = idx_1;
         while j < idx_2 {
+            prefetch_mixedgl(a, j + MIXEDGL_FFT_PREFETCH_DISTANCE);
+            prefetch_mixedgl(a, j + distance + MIXEDGL_FFT_PREFETCH_DISTANCE);
             unsafe {
                 MixedGL::butterfly_16x16_impl(
                     a[j].0.as_ptr() as *mut u64,
@@ -905,6 +1162,8 @@ pub(crate) fn mixedgl_cache_friendly_ntt_natural_to_bitreversed(
 
             let mut j = idx_1;
             while j < idx_2 {
+                prefetch_mixedgl(a, j + MIXEDGL_FFT_PREFETCH_DISTANCE);
+                prefetch_mixedgl(a, j + distance + MIXEDGL_FFT_PREFETCH_DISTANCE);
                 a[j + distance].mul_constant_assign(&s);
                 unsafe {
                     MixedGL::butterfly_16x16_impl(
@@ -1476,6 +1735,66 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_fft_mixedgl_thread_count_independent() {
+        let mut rng = rand::thread_rng();
+        let poly_size_log_2 = 14;
+        let poly_size: usize = 1 << poly_size_log_2;
+        let num_columns = 16;
+        let coset = GoldilocksField::multiplicative_generator();
+
+        let columns: Vec<Vec<GoldilocksField>> = (0..num_columns)
+            .map(|_| {
+                let mut column = allocate_in_with_alignment_of::<GoldilocksField, MixedGL, Global>(
+                    poly_size, Global,
+                );
+                (0..poly_size)
+                    .map(|_| rand_from_rng::<_, GoldilocksField>(&mut rng))
+                    .collect_into(&mut column);
+                column
+            })
+            .collect();
+
+        let mut results = vec![];
+        for num_threads in [1, 2, 4, 8] {
+            let worker = Worker::new_with_num_threads(num_threads);
+            let twiddles =
+                MixedGL::precompute_forward_twiddles_for_fft::<Global>(poly_size, &worker, &mut ());
+            let mut forward: Vec<Vec<MixedGL>> = columns
+                .iter()
+                .map(|el| {
+                    MixedGL::vec_from_base_vec(clone_respecting_allignment::<
+                        GoldilocksField,
+                        MixedGL,
+                        Global,
+                    >(el))
+                })
+                .collect();
+            worker.scope(num_columns, |scope, chunk_size| {
+                for chunk in forward.chunks_mut(chunk_size) {
+                    let twiddles = &twiddles;
+                    scope.spawn(move |_| {
+                        for column in chunk.iter_mut() {
+                            MixedGL::fft_natural_to_bitreversed(column, coset, twiddles, &mut ());
+                        }
+                    });
+                }
+            });
+            let forward: Vec<Vec<GoldilocksField>> = forward
+                .into_iter()
+                .map(MixedGL::vec_into_base_vec)
+                .collect();
+            let twiddles = MixedGL::vec_into_base_vec(twiddles);
+            results.push((num_threads, twiddles, forward));
+        }
+
+        let (_, reference_twiddles, reference) = &results[0];
+        for (num_threads, twiddles, forward) in results.iter().skip(1) {
+            assert_eq!(reference_twiddles, twiddles, "twiddles differ for {} threads", num_threads);
+            assert_eq!(reference, forward, "FFT results differ for {} threads", num_threads);
+        }
+    }
+
     #[test]
     fn test_ifft_mixedgl() {
         let worker = Worker::new();
@@ -1590,6 +1909,150 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_fft_bitreversed_to_natural_mixedgl() {
+        let worker = Worker::new();
+        let mut ctx = ();
+        let mut rng = rand::thread_rng();
+
+        for poly_size_log_2 in 4..16 {
+            let poly_size: usize = 1 << poly_size_log_2;
+
+            let mut original = allocate_in_with_alignment_of::<GoldilocksField, MixedGL, Global>(
+                poly_size, Global,
+            );
+            (0..poly_size)
+                .map(|_| rand_from_rng::<_, GoldilocksField>(&mut rng))
+                .collect_into(&mut original);
+
+            let forward_twiddles_gl = GoldilocksField::precompute_forward_twiddles_for_fft::<Global>(
+                poly_size, &worker, &mut ctx,
+            );
+            let forward_twiddles_mixedgl = MixedGL::precompute_forward_twiddles_for_fft::<Global>(
+                poly_size, &worker, &mut ctx,
+            );
+            let inverse_twiddles_mixedgl = MixedGL::precompute_inverse_twiddles_for_fft::<Global>(
+                poly_size, &worker, &mut ctx,
+            );
+
+            // natural -> bitreversed -> natural gives back the input
+            let mut roundtrip = MixedGL::vec_from_base_vec(clone_respecting_allignment::<
+                GoldilocksField,
+                MixedGL,
+                Global,
+            >(&original));
+            fft_natural_to_bitreversed_mixedgl(
+                &mut roundtrip,
+                GoldilocksField::ONE,
+                &forward_twiddles_mixedgl,
+            );
+            fft_bitreversed_to_natural_mixedgl(&mut roundtrip, &[], &inverse_twiddles_mixedgl);
+            let n_inv = GoldilocksField::from_u64_with_reduction(poly_size as u64)
+                .inverse()
+                .unwrap();
+            for el in roundtrip.iter_mut() {
+                el.mul_constant_assign(&n_inv);
+            }
+            assert_eq!(
+                original,
+                MixedGL::vec_into_base_vec(roundtrip),
+                "roundtrip failed for log2 size {}",
+                poly_size_log_2
+            );
+
+            // and with forward twiddles it's the same evaluation with swapped orderings
+            for coset in [GoldilocksField::ONE, GoldilocksField(7)] {
+                let mut reference = original.clone();
+                fft_natural_to_bitreversed(&mut reference, coset, &forward_twiddles_gl);
+                bitreverse_enumeration_inplace(&mut reference);
+
+                let mut bitreversed =
+                    clone_respecting_allignment::<GoldilocksField, MixedGL, Global>(&original);
+                bitreverse_enumeration_inplace(&mut bitreversed);
+                let mut bitreversed = MixedGL::vec_from_base_vec(bitreversed);
+                let coset_powers =
+                    precompute_bitreversed_coset_powers_mixedgl(coset, poly_size, &worker);
+                fft_bitreversed_to_natural_mixedgl(
+                    &mut bitreversed,
+                    &coset_powers,
+                    &forward_twiddles_mixedgl,
+                );
+                assert_eq!(
+                    reference,
+                    MixedGL::vec_into_base_vec(bitreversed),
+                    "invalid for log2 size {}",
+                    poly_size_log_2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_ifft_unscaled() {
+        let worker = Worker::new();
+        let mut ctx = ();
+        let mut rng = rand::thread_rng();
+
+        for poly_size_log_2 in 4..16 {
+            let poly_size: usize = 1 << poly_size_log_2;
+
+            let mut original = allocate_in_with_alignment_of::<GoldilocksField, MixedGL, Global>(
+                poly_size, Global,
+            );
+            (0..poly_size)
+                .map(|_| rand_from_rng::<_, GoldilocksField>(&mut rng))
+                .collect_into(&mut original);
+
+            let inverse_twiddles_gl = GoldilocksField::precompute_inverse_twiddles_for_fft::<Global>(
+                poly_size, &worker, &mut ctx,
+            );
+            let inverse_twiddles_mixedgl = MixedGL::precompute_inverse_twiddles_for_fft::<Global>(
+                poly_size, &worker, &mut ctx,
+            );
+
+            for coset in [GoldilocksField::ONE, GoldilocksField(7)] {
+                let mut reference = original.clone();
+                ifft_natural_to_natural(&mut reference, coset, &inverse_twiddles_gl);
+
+                let mut unscaled = original.clone();
+                let n_inv =
+                    ifft_natural_to_natural_unscaled(&mut unscaled, coset, &inverse_twiddles_gl);
+                for el in unscaled.iter_mut() {
+                    Field::mul_assign(el, &n_inv);
+                }
+                assert_eq!(reference, unscaled, "invalid for log2 size {}", poly_size_log_2);
+
+                let mut scaled_mixedgl = MixedGL::vec_from_base_vec(clone_respecting_allignment::<
+                    GoldilocksField,
+                    MixedGL,
+                    Global,
+                >(&original));
+                ifft_natural_to_natural_mixedgl(
+                    &mut scaled_mixedgl,
+                    coset,
+                    &inverse_twiddles_mixedgl,
+                );
+                assert_eq!(reference, MixedGL::vec_into_base_vec(scaled_mixedgl));
+
+                let mut unscaled_mixedgl =
+                    MixedGL::vec_from_base_vec(clone_respecting_allignment::<
+                        GoldilocksField,
+                        MixedGL,
+                        Global,
+                    >(&original));
+                let n_inv = ifft_natural_to_natural_unscaled_mixedgl(
+                    &mut unscaled_mixedgl,
+                    coset,
+                    &inverse_twiddles_mixedgl,
+                );
+                for el in unscaled_mixedgl.iter_mut() {
+                    el.mul_constant_assign(&n_inv);
+                }
+                assert_eq!(reference, MixedGL::vec_into_base_vec(unscaled_mixedgl));
+            }
+        }
+    }
+
     #[test]
     fn test_over_goldilocks_valid_naive_in_coset() {
         let worker = Worker::new();
@@ -1710,6 +2173,43 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_fft_two_packed() {
+        let worker = Worker::new();
+        let mut rng = rand::thread_rng();
+        for poly_size_log in 0..12 {
+            let poly_size = 1 << poly_size_log;
+
+            let a: Vec<GoldilocksField> = (0..poly_size).map(|_| rand_from_rng(&mut rng)).collect();
+            let b: Vec<GoldilocksField> = (0..poly_size).map(|_| rand_from_rng(&mut rng)).collect();
+
+            let twiddles = precompute_twiddles_for_fft::<
+                GoldilocksField,
+                GoldilocksField,
+                Global,
+                false,
+            >(poly_size, &worker, &mut ());
+            let packed_twiddles = precompute_twiddles_for_fft::<
+                GoldilocksField,
+                GoldilocksField,
+                Global,
+                false,
+            >(poly_size * 2, &worker, &mut ());
+
+            for coset in [GoldilocksField::ONE, GoldilocksField::multiplicative_generator()] {
+                let (a_packed, b_packed) = fft_two_packed(&a, &b, coset, &packed_twiddles);
+
+                let mut a_reference = a.clone();
+                fft_natural_to_bitreversed(&mut a_reference, coset, &twiddles);
+                let mut b_reference = b.clone();
+                fft_natural_to_bitreversed(&mut b_reference, coset, &twiddles);
+
+                assert_eq!(a_reference, a_packed, "failed for size 2^{}", poly_size_log);
+                assert_eq!(b_reference, b_packed, "failed for size 2^{}", poly_size_log);
+            }
+        }
+    }
+
     #[test]
     fn calculator() {
         use crate::field::traits::representation::U64Representable;
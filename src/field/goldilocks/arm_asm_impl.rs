@@ -17,7 +17,7 @@ use crate::{
 
 // we need max of an alignment of u64x4 and u64x8 in this implementation, so 64
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Hash, Clone, Copy)]
 #[repr(C, align(64))]
 pub struct MixedGL(pub [GoldilocksField; 16]);
 
@@ -26,6 +26,19 @@ pub struct MixedGL(pub [GoldilocksField; 16]);
 #[repr(C, align(64))]
 struct U64x4Holder([u64x4; 4]);
 
+// `as_u64x4_arrays`, `as_u64x8_arrays` and their inverses transmute between these, and casts from
+// base field slices rely on the size and alignment, so a layout change must fail to compile
+const _: () = {
+    use std::mem::{align_of, size_of};
+
+    assert!(size_of::<MixedGL>() == 16 * size_of::<GoldilocksField>());
+    assert!(size_of::<MixedGL>() == size_of::<U64x4Holder>());
+    assert!(size_of::<MixedGL>() == size_of::<[u64x8; 2]>());
+    assert!(align_of::<MixedGL>() == 64);
+    assert!(align_of::<U64x4Holder>() == align_of::<MixedGL>());
+    assert!(align_of::<[u64x8; 2]>() <= align_of::<MixedGL>());
+};
+
 impl std::fmt::Debug for MixedGL {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.0)
@@ -38,6 +51,25 @@ impl std::fmt::Display for MixedGL {
     }
 }
 
+// Lanes are compared in canonical form, same as `GoldilocksField` does, but both operands are
+// reduced with a vector compare and select instead of per lane branches
+impl PartialEq for MixedGL {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        let mut a = *self;
+        a.to_reduced_wide();
+        let mut b = *other;
+        b.to_reduced_wide();
+
+        let a = Self::as_u64x8_arrays(&a);
+        let b = Self::as_u64x8_arrays(&b);
+
+        a[0].simd_eq(b[0]).all() && a[1].simd_eq(b[1]).all()
+    }
+}
+
+impl Eq for MixedGL {}
+
 impl MixedGL {
     pub const ORDER_BITS: usize = GoldilocksField::ORDER_BITS;
     pub const ORDER: u64 = GoldilocksField::ORDER;
@@ -87,8 +119,36 @@ impl MixedGL {
     #[inline(always)]
     #[unroll::unroll_for_loops]
     pub fn mul_constant_assign(&'_ mut self, other: &GoldilocksField) -> &mut Self {
-        for i in 0..16 {
-            self.0[i].mul_assign(other);
+        let parts = Self::as_u64x8_arrays(self);
+        let mut result = [u64x8::splat(0); 2];
+
+        // constant is broadcasted once, and split into 32-bit halves for the schoolbook
+        // multiplication below, as there is no 64x64 -> 128 multiplication over vectors
+        let b = u64x8::splat(other.0);
+        let b_lo = b & Self::EPSILON_VECTOR_D;
+        let b_hi = b >> 32;
+
+        for i in 0..2 {
+            let a = parts[i];
+            let a_lo = a & Self::EPSILON_VECTOR_D;
+            let a_hi = a >> 32;
+
+            // none of those can overflow
+            let lo_lo = a_lo * b_lo;
+            let lo_hi = a_lo * b_hi;
+            let hi_lo = a_hi * b_lo;
+            let hi_hi = a_hi * b_hi;
+            let mid0 = lo_hi + (lo_lo >> 32);
+            let mid1 = hi_lo + (mid0 & Self::EPSILON_VECTOR_D);
+
+            let x_lo = (mid1 << 32) | (lo_lo & Self::EPSILON_VECTOR_D);
+            let x_hi = hi_hi + (mid0 >> 32) + (mid1 >> 32);
+
+            result[i] = Self::reduce_u128x2(x_lo, x_hi);
+        }
+
+        unsafe {
+            *self = Self::from_u64x8_arrays(result);
         }
 
         self
@@ -405,9 +465,12 @@ impl crate::field::traits::field_like::PrimeFieldLike for MixedGL {
     }
 
     #[inline(always)]
+    #[unroll::unroll_for_loops]
     fn square(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
-        let t = *self;
-        self.mul_assign(&t, _ctx);
+        // no copy of the whole vector, every lane is squared in place
+        for i in 0..16 {
+            self.0[i].square_impl();
+        }
 
         self
     }
@@ -479,24 +542,24 @@ impl crate::field::traits::field_like::PrimeFieldLikeVectorized for MixedGL {
 
     #[inline(always)]
     fn slice_from_base_slice(input: &[Self::Base]) -> &[Self] {
-        if input.len() < Self::SIZE_FACTOR {
-            panic!("too small input size to cast");
-        }
-        debug_assert!(input.len() % Self::SIZE_FACTOR == 0);
-        debug_assert!(input.as_ptr().addr() % std::mem::align_of::<Self>() == 0);
-        let result_len = input.len() / 16;
+        crate::field::traits::field_like::assert_castable_from_base::<Self>(
+            input.as_ptr().addr(),
+            input.len(),
+            None,
+        );
+        let result_len = input.len() / Self::SIZE_FACTOR;
         unsafe { std::slice::from_raw_parts(input.as_ptr() as *mut Self, result_len) }
     }
 
     #[inline(always)]
     fn slice_into_base_slice(input: &[Self]) -> &[Self::Base] {
-        let result_len = input.len() * 16;
+        let result_len = input.len() * Self::SIZE_FACTOR;
         unsafe { std::slice::from_raw_parts(input.as_ptr() as *mut GoldilocksField, result_len) }
     }
 
     #[inline(always)]
     fn slice_into_base_slice_mut(input: &mut [Self]) -> &mut [Self::Base] {
-        let result_len = input.len() * 16;
+        let result_len = input.len() * Self::SIZE_FACTOR;
         unsafe {
             std::slice::from_raw_parts_mut(input.as_ptr() as *mut GoldilocksField, result_len)
         }
@@ -504,13 +567,12 @@ impl crate::field::traits::field_like::PrimeFieldLikeVectorized for MixedGL {
 
     #[inline(always)]
     fn vec_from_base_vec<A: GoodAllocator>(input: Vec<Self::Base, A>) -> Vec<Self, A> {
-        if input.len() < Self::SIZE_FACTOR {
-            panic!("too small input size to cast");
-        }
+        crate::field::traits::field_like::assert_castable_from_base::<Self>(
+            input.as_ptr().addr(),
+            input.len(),
+            Some(input.capacity()),
+        );
         let (ptr, len, capacity, allocator) = input.into_raw_parts_with_alloc();
-        debug_assert!(ptr.addr() % std::mem::align_of::<Self>() == 0);
-        debug_assert!(len % Self::SIZE_FACTOR == 0);
-        debug_assert!(capacity % Self::SIZE_FACTOR == 0);
 
         unsafe {
             Vec::from_raw_parts_in(
@@ -600,6 +662,28 @@ mod test {
         utils::clone_respecting_allignment,
     };
 
+    #[test]
+    fn test_layout_transmutes() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut a = MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng)));
+            // non-reduced lane
+            a.0[3] = GoldilocksField(u64::MAX);
+
+            let holder = MixedGL::as_u64x4_arrays(&a);
+            let arrays = MixedGL::as_u64x8_arrays(&a);
+            for i in 0..16 {
+                assert_eq!(holder.0[i / 4][i % 4], a.0[i].0);
+                assert_eq!(arrays[i / 8][i % 8], a.0[i].0);
+            }
+
+            let back = unsafe { MixedGL::from_u64x4_arrays(holder) };
+            assert_eq!(back.0.map(|el| el.0), a.0.map(|el| el.0));
+            let back = unsafe { MixedGL::from_u64x8_arrays(arrays) };
+            assert_eq!(back.0.map(|el| el.0), a.0.map(|el| el.0));
+        }
+    }
+
     #[test]
     fn test_mixedgl_negate() {
         let mut ctx = ();
@@ -879,4 +963,50 @@ mod test {
         assert_eq!(ag, av);
         // assert_eq!(bg, bv);
     }
+
+    #[test]
+    fn test_mixedgl_mul_constant_assign() {
+        const POLY_SIZE: usize = 1 << 16;
+        let mut rng = rand::thread_rng();
+
+        // mix of canonical and non-reduced values
+        let mut random_element = || {
+            if rng.gen_bool(0.5) {
+                rand_from_rng::<_, GoldilocksField>(&mut rng)
+            } else {
+                GoldilocksField(rng.gen_range(GoldilocksField::ORDER..=u64::MAX))
+            }
+        };
+
+        let a: Vec<GoldilocksField> = (0..POLY_SIZE).map(|_| random_element()).collect();
+        let constants = [
+            GoldilocksField::ZERO,
+            GoldilocksField::ONE,
+            GoldilocksField(u64::MAX),
+            random_element(),
+            random_element(),
+        ];
+
+        for constant in constants {
+            let mut ag = a.clone();
+            for aa in ag.iter_mut() {
+                Field::mul_assign(aa, &constant);
+            }
+
+            let mut av: Vec<MixedGL> = MixedGL::vec_from_base_vec(clone_respecting_allignment::<
+                GoldilocksField,
+                MixedGL,
+                _,
+            >(&a));
+            for aa in av.iter_mut() {
+                aa.mul_constant_assign(&constant);
+            }
+
+            // not only the values, but also the representations must match
+            let av = MixedGL::vec_into_base_vec(av);
+            for (i, (v, g)) in av.iter().zip(ag.iter()).enumerate() {
+                assert_eq!(v.0, g.0, "invalid for element {} and constant {}", i, constant.0);
+            }
+        }
+    }
 }
@@ -35,6 +35,18 @@ struct AlignedArray([u64; 8]);
 #[repr(C, align(64))]
 pub struct MixedGL(pub [GoldilocksField; 8]);
 
+// `from_v`, `to_v` and the constants transmute between these, and casts from base field slices
+// rely on the size and alignment, so a layout change must fail to compile
+const _: () = {
+    use std::mem::{align_of, size_of};
+
+    assert!(size_of::<MixedGL>() == 8 * size_of::<GoldilocksField>());
+    assert!(size_of::<MixedGL>() == size_of::<__m512i>());
+    assert!(size_of::<AlignedArray>() == size_of::<__m512i>());
+    assert!(align_of::<MixedGL>() == 64);
+    assert!(align_of::<AlignedArray>() == align_of::<__m512i>());
+};
+
 impl std::fmt::Debug for MixedGL {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.0)
@@ -102,7 +114,7 @@ impl MixedGL {
 
     #[inline(always)]
     #[unroll::unroll_for_loops]
-    fn to_reduced(&mut self) -> &mut Self {
+    pub fn to_reduced(&mut self) -> &mut Self {
         let r = unsafe { Self::canonicalize(self.to_v()) };
         *self = Self::from_v(r);
         self
@@ -483,9 +495,9 @@ impl crate::field::traits::field_like::PrimeFieldLike for MixedGL {
 
     #[inline(always)]
     fn square(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
-        let t = *self;
-        self.mul_assign(&t, _ctx);
-
+        // square64 needs 3 multiplications of 32-bit halves instead of 4 in mul64_64
+        let r = unsafe { Self::reduce128(Self::square64(self.to_v())) };
+        *self = Self::from_v(r);
         self
     }
 
@@ -545,24 +557,24 @@ impl crate::field::traits::field_like::PrimeFieldLikeVectorized for MixedGL {
 
     #[inline(always)]
     fn slice_from_base_slice(input: &[Self::Base]) -> &[Self] {
-        if input.len() < Self::SIZE_FACTOR {
-            panic!("too small input size to cast");
-        }
-        debug_assert!(input.len() % Self::SIZE_FACTOR == 0);
-        debug_assert!(input.as_ptr().addr() % std::mem::align_of::<Self>() == 0);
-        let result_len = input.len() / 8;
+        crate::field::traits::field_like::assert_castable_from_base::<Self>(
+            input.as_ptr().addr(),
+            input.len(),
+            None,
+        );
+        let result_len = input.len() / Self::SIZE_FACTOR;
         unsafe { std::slice::from_raw_parts(input.as_ptr() as *mut Self, result_len) }
     }
 
     #[inline(always)]
     fn slice_into_base_slice(input: &[Self]) -> &[Self::Base] {
-        let result_len = input.len() * 8;
+        let result_len = input.len() * Self::SIZE_FACTOR;
         unsafe { std::slice::from_raw_parts(input.as_ptr() as *mut GoldilocksField, result_len) }
     }
 
     #[inline(always)]
     fn slice_into_base_slice_mut(input: &mut [Self]) -> &mut [Self::Base] {
-        let result_len = input.len() * 8;
+        let result_len = input.len() * Self::SIZE_FACTOR;
         unsafe {
             std::slice::from_raw_parts_mut(input.as_ptr() as *mut GoldilocksField, result_len)
         }
@@ -570,13 +582,12 @@ impl crate::field::traits::field_like::PrimeFieldLikeVectorized for MixedGL {
 
     #[inline(always)]
     fn vec_from_base_vec<A: GoodAllocator>(input: Vec<Self::Base, A>) -> Vec<Self, A> {
-        if input.len() < Self::SIZE_FACTOR {
-            panic!("too small input size to cast");
-        }
+        crate::field::traits::field_like::assert_castable_from_base::<Self>(
+            input.as_ptr().addr(),
+            input.len(),
+            Some(input.capacity()),
+        );
         let (ptr, len, capacity, allocator) = input.into_raw_parts_with_alloc();
-        debug_assert!(ptr.addr() % std::mem::align_of::<Self>() == 0);
-        debug_assert!(len % Self::SIZE_FACTOR == 0);
-        debug_assert!(capacity % Self::SIZE_FACTOR == 0);
 
         unsafe {
             Vec::from_raw_parts_in(
@@ -666,6 +677,19 @@ mod test {
         utils::clone_respecting_allignment,
     };
 
+    #[test]
+    fn test_layout_transmutes() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut a = MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng)));
+            // non-reduced lane
+            a.0[3] = GoldilocksField(u64::MAX);
+
+            let back = MixedGL::from_v(a.to_v());
+            assert_eq!(back.0.map(|el| el.0), a.0.map(|el| el.0));
+        }
+    }
+
     #[test]
     fn test_mixedgl_negate() {
         let mut ctx = ();
@@ -0,0 +1,180 @@
+//! Implementations of the `ff` traits, so `GoldilocksField` can be used with crates that are
+//! generic over `ff::PrimeField`. Elements are always compared and encoded in canonical form.
+use ff::{FieldBits, PrimeFieldBits};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use super::GoldilocksField;
+use crate::field::{Field, PrimeField, SqrtField};
+
+impl ConstantTimeEq for GoldilocksField {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.to_reduced_u64().ct_eq(&other.to_reduced_u64())
+    }
+}
+
+impl ConditionallySelectable for GoldilocksField {
+    #[inline]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(u64::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl ff::Field for GoldilocksField {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+
+    fn random(mut rng: impl rand::RngCore) -> Self {
+        crate::field::rand_from_rng(&mut rng)
+    }
+
+    #[inline]
+    fn square(&self) -> Self {
+        let mut result = *self;
+        Field::square(&mut result);
+        result
+    }
+
+    #[inline]
+    fn double(&self) -> Self {
+        let mut result = *self;
+        Field::double(&mut result);
+        result
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        let is_nonzero = !self.to_reduced_u64().ct_eq(&0);
+        CtOption::new(self.inverse_or_zero(), is_nonzero)
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        match SqrtField::sqrt(self) {
+            Some(root) => CtOption::new(root, Choice::from(1)),
+            None => CtOption::new(Self(0), Choice::from(0)),
+        }
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+}
+
+impl ff::PrimeField for GoldilocksField {
+    /// Canonical value as 8 little-endian bytes, see [`GoldilocksField::to_le_bytes`].
+    type Repr = [u8; 8];
+
+    const MODULUS: &'static str = "0xffffffff00000001";
+    const NUM_BITS: u32 = 64;
+    const CAPACITY: u32 = 63;
+    const TWO_INV: Self = Self(0x7fffffff80000001);
+    const MULTIPLICATIVE_GENERATOR: Self = Self::MULTIPLICATIVE_GROUP_GENERATOR;
+    const S: u32 = Self::TWO_ADICITY as u32;
+    // `MULTIPLICATIVE_GENERATOR^T`
+    const ROOT_OF_UNITY: Self = Self::RADIX_2_SUBGROUP_GENERATOR;
+    const ROOT_OF_UNITY_INV: Self = Self(0x76b6b635b6fc8719);
+    // `MULTIPLICATIVE_GENERATOR^(2^S)`
+    const DELTA: Self = Self(0xaa5b2509f86bb4d4);
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        let value = u64::from_le_bytes(repr);
+        CtOption::new(Self(value), Choice::from((value < Self::ORDER) as u8))
+    }
+
+    #[inline]
+    fn to_repr(&self) -> Self::Repr {
+        self.to_le_bytes()
+    }
+
+    #[inline]
+    fn is_odd(&self) -> Choice {
+        Choice::from((self.to_reduced_u64() & 1) as u8)
+    }
+}
+
+impl PrimeFieldBits for GoldilocksField {
+    type ReprBits = [u64; 1];
+
+    #[inline]
+    fn to_le_bits(&self) -> FieldBits<Self::ReprBits> {
+        FieldBits::new([self.to_reduced_u64()])
+    }
+
+    #[inline]
+    fn char_le_bits() -> FieldBits<Self::ReprBits> {
+        FieldBits::new([Self::ORDER])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ff::{Field as _, PrimeField as _};
+
+    use super::*;
+    use crate::field::rand_from_rng;
+
+    #[test]
+    fn test_repr() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let a: GoldilocksField = rand_from_rng(&mut rng);
+            let repr = a.to_repr();
+            assert_eq!(repr, a.0.to_le_bytes());
+            assert_eq!(GoldilocksField::from_repr(repr).unwrap().0, a.0);
+            assert_eq!(bool::from(a.is_odd()), a.0 & 1 == 1);
+        }
+
+        // non-canonical representations are encoded as canonical ones, and rejected on decoding
+        let x = GoldilocksField(GoldilocksField::ORDER + 5);
+        assert_eq!(x.to_repr(), 5u64.to_le_bytes());
+        let order = GoldilocksField::ORDER.to_le_bytes();
+        assert!(bool::from(GoldilocksField::from_repr(order).is_none()));
+        assert!(bool::from(GoldilocksField::from_repr(u64::MAX.to_le_bytes()).is_none()));
+
+        let bits = GoldilocksField(GoldilocksField::ORDER + 5).to_le_bits();
+        assert_eq!(bits.into_inner(), [5]);
+        assert_eq!(GoldilocksField::char_le_bits().into_inner(), [GoldilocksField::ORDER]);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let a: GoldilocksField = rand_from_rng(&mut rng);
+            let b = <GoldilocksField as ff::Field>::random(&mut rng);
+
+            let mut expected = a;
+            Field::square(&mut expected);
+            assert_eq!(ff::Field::square(&a), expected);
+            let mut expected = a;
+            Field::double(&mut expected);
+            assert_eq!(ff::Field::double(&a), expected);
+            assert_eq!(a.invert().unwrap(), PrimeField::inverse(&a).unwrap());
+            assert_eq!([a, b].iter().sum::<GoldilocksField>(), a + b);
+            assert_eq!([a, b].into_iter().product::<GoldilocksField>(), a * b);
+
+            let square = ff::Field::square(&a);
+            let root = ff::Field::sqrt(&square).unwrap();
+            assert_eq!(ff::Field::square(&root), square);
+            let (is_square, ratio_root) = GoldilocksField::sqrt_ratio(&(square * b), &b);
+            assert!(bool::from(is_square));
+            assert_eq!(ff::Field::square(&ratio_root), square);
+        }
+        assert!(bool::from(GoldilocksField(0).invert().is_none()));
+        assert!(bool::from(GoldilocksField(GoldilocksField::ORDER).invert().is_none()));
+        // 7 is a generator, so it's not a square
+        assert!(bool::from(ff::Field::sqrt(&GoldilocksField(7)).is_none()));
+    }
+
+    #[test]
+    fn test_constants() {
+        type F = GoldilocksField;
+        let one = GoldilocksField(1);
+        let t = (F::ORDER - 1) >> F::S;
+        assert_eq!(F::TWO_INV * GoldilocksField(2), one);
+        assert_eq!(F::MULTIPLICATIVE_GENERATOR.pow_u64(t), F::ROOT_OF_UNITY);
+        assert_eq!(F::ROOT_OF_UNITY * F::ROOT_OF_UNITY_INV, one);
+        assert_eq!(F::ROOT_OF_UNITY.pow_u64(1 << F::S), one);
+        assert_ne!(F::ROOT_OF_UNITY.pow_u64(1 << (F::S - 1)), one);
+        assert_eq!(F::MULTIPLICATIVE_GENERATOR.pow_u64(1 << F::S), F::DELTA);
+    }
+}
@@ -50,7 +50,7 @@ impl MixedGL {
 
     #[inline(always)]
     #[unroll::unroll_for_loops]
-    fn to_reduced(&mut self) -> &mut Self {
+    pub fn to_reduced(&mut self) -> &mut Self {
         for i in 0..16 {
             let r = self.0[i].to_reduced_u64();
             self.0[i] = GoldilocksField(r);
@@ -223,9 +223,12 @@ impl crate::field::traits::field_like::PrimeFieldLike for MixedGL {
     }
 
     #[inline(always)]
+    #[unroll::unroll_for_loops]
     fn square(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
-        let t = *self;
-        self.mul_assign(&t, _ctx);
+        // no copy of the whole vector, every lane is squared in place
+        for i in 0..16 {
+            self.0[i].square_impl();
+        }
 
         self
     }
@@ -288,24 +291,24 @@ impl crate::field::traits::field_like::PrimeFieldLikeVectorized for MixedGL {
 
     #[inline(always)]
     fn slice_from_base_slice(input: &[Self::Base]) -> &[Self] {
-        if input.len() < Self::SIZE_FACTOR {
-            panic!("too small input size to cast");
-        }
-        debug_assert!(input.len() % Self::SIZE_FACTOR == 0);
-        debug_assert!(input.as_ptr().addr() % std::mem::align_of::<Self>() == 0);
-        let result_len = input.len() / 16;
+        crate::field::traits::field_like::assert_castable_from_base::<Self>(
+            input.as_ptr().addr(),
+            input.len(),
+            None,
+        );
+        let result_len = input.len() / Self::SIZE_FACTOR;
         unsafe { std::slice::from_raw_parts(input.as_ptr() as *mut Self, result_len) }
     }
 
     #[inline(always)]
     fn slice_into_base_slice(input: &[Self]) -> &[Self::Base] {
-        let result_len = input.len() * 16;
+        let result_len = input.len() * Self::SIZE_FACTOR;
         unsafe { std::slice::from_raw_parts(input.as_ptr() as *mut GoldilocksField, result_len) }
     }
 
     #[inline(always)]
     fn slice_into_base_slice_mut(input: &mut [Self]) -> &mut [Self::Base] {
-        let result_len = input.len() * 16;
+        let result_len = input.len() * Self::SIZE_FACTOR;
         unsafe {
             std::slice::from_raw_parts_mut(input.as_ptr() as *mut GoldilocksField, result_len)
         }
@@ -313,13 +316,12 @@ impl crate::field::traits::field_like::PrimeFieldLikeVectorized for MixedGL {
 
     #[inline(always)]
     fn vec_from_base_vec<A: GoodAllocator>(input: Vec<Self::Base, A>) -> Vec<Self, A> {
-        if input.len() < Self::SIZE_FACTOR {
-            panic!("too small input size to cast");
-        }
+        crate::field::traits::field_like::assert_castable_from_base::<Self>(
+            input.as_ptr().addr(),
+            input.len(),
+            Some(input.capacity()),
+        );
         let (ptr, len, capacity, allocator) = input.into_raw_parts_with_alloc();
-        debug_assert!(ptr.addr() % std::mem::align_of::<Self>() == 0);
-        debug_assert!(len % Self::SIZE_FACTOR == 0);
-        debug_assert!(capacity % Self::SIZE_FACTOR == 0);
 
         unsafe {
             Vec::from_raw_parts_in(
@@ -0,0 +1,406 @@
+//! Half-width counterpart of `MixedGL` for the workloads that are naturally 8 wide (e.g. the rate
+//! part of the Poseidon2 state). It's built on a single portable `u64x8`, so it's the same on all
+//! targets, and can be reinterpreted from/to slices of `MixedGL` without copying.
+
+use std::{
+    ops::{Add, BitOr, Sub},
+    simd::{
+        cmp::{SimdPartialEq, SimdPartialOrd},
+        u64x8,
+    },
+};
+
+use super::{GoldilocksField, MixedGL};
+use crate::field::{traits::field_like::PrimeFieldLike, Field, PrimeField};
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[repr(C, align(64))]
+pub struct MixedGL8(pub [GoldilocksField; 8]);
+
+// `as_u64x8` and `from_u64x8` transmute between these, and slices of `MixedGL` are reinterpreted
+// as slices of `MixedGL8`, so a layout change must fail to compile
+const _: () = {
+    use std::mem::{align_of, size_of};
+
+    assert!(size_of::<MixedGL8>() == size_of::<u64x8>());
+    assert!(align_of::<u64x8>() <= align_of::<MixedGL8>());
+    assert!(size_of::<MixedGL>() % size_of::<MixedGL8>() == 0);
+    assert!(align_of::<MixedGL>() == align_of::<MixedGL8>());
+};
+
+impl std::fmt::Debug for MixedGL8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl std::fmt::Display for MixedGL8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl Default for MixedGL8 {
+    fn default() -> Self {
+        Self([GoldilocksField::ZERO; 8])
+    }
+}
+
+impl MixedGL8 {
+    pub const ORDER: u64 = GoldilocksField::ORDER;
+    pub const EPSILON: u64 = (1 << 32) - 1;
+    pub const EPSILON_VECTOR: u64x8 = u64x8::from_array([Self::EPSILON; 8]);
+
+    // MixedGL is made of a whole number of those, with the same alignment
+    const PER_MIXEDGL: usize = std::mem::size_of::<MixedGL>() / std::mem::size_of::<Self>();
+
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self([GoldilocksField::ZERO; 8])
+    }
+
+    #[inline(always)]
+    pub fn from_constant(value: GoldilocksField) -> Self {
+        Self([value; 8])
+    }
+
+    #[inline(always)]
+    pub fn from_array(value: [GoldilocksField; 8]) -> Self {
+        Self(value)
+    }
+
+    #[inline(always)]
+    fn as_u64x8(&self) -> u64x8 {
+        // this preserves an alignment
+        unsafe { std::mem::transmute(*self) }
+    }
+
+    #[inline(always)]
+    fn from_u64x8(input: u64x8) -> Self {
+        // this preserves an alignment
+        unsafe { std::mem::transmute(input) }
+    }
+
+    // brings every lane into 0..ORDER
+    #[inline(always)]
+    fn reduce_vector(a: u64x8) -> u64x8 {
+        let a_reduced = a.add(Self::EPSILON_VECTOR);
+        let cmp = a_reduced.simd_lt(Self::EPSILON_VECTOR);
+        cmp.select(a_reduced, a)
+    }
+
+    // lane-wise product, reduced the same way as GoldilocksField::from_u128_with_reduction, so the
+    // representation matches the scalar multiplication
+    #[inline(always)]
+    fn mul_vectors(a: u64x8, b: u64x8) -> u64x8 {
+        let a_lo = a & Self::EPSILON_VECTOR;
+        let a_hi = a >> 32;
+        let b_lo = b & Self::EPSILON_VECTOR;
+        let b_hi = b >> 32;
+
+        // none of those can overflow
+        let lo_lo = a_lo * b_lo;
+        let lo_hi = a_lo * b_hi;
+        let hi_lo = a_hi * b_lo;
+        let hi_hi = a_hi * b_hi;
+        let mid0 = lo_hi + (lo_lo >> 32);
+        let mid1 = hi_lo + (mid0 & Self::EPSILON_VECTOR);
+
+        let x_lo = (mid1 << 32) | (lo_lo & Self::EPSILON_VECTOR);
+        let x_hi = hi_hi + (mid0 >> 32) + (mid1 >> 32);
+
+        MixedGL::reduce_u128x2(x_lo, x_hi)
+    }
+
+    #[inline(always)]
+    pub fn to_reduced(&mut self) -> &mut Self {
+        *self = Self::from_u64x8(Self::reduce_vector(self.as_u64x8()));
+
+        self
+    }
+
+    #[inline(always)]
+    pub fn mul_constant_assign(&'_ mut self, other: &GoldilocksField) -> &mut Self {
+        *self = Self::from_u64x8(Self::mul_vectors(self.as_u64x8(), u64x8::splat(other.0)));
+
+        self
+    }
+
+    /// Views every `MixedGL` as the consecutive 8-lane halves of it.
+    #[inline(always)]
+    pub fn slice_from_mixedgl_slice(input: &[MixedGL]) -> &[Self] {
+        let result_len = input.len() * Self::PER_MIXEDGL;
+        unsafe { std::slice::from_raw_parts(input.as_ptr() as *const Self, result_len) }
+    }
+
+    #[inline(always)]
+    pub fn slice_from_mixedgl_slice_mut(input: &mut [MixedGL]) -> &mut [Self] {
+        let result_len = input.len() * Self::PER_MIXEDGL;
+        unsafe { std::slice::from_raw_parts_mut(input.as_mut_ptr() as *mut Self, result_len) }
+    }
+
+    /// Inverse of [`Self::slice_from_mixedgl_slice`], the length must be a multiple of the number
+    /// of `MixedGL8` in `MixedGL`.
+    #[inline(always)]
+    pub fn slice_into_mixedgl_slice(input: &[Self]) -> &[MixedGL] {
+        assert!(input.len() % Self::PER_MIXEDGL == 0);
+        let result_len = input.len() / Self::PER_MIXEDGL;
+        unsafe { std::slice::from_raw_parts(input.as_ptr() as *const MixedGL, result_len) }
+    }
+
+    #[inline(always)]
+    pub fn slice_into_mixedgl_slice_mut(input: &mut [Self]) -> &mut [MixedGL] {
+        assert!(input.len() % Self::PER_MIXEDGL == 0);
+        let result_len = input.len() / Self::PER_MIXEDGL;
+        unsafe { std::slice::from_raw_parts_mut(input.as_mut_ptr() as *mut MixedGL, result_len) }
+    }
+}
+
+impl PrimeFieldLike for MixedGL8 {
+    type Base = GoldilocksField;
+    type Context = ();
+
+    #[inline(always)]
+    fn zero(_ctx: &mut Self::Context) -> Self {
+        Self([GoldilocksField::ZERO; 8])
+    }
+    #[inline(always)]
+    fn one(_ctx: &mut Self::Context) -> Self {
+        Self([GoldilocksField::ONE; 8])
+    }
+    #[inline(always)]
+    fn minus_one(_ctx: &mut Self::Context) -> Self {
+        Self([GoldilocksField::MINUS_ONE; 8])
+    }
+
+    #[inline(always)]
+    fn add_assign(&mut self, other: &Self, _ctx: &mut Self::Context) -> &mut Self {
+        let a = self.as_u64x8();
+        // additional reduction over b
+        let b = Self::reduce_vector(other.as_u64x8());
+        // a+b
+        let sum = a.add(b);
+        let sum_reduced = sum.add(Self::EPSILON_VECTOR);
+        let cmp0 = sum_reduced.simd_lt(sum);
+        let cmp1 = sum.simd_lt(a);
+        let reduce_flag = cmp0.bitor(cmp1);
+        *self = Self::from_u64x8(reduce_flag.select(sum_reduced, sum));
+
+        self
+    }
+
+    #[inline(always)]
+    fn sub_assign(&'_ mut self, other: &Self, _ctx: &mut Self::Context) -> &mut Self {
+        let a = self.as_u64x8();
+        // additional reduction over b
+        let b = Self::reduce_vector(other.as_u64x8());
+        // a-b
+        let diff = a.sub(b);
+        let diff_reduced = diff.sub(Self::EPSILON_VECTOR);
+        let cmp = a.simd_lt(b);
+        *self = Self::from_u64x8(cmp.select(diff_reduced, diff));
+
+        self
+    }
+
+    #[inline(always)]
+    fn mul_assign(&'_ mut self, other: &Self, _ctx: &mut Self::Context) -> &mut Self {
+        *self = Self::from_u64x8(Self::mul_vectors(self.as_u64x8(), other.as_u64x8()));
+
+        self
+    }
+
+    #[inline(always)]
+    fn square(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
+        let t = *self;
+        self.mul_assign(&t, _ctx);
+
+        self
+    }
+
+    #[inline(always)]
+    fn negate(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
+        let a = Self::reduce_vector(self.as_u64x8());
+        let is_zero = a.simd_eq(u64x8::splat(0));
+        let neg = u64x8::splat(Self::ORDER).sub(a);
+        *self = Self::from_u64x8(is_zero.select(a, neg));
+
+        self
+    }
+
+    #[inline(always)]
+    fn double(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
+        let t = *self;
+        self.add_assign(&t, _ctx);
+
+        self
+    }
+
+    #[inline(always)]
+    fn inverse(&self, _ctx: &mut Self::Context) -> Self {
+        let mut result = *self;
+        for el in result.0.iter_mut() {
+            *el = PrimeField::inverse(el).expect("inverse must exist");
+        }
+
+        result
+    }
+
+    #[inline(always)]
+    fn constant(value: Self::Base, _ctx: &mut Self::Context) -> Self {
+        Self([value; 8])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        field::{rand_from_rng, traits::field_like::PrimeFieldLikeVectorized},
+        utils::clone_respecting_allignment,
+    };
+
+    fn rand_element<R: Rng>(rng: &mut R) -> GoldilocksField {
+        if rng.gen_bool(0.5) {
+            rand_from_rng(rng)
+        } else {
+            // non-reduced one
+            GoldilocksField(rng.gen_range(GoldilocksField::ORDER..=u64::MAX))
+        }
+    }
+
+    fn check_binop(
+        op: impl Fn(&mut MixedGL8, &MixedGL8),
+        scalar_op: impl Fn(&mut GoldilocksField, &GoldilocksField),
+    ) {
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let a = MixedGL8(std::array::from_fn(|_| rand_element(&mut rng)));
+            let mut b = MixedGL8(std::array::from_fn(|_| rand_element(&mut rng)));
+            b.0[0] = GoldilocksField::ZERO;
+            b.0[1] = GoldilocksField(GoldilocksField::ORDER);
+
+            let mut expected = a.0;
+            for (a, b) in expected.iter_mut().zip(b.0.iter()) {
+                scalar_op(a, b);
+            }
+            let mut result = a;
+            op(&mut result, &b);
+
+            assert_eq!(result.0, expected);
+        }
+    }
+
+    #[test]
+    fn test_layout_transmutes() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let a = MixedGL8(std::array::from_fn(|_| rand_element(&mut rng)));
+            let vector = a.as_u64x8();
+            for i in 0..8 {
+                assert_eq!(vector[i], a.0[i].0);
+            }
+            assert_eq!(MixedGL8::from_u64x8(vector).0.map(|el| el.0), a.0.map(|el| el.0));
+        }
+    }
+
+    #[test]
+    fn test_mixedgl8_add_sub() {
+        check_binop(
+            |a, b| {
+                a.add_assign(b, &mut ());
+            },
+            |a, b| {
+                Field::add_assign(a, b);
+            },
+        );
+        check_binop(
+            |a, b| {
+                a.sub_assign(b, &mut ());
+            },
+            |a, b| {
+                Field::sub_assign(a, b);
+            },
+        );
+    }
+
+    #[test]
+    fn test_mixedgl8_mul() {
+        check_binop(
+            |a, b| {
+                a.mul_assign(b, &mut ());
+            },
+            |a, b| {
+                Field::mul_assign(a, b);
+            },
+        );
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let a = MixedGL8(std::array::from_fn(|_| rand_element(&mut rng)));
+            let constant = rand_element(&mut rng);
+
+            let mut result = a;
+            result.mul_constant_assign(&constant);
+            for (result, a) in result.0.iter().zip(a.0.iter()) {
+                let mut expected = *a;
+                Field::mul_assign(&mut expected, &constant);
+                // same representation as the scalar multiplication
+                assert_eq!(result.0, expected.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mixedgl8_negate() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let mut a = MixedGL8(std::array::from_fn(|_| rand_element(&mut rng)));
+            a.0[0] = GoldilocksField::ZERO;
+            a.0[1] = GoldilocksField(GoldilocksField::ORDER);
+
+            let mut expected = a.0;
+            for el in expected.iter_mut() {
+                Field::negate(el);
+            }
+            a.negate(&mut ());
+
+            assert_eq!(a.0, expected);
+            assert_eq!(a.0[0].0, 0);
+            assert_eq!(a.0[1].0, 0);
+        }
+    }
+
+    #[test]
+    fn test_mixedgl8_mixedgl_casts() {
+        let mut rng = rand::thread_rng();
+        let values: Vec<GoldilocksField> = (0..MixedGL::SIZE_FACTOR * 4)
+            .map(|_| rand_from_rng(&mut rng))
+            .collect();
+        let mut vectors =
+            MixedGL::vec_from_base_vec(clone_respecting_allignment::<GoldilocksField, MixedGL, _>(
+                &values,
+            ));
+
+        let halves = MixedGL8::slice_from_mixedgl_slice(&vectors);
+        assert_eq!(halves.len(), values.len() / 8);
+        for (half, chunk) in halves.iter().zip(values.chunks(8)) {
+            assert_eq!(&half.0[..], chunk);
+        }
+        assert_eq!(MixedGL8::slice_into_mixedgl_slice(halves), &vectors[..]);
+
+        for half in MixedGL8::slice_from_mixedgl_slice_mut(&mut vectors) {
+            half.double(&mut ());
+        }
+        for (doubled, value) in MixedGL::slice_into_base_slice(&vectors)
+            .iter()
+            .zip(values.iter())
+        {
+            let mut expected = *value;
+            Field::double(&mut expected);
+            assert_eq!(*doubled, expected);
+        }
+    }
+}
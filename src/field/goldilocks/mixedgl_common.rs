@@ -0,0 +1,1644 @@
+//! Backend independent functionality of the vectorized `MixedGL`. Everything here is expressed in
+//! terms of `PrimeFieldLike` operations and lane access, so it works with any of the SIMD
+//! implementations selected in the parent module.
+
+use std::simd::{
+    cmp::{SimdPartialEq, SimdPartialOrd},
+    mask64x8, u64x8,
+};
+
+use super::{Backend, GoldilocksField, MixedGL};
+use crate::{
+    field::{
+        traits::field_like::{PrimeFieldLike, PrimeFieldLikeVectorized},
+        Field,
+    },
+    utils::Aligned64Allocator,
+    worker::Worker,
+};
+
+// casts from and into base field slices on every backend rely on `MixedGL` being exactly
+// `SIZE_FACTOR` packed elements aligned to 64 bytes
+const _: () = {
+    use std::mem::{align_of, size_of};
+
+    assert!(
+        size_of::<MixedGL>()
+            == <MixedGL as PrimeFieldLikeVectorized>::SIZE_FACTOR * size_of::<GoldilocksField>()
+    );
+    assert!(align_of::<MixedGL>() == 64);
+};
+
+impl MixedGL {
+    /// Which of the implementations is compiled in. The selection mirrors the `cfg`s on the
+    /// backend modules, so it's useful to log it in benchmarks and bug reports.
+    pub const fn active_backend() -> Backend {
+        if cfg!(all(
+            target_feature = "avx512bw",
+            target_feature = "avx512cd",
+            target_feature = "avx512dq",
+            target_feature = "avx512f",
+            target_feature = "avx512vl"
+        )) {
+            Backend::Avx512
+        } else if cfg!(all(target_feature = "avx512f", target_feature = "avx512vl")) {
+            Backend::Avx512F
+        } else if cfg!(target_feature = "neon") {
+            Backend::Neon
+        } else if cfg!(target_feature = "avx2") {
+            Backend::Avx2
+        } else {
+            Backend::Generic
+        }
+    }
+
+    /// Binds the first variable of a multilinear polynomial given by its evaluations over the
+    /// boolean hypercube to `r`. The first variable corresponds to the most significant bit of the
+    /// evaluation index, so for halves `lo` and `hi` of `evals` it computes `lo + r * (hi - lo)`.
+    pub fn fold_multilinear(evals: &[Self], r: GoldilocksField) -> Vec<Self> {
+        assert!(evals.len() >= 2, "can only fold over at least two vectors");
+        debug_assert!(evals.len().is_power_of_two());
+
+        let (lo, hi) = evals.split_at(evals.len() / 2);
+        let mut result = Vec::with_capacity(lo.len());
+        for (lo, hi) in lo.iter().zip(hi.iter()) {
+            let mut tmp = *hi;
+            tmp.sub_assign(lo, &mut ());
+            tmp.mul_constant_assign(&r);
+            tmp.add_assign(lo, &mut ());
+            result.push(tmp);
+        }
+
+        result
+    }
+
+    /// Evaluates a multilinear polynomial given by its evaluations over the boolean hypercube at
+    /// `point`. The first coordinate of `point` binds the most significant bit of the evaluation
+    /// index, same as in [`Self::fold_multilinear`].
+    pub fn eval_multilinear(evals: &[Self], point: &[GoldilocksField]) -> GoldilocksField {
+        let num_evals = evals.len() * Self::SIZE_FACTOR;
+        assert!(num_evals.is_power_of_two());
+        assert_eq!(num_evals.trailing_zeros() as usize, point.len());
+
+        let mut coordinates = point.iter();
+
+        let last = if evals.len() > 1 {
+            let mut current = Self::fold_multilinear(evals, *coordinates.next().unwrap());
+            while current.len() > 1 {
+                current = Self::fold_multilinear(&current, *coordinates.next().unwrap());
+            }
+
+            current[0]
+        } else {
+            evals[0]
+        };
+
+        // and finish over the lanes of the last vector
+        let mut lanes = last.0;
+        let mut len = lanes.len();
+        for r in coordinates {
+            len /= 2;
+            for i in 0..len {
+                let mut tmp = lanes[len + i];
+                Field::sub_assign(&mut tmp, &lanes[i]);
+                Field::mul_assign(&mut tmp, r);
+                Field::add_assign(&mut tmp, &lanes[i]);
+                lanes[i] = tmp;
+            }
+        }
+        debug_assert_eq!(len, 1);
+
+        lanes[0]
+    }
+
+    /// Writes `sum_i alpha^i * selectors[i] * constraints[i]` into `out`, where all the terms are
+    /// in evaluation form over the same domain. It's computed as a Horner scheme over the
+    /// constraints, so every element of `out` costs one multiplication by `alpha` and one fused
+    /// multiply-add per constraint.
+    pub fn aggregate_constraints(
+        constraints: &[&[Self]],
+        selectors: &[&[Self]],
+        alpha: GoldilocksField,
+        out: &mut [Self],
+    ) {
+        assert_eq!(constraints.len(), selectors.len());
+        for (constraint, selector) in constraints.iter().zip(selectors.iter()) {
+            assert_eq!(constraint.len(), out.len());
+            assert_eq!(selector.len(), out.len());
+        }
+
+        out.fill(Self::zero(&mut ()));
+        for (constraint, selector) in constraints.iter().zip(selectors.iter()).rev() {
+            for ((dst, c), s) in out.iter_mut().zip(constraint.iter()).zip(selector.iter()) {
+                dst.mul_constant_assign(&alpha);
+                Self::mul_and_accumulate_into(dst, c, s, &mut ());
+            }
+        }
+    }
+
+    /// Evaluates the polynomial with `coeffs` (constant term first) at `point`. Blocks of
+    /// `SIZE_FACTOR` coefficients are accumulated with Horner's rule over `point^SIZE_FACTOR`, one
+    /// block per lane, and the lanes are combined with the powers of `point` at the end. A trailing
+    /// incomplete block is padded with zero coefficients.
+    pub fn horner_eval(coeffs: &[GoldilocksField], point: GoldilocksField) -> GoldilocksField {
+        let step = Field::pow_u64(&point, Self::SIZE_FACTOR as u64);
+
+        let mut acc = Self::zero(&mut ());
+        for chunk in coeffs.chunks(Self::SIZE_FACTOR).rev() {
+            let mut block = Self::zero(&mut ());
+            block.0[..chunk.len()].copy_from_slice(chunk);
+            acc.mul_constant_assign(&step);
+            acc.add_assign(&block, &mut ());
+        }
+
+        let mut result = GoldilocksField::ZERO;
+        for lane in acc.0.iter().rev() {
+            Field::mul_assign(&mut result, &point);
+            Field::add_assign(&mut result, lane);
+        }
+
+        result
+    }
+
+    /// Reduces 8 lane-wise 128-bit values given by their 64-bit limbs, `value = hi * 2^64 + lo`,
+    /// e.g. accumulated products, with the same folding as
+    /// `GoldilocksField::from_u128_with_reduction`. The result fits into 64 bits and has exactly
+    /// the scalar representation, but it's not necessarily canonical.
+    #[inline(always)]
+    pub fn reduce_u128x2(lo: u64x8, hi: u64x8) -> u64x8 {
+        const EPSILON: u64x8 = u64x8::from_array([(1 << 32) - 1; 8]);
+
+        // 2^64 = EPSILON and 2^96 = -1 modulo the field order
+        let hi_hi = hi >> 32;
+        let hi_lo = hi & EPSILON;
+
+        let t0 = lo - hi_hi;
+        let borrow = lo.simd_lt(hi_hi);
+        let t0 = borrow.select(t0 - EPSILON, t0);
+        let t1 = hi_lo * EPSILON;
+        let t2 = t0 + t1;
+        let carry = t2.simd_lt(t0);
+        carry.select(t2 + EPSILON, t2)
+    }
+
+    /// Same as `to_reduced`, but over groups of 8 lanes with a vector compare and select, so it
+    /// compiles to the same code on every backend.
+    #[inline(always)]
+    pub fn to_reduced_wide(&mut self) -> &mut Self {
+        const EPSILON: u64x8 = u64x8::from_array([(1 << 32) - 1; 8]);
+
+        for lanes in self.0.chunks_exact_mut(8) {
+            let a = u64x8::from_array(std::array::from_fn(|j| lanes[j].0));
+            let a_reduced = a + EPSILON;
+            let reduced = a_reduced.simd_lt(EPSILON).select(a_reduced, a);
+            for (dst, el) in lanes.iter_mut().zip(reduced.to_array()) {
+                *dst = GoldilocksField(el);
+            }
+        }
+
+        self
+    }
+
+    /// Slices shorter than this (in vectors) are processed by the elementwise helpers below on the
+    /// current thread, as the work is too cheap to pay for spawning.
+    const PARALLEL_SLICE_THRESHOLD: usize = 1 << 12;
+
+    /// Brings every lane of `input` into canonical form, e.g. before serialization or hashing.
+    /// Reduction is branchless and doesn't change lanes that are already reduced, so it costs the
+    /// same as checking them would.
+    pub fn reduce_slice(input: &mut [Self], worker: &Worker) {
+        if input.len() < Self::PARALLEL_SLICE_THRESHOLD {
+            for el in input.iter_mut() {
+                el.to_reduced();
+            }
+
+            return;
+        }
+
+        worker.scope(input.len(), |scope, chunk_size| {
+            for chunk in input.chunks_mut(chunk_size) {
+                scope.spawn(move |_| {
+                    for el in chunk.iter_mut() {
+                        el.to_reduced();
+                    }
+                });
+            }
+        });
+    }
+
+    /// Negates every element of `input` inplace, splitting large slices between the worker
+    /// threads. Zero lanes stay zero.
+    pub fn negate_slice(input: &mut [Self], worker: &Worker) {
+        if input.len() < Self::PARALLEL_SLICE_THRESHOLD {
+            for el in input.iter_mut() {
+                el.negate(&mut ());
+            }
+
+            return;
+        }
+
+        worker.scope(input.len(), |scope, chunk_size| {
+            for chunk in input.chunks_mut(chunk_size) {
+                scope.spawn(move |_| {
+                    for el in chunk.iter_mut() {
+                        el.negate(&mut ());
+                    }
+                });
+            }
+        });
+    }
+
+    /// Computes `acc[i] += alpha * x[i]` in a single pass, splitting large slices between the
+    /// worker threads. Every multiplication and addition leaves the lanes in the usual (possibly
+    /// non-canonical) `u64` representation, so the accumulator needs no extra reductions no
+    /// matter how many times it's updated.
+    pub fn axpy(acc: &mut [Self], alpha: GoldilocksField, x: &[Self], worker: &Worker) {
+        assert_eq!(acc.len(), x.len(), "accumulator and input must have the same length");
+        if acc.len() < Self::PARALLEL_SLICE_THRESHOLD {
+            Self::axpy_serial(acc, &alpha, x);
+
+            return;
+        }
+
+        worker.scope(acc.len(), |scope, chunk_size| {
+            for (acc, x) in acc.chunks_mut(chunk_size).zip(x.chunks(chunk_size)) {
+                scope.spawn(move |_| {
+                    Self::axpy_serial(acc, &alpha, x);
+                });
+            }
+        });
+    }
+
+    #[inline]
+    fn axpy_serial(acc: &mut [Self], alpha: &GoldilocksField, x: &[Self]) {
+        for (acc, x) in acc.iter_mut().zip(x.iter()) {
+            let mut t = *x;
+            t.mul_constant_assign(alpha);
+            acc.add_assign(&t, &mut ());
+        }
+    }
+
+    /// Same as `vec_add_assign`, but large slices are split between the worker threads.
+    pub fn vec_add_assign_parallel(a: &mut [Self], b: &[Self], worker: &Worker) {
+        Self::zip_assign_parallel(a, b, worker, |a, b| {
+            a.add_assign(b, &mut ());
+        });
+    }
+
+    /// Same as `vec_mul_assign`, but large slices are split between the worker threads.
+    pub fn vec_mul_assign_parallel(a: &mut [Self], b: &[Self], worker: &Worker) {
+        Self::zip_assign_parallel(a, b, worker, |a, b| {
+            a.mul_assign(b, &mut ());
+        });
+    }
+
+    #[inline]
+    fn zip_assign_parallel(
+        a: &mut [Self],
+        b: &[Self],
+        worker: &Worker,
+        op: impl Fn(&mut Self, &Self) + Send + Sync + Copy,
+    ) {
+        assert_eq!(a.len(), b.len(), "operands must have the same length");
+        if a.len() < Self::PARALLEL_SLICE_THRESHOLD {
+            a.iter_mut().zip(b.iter()).for_each(|(a, b)| op(a, b));
+
+            return;
+        }
+
+        worker.scope(a.len(), |scope, chunk_size| {
+            for (a, b) in a.chunks_mut(chunk_size).zip(b.chunks(chunk_size)) {
+                scope.spawn(move |_| {
+                    a.iter_mut().zip(b.iter()).for_each(|(a, b)| op(a, b));
+                });
+            }
+        });
+    }
+
+    /// Adds polynomial `other` to `acc` when their lengths may differ, e.g. when combining quotient
+    /// parts of different degrees. Both are expected to be packed the same way, with coefficient
+    /// `i` in lane `i % SIZE_FACTOR` of vector `i / SIZE_FACTOR` and the last vector padded with
+    /// zeroes, so the overlapping vectors are added lane-wise, and if `other` is longer, its
+    /// remaining vectors are appended to `acc` as they are.
+    pub fn add_assign_unequal(acc: &mut Vec<Self>, other: &[Self]) {
+        let overlap = std::cmp::min(acc.len(), other.len());
+        for (acc, other) in acc[..overlap].iter_mut().zip(other[..overlap].iter()) {
+            acc.add_assign(other, &mut ());
+        }
+        if other.len() > overlap {
+            acc.extend_from_slice(&other[overlap..]);
+        }
+    }
+
+    /// Fills `out` with canonical `base^0, base^1, ..., base^(out.len() - 1)`. Every worker chunk
+    /// starts from `base` raised to the index of its first element, and is then filled
+    /// `SIZE_FACTOR` powers at a time by multiplying all lanes with `base^SIZE_FACTOR`.
+    pub fn fill_powers(base: GoldilocksField, out: &mut [GoldilocksField], worker: &Worker) {
+        let step = Field::pow_u64(&base, Self::SIZE_FACTOR as u64);
+        worker.scope(out.len(), |scope, chunk_size| {
+            for (chunk_idx, chunk) in out.chunks_mut(chunk_size).enumerate() {
+                scope.spawn(move |_| {
+                    let start = Field::pow_u64(&base, (chunk_idx * chunk_size) as u64);
+                    Self::fill_powers_serial(start, base, step, chunk);
+                });
+            }
+        });
+    }
+
+    pub(super) fn fill_powers_serial(
+        start: GoldilocksField,
+        base: GoldilocksField,
+        step: GoldilocksField,
+        out: &mut [GoldilocksField],
+    ) {
+        let mut current = Self::zero(&mut ());
+        let mut power = start;
+        for lane in current.0.iter_mut() {
+            *lane = power;
+            Field::mul_assign(&mut power, &base);
+        }
+
+        for dst in out.chunks_mut(Self::SIZE_FACTOR) {
+            for (dst, src) in dst.iter_mut().zip(current.0.iter()) {
+                *dst = GoldilocksField(src.to_reduced_u64());
+            }
+            current.mul_constant_assign(&step);
+        }
+    }
+
+    /// Returns a mask with bit `i` set if lane `i` of `self` is equal to lane `i` of `other`. Both
+    /// operands are compared in canonical form, 8 lanes at a time.
+    #[inline]
+    pub fn lane_eq(&self, other: &Self) -> u16 {
+        self.lane_mask(other, |a, b| a.simd_eq(b))
+    }
+
+    /// Returns a mask with bit `i` set if lane `i` of `self` is less than lane `i` of `other`. Both
+    /// operands are compared in canonical form, 8 lanes at a time.
+    #[inline]
+    pub fn lane_lt(&self, other: &Self) -> u16 {
+        self.lane_mask(other, |a, b| a.simd_lt(b))
+    }
+
+    #[inline(always)]
+    fn lane_mask(&self, other: &Self, cmp: impl Fn(u64x8, u64x8) -> mask64x8) -> u16 {
+        let order = u64x8::splat(GoldilocksField::ORDER);
+        let reduce = |lanes: &[GoldilocksField]| {
+            let lanes = u64x8::from_array(std::array::from_fn(|j| lanes[j].0));
+            lanes.simd_ge(order).select(lanes - order, lanes)
+        };
+
+        let mut mask = 0u16;
+        for (i, (a, b)) in self
+            .0
+            .chunks_exact(8)
+            .zip(other.0.chunks_exact(8))
+            .enumerate()
+        {
+            mask |= (cmp(reduce(a), reduce(b)).to_bitmask() as u16) << (i * 8);
+        }
+
+        mask
+    }
+
+    /// Subtracts `other` and returns the mask of lanes that wrapped around the field order, i.e.
+    /// with bit `i` set if lane `i` of `self` was less than the one of `other`, as by
+    /// [`MixedGL::lane_lt`]. Lanes are compared as canonical values, so the mask doesn't depend on
+    /// the representation of the operands or on the backend.
+    #[inline]
+    pub fn sub_assign_with_borrow(&mut self, other: &Self) -> u16 {
+        let borrow = self.lane_lt(other);
+        PrimeFieldLike::sub_assign(self, other, &mut ());
+
+        borrow
+    }
+
+    /// Checks that `a` and `b` hold the same values, comparing lanes in canonical form, so
+    /// `x` and `x + ORDER` are equal. Lanes are reduced and compared 8 at a time, and the check
+    /// stops at the first mismatch. Slices of different lengths are never equal.
+    pub fn slices_equal_canonical(a: &[Self], b: &[Self]) -> bool {
+        if a.len() != b.len() {
+            return false;
+        }
+
+        let order = u64x8::splat(GoldilocksField::ORDER);
+        let reduce = |lanes: &[GoldilocksField]| {
+            let lanes = u64x8::from_array(std::array::from_fn(|j| lanes[j].0));
+            lanes.simd_ge(order).select(lanes - order, lanes)
+        };
+        a.iter().zip(b.iter()).all(|(a, b)| {
+            a.0.chunks_exact(8)
+                .zip(b.0.chunks_exact(8))
+                .all(|(a, b)| reduce(a).simd_eq(reduce(b)).all())
+        })
+    }
+
+    /// Writes into `out[i]` a mask with bit `j` set if lane `j` of `input[i]` is zero. Zero has
+    /// two `u64` representations, `0` and `ORDER`, so lanes are compared with both 8 at a time
+    /// instead of being reduced first.
+    pub fn zero_mask(input: &[Self], out: &mut [u16]) {
+        assert_eq!(input.len(), out.len(), "there must be one mask per vector");
+        let zero = u64x8::splat(0);
+        let order = u64x8::splat(GoldilocksField::ORDER);
+        for (el, dst) in input.iter().zip(out.iter_mut()) {
+            let mut mask = 0u16;
+            for (i, lanes) in el.0.chunks_exact(8).enumerate() {
+                let lanes = u64x8::from_array(std::array::from_fn(|j| lanes[j].0));
+                let is_zero = lanes.simd_eq(zero) | lanes.simd_eq(order);
+                mask |= (is_zero.to_bitmask() as u16) << (i * 8);
+            }
+            *dst = mask;
+        }
+    }
+
+    /// Takes lane `i` from `a` if bit `i` of `mask` is set, and from `b` otherwise. Lanes are
+    /// blended 8 at a time with a SIMD select on a mask built from the bits of `mask`.
+    #[inline]
+    pub fn select(mask: u16, a: &Self, b: &Self) -> Self {
+        let mut result = *b;
+        for (i, (dst, src)) in result
+            .0
+            .chunks_exact_mut(8)
+            .zip(a.0.chunks_exact(8))
+            .enumerate()
+        {
+            let lanes_mask = mask64x8::from_bitmask(((mask >> (i * 8)) & 0xff) as u64);
+            let a = u64x8::from_array(std::array::from_fn(|j| src[j].0));
+            let b = u64x8::from_array(std::array::from_fn(|j| dst[j].0));
+            for (dst, el) in dst.iter_mut().zip(lanes_mask.select(a, b).to_array()) {
+                *dst = GoldilocksField(el);
+            }
+        }
+
+        result
+    }
+
+    /// Alias of [`MixedGL::select`], under the name used by the SIMD intrinsics.
+    #[inline]
+    pub fn blend(mask: u16, when_set: &Self, when_clear: &Self) -> Self {
+        Self::select(mask, when_set, when_clear)
+    }
+
+    /// Same as `slice_from_base_slice`, but returns `None` instead of panicking if `input` can not
+    /// be reinterpreted in place, i.e. if it's not aligned to `MixedGL`, or if its length is zero
+    /// or not a multiple of `SIZE_FACTOR`. Nothing is copied.
+    pub fn try_slice_from_base_slice(input: &[GoldilocksField]) -> Option<&[Self]> {
+        let castable = !input.is_empty()
+            && input.len() % Self::SIZE_FACTOR == 0
+            && input.as_ptr().addr() % std::mem::align_of::<Self>() == 0;
+
+        castable.then(|| Self::slice_from_base_slice(input))
+    }
+
+    /// Zeroed vector of `len` elements, allocated with [`Aligned64Allocator`], so it stays
+    /// castable to and from a base vector, whatever it's converted to.
+    pub fn aligned_vec(len: usize) -> Vec<Self, Aligned64Allocator> {
+        let mut result = Vec::with_capacity_in(len, Aligned64Allocator);
+        result.resize(len, Self::zero(&mut ()));
+
+        result
+    }
+
+    /// Returns lane `i` in canonical form. Panics if `i` is out of range.
+    #[inline]
+    pub fn get_lane(&self, i: usize) -> GoldilocksField {
+        debug_assert!(i < Self::SIZE_FACTOR);
+        GoldilocksField(self.0[i].to_reduced_u64())
+    }
+
+    /// Writes `value` in canonical form into lane `i`. Panics if `i` is out of range.
+    #[inline]
+    pub fn set_lane(&mut self, i: usize, value: GoldilocksField) {
+        debug_assert!(i < Self::SIZE_FACTOR);
+        self.0[i] = GoldilocksField(value.to_reduced_u64());
+    }
+
+    /// Lane-wise [`GoldilocksField::inverse_or_zero`]. Unlike `PrimeFieldLike::inverse` it doesn't
+    /// panic if some lanes are zero, those are just mapped to zero.
+    #[inline]
+    pub fn inverse_or_zero(&self) -> Self {
+        let mut result = *self;
+        for el in result.0.iter_mut() {
+            *el = el.inverse_or_zero();
+        }
+
+        result
+    }
+
+    /// Packs raw values as they are, without reduction, e.g. for data that is already known to be
+    /// canonical. Lanes may end up in any representation up to `u64::MAX`, which all the
+    /// arithmetic accepts.
+    #[inline]
+    pub fn from_u64_array(
+        input: [u64; <MixedGL as PrimeFieldLikeVectorized>::SIZE_FACTOR],
+    ) -> Self {
+        Self::from_array(input.map(GoldilocksField))
+    }
+
+    /// Packs raw values reducing every lane to canonical form, e.g. for data that arrives as `u64`
+    /// over FFI.
+    #[inline]
+    pub fn from_u64_array_reduced(
+        input: [u64; <MixedGL as PrimeFieldLikeVectorized>::SIZE_FACTOR],
+    ) -> Self {
+        Self::from_array(input.map(GoldilocksField::from_nonreduced_u64))
+    }
+
+    /// Number of bytes packed into a lane by [`MixedGL::from_bytes_canonical`]. Any 7 bytes are
+    /// below the field order, so the packing is injective and every lane is canonical.
+    pub const BYTES_PER_LANE: usize = 7;
+
+    /// Packs `bytes` into vectors, [`MixedGL::BYTES_PER_LANE`] bytes per lane as a little-endian
+    /// integer, lanes filled in order. The last lane and the last vector are padded with zeroes,
+    /// so the byte length is returned too, for [`MixedGL::to_bytes_canonical`] to recover the
+    /// exact input.
+    pub fn from_bytes_canonical(bytes: &[u8]) -> (Vec<Self>, usize) {
+        let result = bytes
+            .chunks(Self::BYTES_PER_LANE * Self::SIZE_FACTOR)
+            .map(|chunk| {
+                let mut vector = Self::zero(&mut ());
+                for (dst, src) in vector.0.iter_mut().zip(chunk.chunks(Self::BYTES_PER_LANE)) {
+                    let mut buffer = [0u8; 8];
+                    buffer[..src.len()].copy_from_slice(src);
+                    *dst = GoldilocksField(u64::from_le_bytes(buffer));
+                }
+                vector
+            })
+            .collect();
+
+        (result, bytes.len())
+    }
+
+    /// Inverse of [`MixedGL::from_bytes_canonical`], returns the first `len` packed bytes. Panics
+    /// if `input` holds less than `len` bytes, or if one of the lanes holding them is not below
+    /// `2^56`, i.e. was not produced by the packing.
+    pub fn to_bytes_canonical(input: &[Self], len: usize) -> Vec<u8> {
+        let num_lanes = len.div_ceil(Self::BYTES_PER_LANE);
+        assert!(
+            num_lanes <= input.len() * Self::SIZE_FACTOR,
+            "{} vectors can not hold {} bytes",
+            input.len(),
+            len
+        );
+
+        let mut result = Vec::with_capacity(num_lanes * Self::BYTES_PER_LANE);
+        for lane in input.iter().flat_map(|el| el.0.iter()).take(num_lanes) {
+            let value = lane.to_reduced_u64();
+            assert!(
+                value >> (8 * Self::BYTES_PER_LANE) == 0,
+                "lane {} doesn't hold 7 bytes",
+                value
+            );
+            result.extend_from_slice(&value.to_le_bytes()[..Self::BYTES_PER_LANE]);
+        }
+        result.truncate(len);
+
+        result
+    }
+
+    /// Lane-wise [`GoldilocksField::mul_small`], 8 lanes at a time. Products of both 32-bit
+    /// halves of a lane with `c` fit into 48 bits, so the 80-bit products are assembled from them
+    /// and folded with [`MixedGL::reduce_u128x2`].
+    #[inline]
+    pub fn mul_small_assign(&mut self, c: u16) -> &mut Self {
+        let c = u64x8::splat(c as u64);
+        let low_half = u64x8::splat(u32::MAX as u64);
+        for lanes in self.0.chunks_exact_mut(8) {
+            let x = u64x8::from_array(std::array::from_fn(|j| lanes[j].0));
+            let lo_product = (x & low_half) * c;
+            let hi_product = (x >> 32) * c;
+            let lo = lo_product + (hi_product << 32);
+            let carry = lo.simd_lt(lo_product);
+            let hi = (hi_product >> 32) + carry.select(u64x8::splat(1), u64x8::splat(0));
+            for (dst, el) in lanes.iter_mut().zip(Self::reduce_u128x2(lo, hi).to_array()) {
+                *dst = GoldilocksField(el);
+            }
+        }
+
+        self
+    }
+
+    /// Lane-wise [`GoldilocksField::mul_by_two_adic_power`].
+    #[inline]
+    pub fn mul_by_two_adic_power_assign(&mut self, k: u32) -> &mut Self {
+        for el in self.0.iter_mut() {
+            *el = el.mul_by_two_adic_power(k);
+        }
+
+        self
+    }
+}
+
+impl GoldilocksField {
+    /// Multiplies every element of `input` by `by`, e.g. by a coset power or `1/n`. The slice
+    /// doesn't need to be packed or aligned: elements are copied into `MixedGL` vectors
+    /// `SIZE_FACTOR` at a time and the tail is multiplied one by one. Large slices are split
+    /// between the worker threads at vector boundaries.
+    pub fn scale_slice(input: &mut [Self], by: Self, worker: &Worker) {
+        let num_vectors = input.len() / MixedGL::SIZE_FACTOR;
+        if num_vectors < MixedGL::PARALLEL_SLICE_THRESHOLD {
+            Self::scale_slice_serial(input, &by);
+
+            return;
+        }
+
+        worker.scope(num_vectors, |scope, chunk_size| {
+            // the tail goes to the last chunk
+            for chunk in input.chunks_mut(chunk_size * MixedGL::SIZE_FACTOR) {
+                scope.spawn(move |_| {
+                    Self::scale_slice_serial(chunk, &by);
+                });
+            }
+        });
+    }
+
+    #[inline]
+    fn scale_slice_serial(input: &mut [Self], by: &Self) {
+        let mut chunks = input.chunks_exact_mut(MixedGL::SIZE_FACTOR);
+        for chunk in &mut chunks {
+            let mut vector = MixedGL::zero(&mut ());
+            vector.0.copy_from_slice(chunk);
+            vector.mul_constant_assign(by);
+            chunk.copy_from_slice(&vector.0);
+        }
+        for el in chunks.into_remainder() {
+            Field::mul_assign(el, by);
+        }
+    }
+}
+
+/// `MixedGL` that is compared and hashed by the canonical values of its lanes, for map keys and
+/// assertions that must not depend on the backend specific `PartialEq` and `Hash` of `MixedGL`.
+#[derive(Clone, Copy, Debug)]
+pub struct CanonicalMixedGL(pub MixedGL);
+
+impl PartialEq for CanonicalMixedGL {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        (0..MixedGL::SIZE_FACTOR).all(|i| self.0.get_lane(i).0 == other.0.get_lane(i).0)
+    }
+}
+
+impl Eq for CanonicalMixedGL {}
+
+impl std::hash::Hash for CanonicalMixedGL {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for i in 0..MixedGL::SIZE_FACTOR {
+            state.write_u64(self.0.get_lane(i).0);
+        }
+    }
+}
+
+impl From<MixedGL> for CanonicalMixedGL {
+    #[inline]
+    fn from(value: MixedGL) -> Self {
+        Self(value)
+    }
+}
+
+impl From<CanonicalMixedGL> for MixedGL {
+    #[inline]
+    fn from(value: CanonicalMixedGL) -> Self {
+        value.0
+    }
+}
+
+impl From<[u64; <MixedGL as PrimeFieldLikeVectorized>::SIZE_FACTOR]> for MixedGL {
+    /// Same as [`MixedGL::from_u64_array_reduced`].
+    #[inline]
+    fn from(value: [u64; <MixedGL as PrimeFieldLikeVectorized>::SIZE_FACTOR]) -> Self {
+        Self::from_u64_array_reduced(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{field::rand_from_rng, utils::clone_respecting_allignment};
+
+    fn eval_multilinear_naive(
+        evals: &[GoldilocksField],
+        point: &[GoldilocksField],
+    ) -> GoldilocksField {
+        let num_vars = point.len();
+        let mut result = GoldilocksField::ZERO;
+        for (idx, value) in evals.iter().enumerate() {
+            let mut term = *value;
+            for (j, r) in point.iter().enumerate() {
+                let bit = (idx >> (num_vars - 1 - j)) & 1;
+                if bit == 1 {
+                    Field::mul_assign(&mut term, r);
+                } else {
+                    let mut one_minus_r = GoldilocksField::ONE;
+                    Field::sub_assign(&mut one_minus_r, r);
+                    Field::mul_assign(&mut term, &one_minus_r);
+                }
+            }
+            Field::add_assign(&mut result, &term);
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_eval_multilinear() {
+        let mut rng = rand::thread_rng();
+        for num_vars in [MixedGL::SIZE_FACTOR.trailing_zeros() as usize, 6, 8] {
+            let evals: Vec<GoldilocksField> = (0..(1 << num_vars))
+                .map(|_| rand_from_rng(&mut rng))
+                .collect();
+            let point: Vec<GoldilocksField> =
+                (0..num_vars).map(|_| rand_from_rng(&mut rng)).collect();
+
+            let evals_vectorized = MixedGL::vec_from_base_vec(clone_respecting_allignment::<
+                GoldilocksField,
+                MixedGL,
+                _,
+            >(&evals));
+
+            let result = MixedGL::eval_multilinear(&evals_vectorized, &point);
+            assert_eq!(result, eval_multilinear_naive(&evals, &point));
+
+            if evals_vectorized.len() > 1 {
+                let folded = MixedGL::fold_multilinear(&evals_vectorized, point[0]);
+                assert_eq!(result, MixedGL::eval_multilinear(&folded, &point[1..]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_aggregate_constraints() {
+        let mut rng = rand::thread_rng();
+        let domain_size = MixedGL::SIZE_FACTOR * 4;
+        let mut rand_column = || -> Vec<GoldilocksField> {
+            (0..domain_size).map(|_| rand_from_rng(&mut rng)).collect()
+        };
+
+        let constraints = [rand_column(), rand_column()];
+        let selectors = [rand_column(), rand_column()];
+        let alpha: GoldilocksField = rand_from_rng(&mut rng);
+
+        let mut expected = vec![GoldilocksField::ZERO; domain_size];
+        let mut alpha_pow = GoldilocksField::ONE;
+        for (constraint, selector) in constraints.iter().zip(selectors.iter()) {
+            for ((dst, c), s) in expected
+                .iter_mut()
+                .zip(constraint.iter())
+                .zip(selector.iter())
+            {
+                let mut tmp = *c;
+                Field::mul_assign(&mut tmp, s);
+                Field::mul_assign(&mut tmp, &alpha_pow);
+                Field::add_assign(dst, &tmp);
+            }
+            Field::mul_assign(&mut alpha_pow, &alpha);
+        }
+
+        let vectorize = |values: &Vec<GoldilocksField>| {
+            MixedGL::vec_from_base_vec(clone_respecting_allignment::<GoldilocksField, MixedGL, _>(
+                values,
+            ))
+        };
+        let constraints: Vec<_> = constraints.iter().map(vectorize).collect();
+        let selectors: Vec<_> = selectors.iter().map(vectorize).collect();
+        let constraints: Vec<&[MixedGL]> = constraints.iter().map(|el| &el[..]).collect();
+        let selectors: Vec<&[MixedGL]> = selectors.iter().map(|el| &el[..]).collect();
+
+        let mut out = vec![MixedGL::new(); domain_size / MixedGL::SIZE_FACTOR];
+        MixedGL::aggregate_constraints(&constraints, &selectors, alpha, &mut out);
+
+        let out: Vec<GoldilocksField> = out.iter().flat_map(|el| el.0).collect();
+        assert_eq!(out, expected);
+    }
+
+    fn rand_non_reduced_vector<R: rand::Rng>(rng: &mut R) -> MixedGL {
+        let mut result = MixedGL::new();
+        for dst in result.0.iter_mut() {
+            let value: GoldilocksField = rand_from_rng(rng);
+            // values below EPSILON can also be represented as value + ORDER
+            *dst = if value.0 < (1 << 32) - 1 && rng.gen_bool(0.5) {
+                GoldilocksField(value.0 + GoldilocksField::ORDER)
+            } else {
+                value
+            };
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_to_reduced_wide() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let a = rand_non_reduced_vector(&mut rng);
+
+            let mut narrow = a;
+            narrow.to_reduced();
+            let mut wide = a;
+            wide.to_reduced_wide();
+            assert_eq!(wide.0.map(|el| el.0), narrow.0.map(|el| el.0));
+            assert!(wide.0.iter().all(|el| el.0 < GoldilocksField::ORDER));
+        }
+    }
+
+    #[test]
+    fn test_lane_comparisons_and_select() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let a = rand_non_reduced_vector(&mut rng);
+            let mut b = rand_non_reduced_vector(&mut rng);
+            // make some lanes equal, possibly in different representations
+            for i in 0..MixedGL::SIZE_FACTOR {
+                if rng.gen_bool(0.25) {
+                    b.0[i] = GoldilocksField(a.0[i].to_reduced_u64());
+                }
+            }
+
+            let eq_mask = a.lane_eq(&b);
+            let lt_mask = a.lane_lt(&b);
+            let select_mask: u16 = rng.gen();
+            let selected = MixedGL::select(select_mask, &a, &b);
+
+            for i in 0..MixedGL::SIZE_FACTOR {
+                let (x, y) = (a.0[i].to_reduced_u64(), b.0[i].to_reduced_u64());
+                assert_eq!(eq_mask & (1 << i) != 0, x == y);
+                assert_eq!(lt_mask & (1 << i) != 0, x < y);
+
+                let expected = if select_mask & (1 << i) != 0 { a.0[i] } else { b.0[i] };
+                assert_eq!(selected.0[i].0, expected.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduce_slice() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        for len in [1, MixedGL::PARALLEL_SLICE_THRESHOLD + 3] {
+            let mut original = vec![MixedGL::new(); len];
+            for el in original.iter_mut() {
+                for dst in el.0.iter_mut() {
+                    // every non-canonical value is in ORDER..2^64
+                    *dst = if rng.gen_bool(0.5) {
+                        GoldilocksField(rng.gen_range(GoldilocksField::ORDER..=u64::MAX))
+                    } else {
+                        rand_from_rng(&mut rng)
+                    };
+                }
+            }
+
+            let mut reduced = original.clone();
+            MixedGL::reduce_slice(&mut reduced, &worker);
+            for (reduced, original) in reduced.iter().zip(original.iter()) {
+                for (a, b) in reduced.0.iter().zip(original.0.iter()) {
+                    assert!(a.0 < GoldilocksField::ORDER);
+                    assert_eq!(a.0, b.to_reduced_u64());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_axpy() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        let alpha: GoldilocksField = rand_from_rng(&mut rng);
+        for len in [1, MixedGL::PARALLEL_SLICE_THRESHOLD * 4 + 3] {
+            let acc: Vec<GoldilocksField> = (0..len * MixedGL::SIZE_FACTOR)
+                .map(|_| rand_from_rng(&mut rng))
+                .collect();
+            let x: Vec<GoldilocksField> = (0..len * MixedGL::SIZE_FACTOR)
+                .map(|_| rand_from_rng(&mut rng))
+                .collect();
+
+            // several rounds, so accumulated values are also checked
+            let mut expected = acc.clone();
+            for _ in 0..3 {
+                for (acc, x) in expected.iter_mut().zip(x.iter()) {
+                    let mut t = *x;
+                    Field::mul_assign(&mut t, &alpha);
+                    Field::add_assign(acc, &t);
+                }
+            }
+
+            let mut result = MixedGL::vec_from_base_vec(clone_respecting_allignment::<
+                GoldilocksField,
+                MixedGL,
+                _,
+            >(&acc));
+            let x = MixedGL::vec_from_base_vec(clone_respecting_allignment::<
+                GoldilocksField,
+                MixedGL,
+                _,
+            >(&x));
+            for _ in 0..3 {
+                MixedGL::axpy(&mut result, alpha, &x, &worker);
+            }
+
+            assert_eq!(MixedGL::vec_into_base_vec(result), expected);
+        }
+    }
+
+    #[test]
+    fn test_negate_slice() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        for len in [1, MixedGL::PARALLEL_SLICE_THRESHOLD * 4 + 3] {
+            let mut values: Vec<GoldilocksField> = (0..len * MixedGL::SIZE_FACTOR)
+                .map(|_| rand_from_rng(&mut rng))
+                .collect();
+            values[0] = GoldilocksField::ZERO;
+
+            let mut expected = values.clone();
+            for el in expected.iter_mut() {
+                Field::negate(el);
+            }
+
+            let mut negated = MixedGL::vec_from_base_vec(clone_respecting_allignment::<
+                GoldilocksField,
+                MixedGL,
+                _,
+            >(&values));
+            MixedGL::negate_slice(&mut negated, &worker);
+
+            let negated = MixedGL::vec_into_base_vec(negated);
+            assert_eq!(negated, expected);
+            assert_eq!(negated[0].0, 0);
+        }
+    }
+
+    #[test]
+    fn test_horner_eval() {
+        let mut rng = rand::thread_rng();
+        let point: GoldilocksField = rand_from_rng(&mut rng);
+        for len in
+            [0, 1, MixedGL::SIZE_FACTOR - 1, MixedGL::SIZE_FACTOR, MixedGL::SIZE_FACTOR + 1, 100]
+        {
+            let coeffs: Vec<GoldilocksField> = (0..len).map(|_| rand_from_rng(&mut rng)).collect();
+
+            let mut expected = GoldilocksField::ZERO;
+            for c in coeffs.iter().rev() {
+                Field::mul_assign(&mut expected, &point);
+                Field::add_assign(&mut expected, c);
+            }
+
+            assert_eq!(MixedGL::horner_eval(&coeffs, point), expected);
+        }
+    }
+
+    #[test]
+    fn test_get_set_lane() {
+        let mut el = MixedGL::new();
+        for i in 0..MixedGL::SIZE_FACTOR {
+            el.set_lane(i, GoldilocksField(GoldilocksField::ORDER + i as u64));
+        }
+        for i in 0..MixedGL::SIZE_FACTOR {
+            assert_eq!(el.0[i].0, i as u64);
+            assert_eq!(el.get_lane(i).0, i as u64);
+        }
+
+        el.0[0] = GoldilocksField(GoldilocksField::ORDER + 5);
+        assert_eq!(el.get_lane(0).0, 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_lane_out_of_range() {
+        let el = MixedGL::new();
+        let _ = el.get_lane(MixedGL::SIZE_FACTOR);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_lane_out_of_range() {
+        let mut el = MixedGL::new();
+        el.set_lane(MixedGL::SIZE_FACTOR, GoldilocksField::ONE);
+    }
+
+    #[test]
+    fn test_fill_powers() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        let base: GoldilocksField = rand_from_rng(&mut rng);
+        for n in [0, 1, MixedGL::SIZE_FACTOR + 3, 1 << 16] {
+            let mut expected = Vec::with_capacity(n);
+            let mut current = GoldilocksField::ONE;
+            for _ in 0..n {
+                expected.push(current);
+                Field::mul_assign(&mut current, &base);
+            }
+
+            let mut powers = vec![GoldilocksField::ZERO; n];
+            MixedGL::fill_powers(base, &mut powers, &worker);
+            assert_eq!(powers, expected);
+            assert!(powers.iter().all(|el| el.0 < GoldilocksField::ORDER));
+        }
+    }
+
+    #[test]
+    fn test_equals_is_canonical() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut reduced = MixedGL::new();
+            let mut non_reduced = MixedGL::new();
+            for (a, b) in reduced.0.iter_mut().zip(non_reduced.0.iter_mut()) {
+                if rng.gen_bool(0.5) {
+                    // every value below 2^32 - 1 also has the representation `value + ORDER`
+                    let value = rng.gen_range(0..((1u64 << 32) - 1));
+                    *a = GoldilocksField(value);
+                    *b = GoldilocksField(value + GoldilocksField::ORDER);
+                } else {
+                    *a = rand_from_rng(&mut rng);
+                    *b = *a;
+                }
+            }
+
+            assert!(reduced == non_reduced);
+            assert!(non_reduced.equals(&reduced));
+
+            let lane = rng.gen_range(0..MixedGL::SIZE_FACTOR);
+            let mut other = non_reduced;
+            Field::add_assign(&mut other.0[lane], &GoldilocksField::ONE);
+            assert!(reduced != other);
+            assert!(other.equals(&reduced) == false);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least SIZE_FACTOR")]
+    fn test_cast_too_short() {
+        let values = vec![GoldilocksField::ONE; MixedGL::SIZE_FACTOR - 1];
+        let _ = MixedGL::slice_from_base_slice(&values);
+    }
+
+    #[test]
+    #[should_panic(expected = "length must be a multiple of SIZE_FACTOR")]
+    fn test_cast_not_a_multiple() {
+        let mut values = crate::utils::allocate_in_with_alignment_of::<GoldilocksField, MixedGL, _>(
+            MixedGL::SIZE_FACTOR * 2,
+            std::alloc::Global,
+        );
+        values.resize(MixedGL::SIZE_FACTOR + 1, GoldilocksField::ONE);
+        let _ = MixedGL::slice_from_base_slice(&values);
+    }
+
+    #[test]
+    #[should_panic(expected = "address must be aligned to")]
+    fn test_cast_misaligned() {
+        let mut values = crate::utils::allocate_in_with_alignment_of::<GoldilocksField, MixedGL, _>(
+            MixedGL::SIZE_FACTOR * 2,
+            std::alloc::Global,
+        );
+        values.resize(MixedGL::SIZE_FACTOR * 2, GoldilocksField::ONE);
+        let _ = MixedGL::slice_from_base_slice(&values[1..][..MixedGL::SIZE_FACTOR]);
+    }
+
+    #[test]
+    fn test_try_slice_from_base_slice() {
+        let mut rng = rand::thread_rng();
+        let values: Vec<GoldilocksField> = (0..MixedGL::SIZE_FACTOR * 3)
+            .map(|_| rand_from_rng(&mut rng))
+            .collect();
+        let values = clone_respecting_allignment::<GoldilocksField, MixedGL, _>(&values);
+
+        let full = MixedGL::try_slice_from_base_slice(&values).unwrap();
+        assert_eq!(full.len(), 3);
+        assert_eq!(MixedGL::slice_into_base_slice(full), &values[..]);
+        assert_eq!(full.as_ptr() as *const GoldilocksField, values.as_ptr());
+
+        let aligned_subslice = &values[MixedGL::SIZE_FACTOR..][..MixedGL::SIZE_FACTOR];
+        let vector = MixedGL::try_slice_from_base_slice(aligned_subslice).unwrap();
+        assert_eq!(vector.len(), 1);
+        assert_eq!(MixedGL::slice_into_base_slice(vector), aligned_subslice);
+
+        // misaligned
+        assert!(MixedGL::try_slice_from_base_slice(&values[1..][..MixedGL::SIZE_FACTOR]).is_none());
+        // not a multiple of SIZE_FACTOR
+        assert!(MixedGL::try_slice_from_base_slice(&values[..MixedGL::SIZE_FACTOR + 1]).is_none());
+        assert!(MixedGL::try_slice_from_base_slice(&values[..MixedGL::SIZE_FACTOR - 1]).is_none());
+        assert!(MixedGL::try_slice_from_base_slice(&values[..0]).is_none());
+    }
+
+    #[test]
+    fn test_aligned_vec() {
+        for len in [0, 1, 3, 100] {
+            let mut vectors = MixedGL::aligned_vec(len);
+            assert_eq!(vectors.len(), len);
+            assert_eq!(vectors.as_ptr().addr() % 64, 0);
+
+            let base = MixedGL::slice_into_base_slice(&vectors);
+            assert_eq!(base.len(), len * MixedGL::SIZE_FACTOR);
+            assert!(base.iter().all(|el| el.is_zero()));
+
+            for (i, el) in MixedGL::slice_into_base_slice_mut(&mut vectors)
+                .iter_mut()
+                .enumerate()
+            {
+                *el = GoldilocksField(i as u64);
+            }
+            if len == 0 {
+                continue;
+            }
+            let base = MixedGL::vec_into_base_vec(vectors);
+            assert_eq!(base.as_ptr().addr() % 64, 0);
+            let vectors = MixedGL::vec_from_base_vec(base);
+            for (i, el) in MixedGL::slice_into_base_slice(&vectors).iter().enumerate() {
+                assert_eq!(el.0, i as u64);
+            }
+        }
+
+        // base vectors from the same allocator are always castable
+        let mut base = Vec::with_capacity_in(MixedGL::SIZE_FACTOR * 2, Aligned64Allocator);
+        base.resize(MixedGL::SIZE_FACTOR * 2, GoldilocksField::ONE);
+        assert_eq!(MixedGL::slice_from_base_slice(&base).len(), 2);
+    }
+
+    #[test]
+    fn test_canonical_mixedgl() {
+        use std::{collections::HashSet, hash::BuildHasher};
+
+        // small values, so all of them have a second representation
+        let x = MixedGL::from_array(std::array::from_fn(|i| GoldilocksField(i as u64 * 3)));
+        let mut y = x;
+        for el in y.0.iter_mut() {
+            el.0 += GoldilocksField::ORDER;
+        }
+        assert!(x.0.iter().zip(y.0.iter()).all(|(a, b)| a.0 != b.0));
+
+        let (cx, cy) = (CanonicalMixedGL::from(x), CanonicalMixedGL::from(y));
+        assert_eq!(cx, cy);
+        let hasher = std::collections::hash_map::RandomState::new();
+        assert_eq!(hasher.hash_one(cx), hasher.hash_one(cy));
+        let set: HashSet<CanonicalMixedGL> = [cx, cy].into_iter().collect();
+        assert_eq!(set.len(), 1);
+
+        let mut z = x;
+        Field::add_assign(&mut z.0[MixedGL::SIZE_FACTOR - 1], &GoldilocksField::ONE);
+        assert_ne!(cx, CanonicalMixedGL(z));
+
+        let back: MixedGL = cy.into();
+        assert_eq!(back.0.map(|el| el.0), y.0.map(|el| el.0));
+    }
+
+    #[test]
+    fn test_chunks_vectorized() {
+        let mut rng = rand::thread_rng();
+        let values: Vec<GoldilocksField> = (0..MixedGL::SIZE_FACTOR * 3 + 5)
+            .map(|_| rand_from_rng(&mut rng))
+            .collect();
+        let values = clone_respecting_allignment::<GoldilocksField, MixedGL, _>(&values);
+
+        for len in [
+            0,
+            1,
+            MixedGL::SIZE_FACTOR - 1,
+            MixedGL::SIZE_FACTOR,
+            MixedGL::SIZE_FACTOR * 3,
+            MixedGL::SIZE_FACTOR * 3 + 5,
+        ] {
+            let (vectors, tail) = MixedGL::chunks_vectorized(&values[..len]);
+            assert_eq!(vectors.len(), len / MixedGL::SIZE_FACTOR);
+            assert_eq!(tail.len(), len % MixedGL::SIZE_FACTOR);
+            assert_eq!([MixedGL::slice_into_base_slice(vectors), tail].concat(), &values[..len]);
+            if !vectors.is_empty() {
+                assert_eq!(vectors.as_ptr() as *const GoldilocksField, values.as_ptr());
+            }
+        }
+
+        // misaligned start, nothing can be cast
+        let (vectors, tail) = MixedGL::chunks_vectorized(&values[1..]);
+        assert!(vectors.is_empty());
+        assert_eq!(tail, &values[1..]);
+
+        // aligned again after a full vector
+        let (vectors, tail) = MixedGL::chunks_vectorized(&values[MixedGL::SIZE_FACTOR..]);
+        assert_eq!(vectors.len(), 2);
+        assert_eq!(tail, &values[MixedGL::SIZE_FACTOR * 3..]);
+
+        // trivial for the base field itself
+        let (vectors, tail) = GoldilocksField::chunks_vectorized(&values[1..]);
+        assert_eq!(vectors, &values[1..]);
+        assert!(tail.is_empty());
+    }
+
+    #[test]
+    fn test_square_matches_mul() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut a = MixedGL::zero(&mut ());
+            for (i, el) in a.0.iter_mut().enumerate() {
+                *el = rand_from_rng(&mut rng);
+                // some non-canonical lanes and edge values
+                if i % 4 == 1 {
+                    el.0 = GoldilocksField::ORDER + (el.0 >> 33);
+                }
+            }
+            a.0[0] = GoldilocksField(u64::MAX);
+            a.0[2] = GoldilocksField(GoldilocksField::ORDER - 1);
+
+            let mut expected = a;
+            expected.mul_assign(&a, &mut ());
+            let mut squared = a;
+            squared.square(&mut ());
+            assert_eq!(squared.0, expected.0);
+
+            for (el, src) in squared.0.iter().zip(a.0.iter()) {
+                let mut t = *src;
+                Field::square(&mut t);
+                assert_eq!(*el, t);
+            }
+        }
+    }
+
+    #[test]
+    fn test_active_backend() {
+        // same conditions as the ones selecting the implementation modules
+        #[cfg(all(
+            target_feature = "avx512bw",
+            target_feature = "avx512cd",
+            target_feature = "avx512dq",
+            target_feature = "avx512f",
+            target_feature = "avx512vl"
+        ))]
+        let expected = (Backend::Avx512, 8);
+        #[cfg(all(
+            target_feature = "avx512f",
+            target_feature = "avx512vl",
+            not(any(
+                target_feature = "avx512bw",
+                target_feature = "avx512cd",
+                target_feature = "avx512dq"
+            ))
+        ))]
+        let expected = (Backend::Avx512F, 16);
+        #[cfg(all(
+            target_feature = "neon",
+            not(all(target_feature = "avx512f", target_feature = "avx512vl"))
+        ))]
+        let expected = (Backend::Neon, 16);
+        #[cfg(all(
+            target_feature = "avx2",
+            not(target_feature = "neon"),
+            not(all(target_feature = "avx512f", target_feature = "avx512vl"))
+        ))]
+        let expected = (Backend::Avx2, 16);
+        #[cfg(not(any(
+            all(target_feature = "avx512f", target_feature = "avx512vl"),
+            target_feature = "neon",
+            target_feature = "avx2"
+        )))]
+        let expected = (Backend::Generic, 16);
+
+        assert_eq!((MixedGL::active_backend(), MixedGL::SIZE_FACTOR), expected);
+    }
+
+    #[test]
+    fn test_add_assign_unequal() {
+        fn pack_padded(coeffs: &[GoldilocksField]) -> Vec<MixedGL> {
+            let mut padded = coeffs.to_vec();
+            padded
+                .resize(coeffs.len().next_multiple_of(MixedGL::SIZE_FACTOR), GoldilocksField::ZERO);
+            MixedGL::vec_from_base_vec(clone_respecting_allignment::<GoldilocksField, MixedGL, _>(
+                &padded,
+            ))
+        }
+
+        let mut rng = rand::thread_rng();
+        for (a_len, b_len) in [(37, 100), (100, 37), (16, 16), (1, 31), (48, 49)] {
+            let a: Vec<GoldilocksField> = (0..a_len).map(|_| rand_from_rng(&mut rng)).collect();
+            let b: Vec<GoldilocksField> = (0..b_len).map(|_| rand_from_rng(&mut rng)).collect();
+
+            let mut expected = vec![GoldilocksField::ZERO; std::cmp::max(a_len, b_len)];
+            for (dst, src) in expected.iter_mut().zip(a.iter()) {
+                Field::add_assign(dst, src);
+            }
+            for (dst, src) in expected.iter_mut().zip(b.iter()) {
+                Field::add_assign(dst, src);
+            }
+
+            let mut acc = pack_padded(&a);
+            MixedGL::add_assign_unequal(&mut acc, &pack_padded(&b));
+            assert_eq!(acc.len(), expected.len().div_ceil(MixedGL::SIZE_FACTOR));
+            let result = MixedGL::slice_into_base_slice(&acc);
+            assert_eq!(&result[..expected.len()], &expected[..], "{} + {}", a_len, b_len);
+            assert!(result[expected.len()..].iter().all(|el| Field::is_zero(el)));
+        }
+    }
+
+    #[test]
+    fn test_scale_slice() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        let by: GoldilocksField = rand_from_rng(&mut rng);
+        let large = MixedGL::PARALLEL_SLICE_THRESHOLD * MixedGL::SIZE_FACTOR * 3 + 5;
+        for len in [0, 1, MixedGL::SIZE_FACTOR - 1, MixedGL::SIZE_FACTOR * 7 + 3, large] {
+            let values: Vec<GoldilocksField> =
+                (0..len + 1).map(|_| rand_from_rng(&mut rng)).collect();
+            let mut expected = values.clone();
+            for el in expected.iter_mut() {
+                Field::mul_assign(el, &by);
+            }
+
+            // also starting at an unaligned address
+            for offset in [0, 1] {
+                let mut result = values[offset..][..len].to_vec();
+                GoldilocksField::scale_slice(&mut result, by, &worker);
+                assert_eq!(result, &expected[offset..][..len], "invalid result for length {}", len);
+
+                let mut result = values.clone();
+                GoldilocksField::scale_slice(&mut result[offset..][..len], by, &worker);
+                assert_eq!(&result[offset..][..len], &expected[offset..][..len]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vec_ops_parallel() {
+        // below and just above the threshold, where the slice is split into uneven chunks
+        let worker = Worker::new_with_num_threads(4);
+        let mut rng = rand::thread_rng();
+        for len in [100, MixedGL::PARALLEL_SLICE_THRESHOLD + 37] {
+            let a: Vec<MixedGL> = (0..len)
+                .map(|_| MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng))))
+                .collect();
+            let b: Vec<MixedGL> = (0..len)
+                .map(|_| MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng))))
+                .collect();
+
+            let mut expected = a.clone();
+            MixedGL::vec_add_assign(&mut expected, &b);
+            let mut result = a.clone();
+            MixedGL::vec_add_assign_parallel(&mut result, &b, &worker);
+            assert!(result.iter().zip(expected.iter()).all(|(r, e)| r.0 == e.0));
+
+            let mut expected = a.clone();
+            MixedGL::vec_mul_assign(&mut expected, &b);
+            let mut result = a;
+            MixedGL::vec_mul_assign_parallel(&mut result, &b, &worker);
+            assert!(result.iter().zip(expected.iter()).all(|(r, e)| r.0 == e.0));
+        }
+    }
+
+    #[test]
+    fn test_bytes_canonical() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let vector_bytes = MixedGL::BYTES_PER_LANE * MixedGL::SIZE_FACTOR;
+        for len in [0, 1, 6, 7, 8, 13, vector_bytes - 1, vector_bytes, vector_bytes + 1, 1000] {
+            let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let (packed, packed_len) = MixedGL::from_bytes_canonical(&bytes);
+            assert_eq!(packed_len, len);
+            assert_eq!(packed.len(), len.div_ceil(vector_bytes));
+            assert!(
+                packed
+                    .iter()
+                    .flat_map(|el| el.0.iter())
+                    .all(|el| el.0 < 1 << 56)
+            );
+            assert_eq!(MixedGL::to_bytes_canonical(&packed, packed_len), bytes);
+        }
+
+        let (packed, _) = MixedGL::from_bytes_canonical(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(packed[0].0[0].0, 0x07060504030201);
+        assert_eq!(packed[0].0[1].0, 8);
+        assert!(packed[0].0[2..].iter().all(|el| el.0 == 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "can not hold")]
+    fn test_bytes_canonical_too_long() {
+        let (packed, len) = MixedGL::from_bytes_canonical(&[1, 2, 3]);
+        MixedGL::to_bytes_canonical(&packed, len + 7 * MixedGL::SIZE_FACTOR);
+    }
+
+    #[test]
+    fn test_slices_equal_canonical() {
+        let mut rng = rand::thread_rng();
+        // just over one pass of the non-canonical lane below through all the lanes
+        let len = MixedGL::SIZE_FACTOR + 1;
+        let a: Vec<MixedGL> = (0..len)
+            .map(|_| MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng))))
+            .collect();
+        assert!(MixedGL::slices_equal_canonical(&a, &a.clone()));
+        assert!(MixedGL::slices_equal_canonical(&[], &[]));
+        assert!(!MixedGL::slices_equal_canonical(&a, &a[1..]));
+
+        // non-canonical representation in one lane of every vector, cycling through the lanes
+        let mut nonreduced = a.clone();
+        for (i, vector) in nonreduced.iter_mut().enumerate() {
+            let lane = i % MixedGL::SIZE_FACTOR;
+            vector.0[lane] = GoldilocksField((vector.0[lane].0 >> 33) + GoldilocksField::ORDER);
+        }
+        let mut expected = a.clone();
+        for (i, vector) in expected.iter_mut().enumerate() {
+            let lane = i % MixedGL::SIZE_FACTOR;
+            vector.0[lane] = GoldilocksField(vector.0[lane].0 >> 33);
+        }
+        assert!(
+            nonreduced
+                .iter()
+                .zip(expected.iter())
+                .any(|(a, b)| a.0 != b.0)
+        );
+        assert!(MixedGL::slices_equal_canonical(&nonreduced, &expected));
+        assert!(MixedGL::slices_equal_canonical(&expected, &nonreduced));
+
+        for (idx, lane) in [(0, 0), (len / 2, 3), (len - 1, MixedGL::SIZE_FACTOR - 1)] {
+            let mut modified = a.clone();
+            Field::add_assign(&mut modified[idx].0[lane], &GoldilocksField(1));
+            assert!(!MixedGL::slices_equal_canonical(&a, &modified));
+            assert!(!MixedGL::slices_equal_canonical(&modified, &a));
+        }
+    }
+
+    #[test]
+    fn test_sub_assign_with_borrow() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut a = MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng)));
+            let mut b = MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng)));
+            // equal lanes, and small values with non-canonical representations
+            b.0[0] = a.0[0];
+            a.0[1] = GoldilocksField(GoldilocksField::ORDER + rng.gen_range(0..100));
+            b.0[1] = GoldilocksField(rng.gen_range(0..100));
+            b.0[2] = GoldilocksField(GoldilocksField::ORDER);
+
+            let mut result = a;
+            let borrow = result.sub_assign_with_borrow(&b);
+            let mut expected = a;
+            PrimeFieldLike::sub_assign(&mut expected, &b, &mut ());
+            assert_eq!(result.0, expected.0);
+            for i in 0..MixedGL::SIZE_FACTOR {
+                let expected = a.0[i].to_reduced_u64() < b.0[i].to_reduced_u64();
+                assert_eq!(borrow & (1 << i) != 0, expected, "invalid borrow of lane {}", i);
+            }
+            assert_eq!(borrow & 1, 0);
+        }
+    }
+
+    #[test]
+    fn test_blend() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let all_set = ((1u32 << MixedGL::SIZE_FACTOR) - 1) as u16;
+        for iteration in 0..100 {
+            let a = MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng)));
+            let b = MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng)));
+            let mask = match iteration {
+                0 => all_set,
+                1 => 0,
+                _ => rng.gen::<u16>() & all_set,
+            };
+
+            let result = MixedGL::blend(mask, &a, &b);
+            for i in 0..MixedGL::SIZE_FACTOR {
+                let expected = if mask & (1 << i) != 0 { a.0[i] } else { b.0[i] };
+                assert_eq!(result.0[i].0, expected.0, "invalid lane {} for mask {:b}", i, mask);
+            }
+            assert_eq!(result.0, MixedGL::select(mask, &a, &b).0);
+        }
+    }
+
+    #[test]
+    fn test_zero_mask() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut input = vec![MixedGL::new(); 100];
+        for el in input.iter_mut() {
+            for dst in el.0.iter_mut() {
+                *dst = match rng.gen_range(0..4) {
+                    0 => GoldilocksField(0),
+                    1 => GoldilocksField(GoldilocksField::ORDER),
+                    2 => GoldilocksField(rng.gen_range(GoldilocksField::ORDER..=u64::MAX)),
+                    _ => rand_from_rng(&mut rng),
+                };
+            }
+        }
+
+        let mut masks = vec![0u16; input.len()];
+        MixedGL::zero_mask(&input, &mut masks);
+        for (el, mask) in input.iter().zip(masks.iter()) {
+            for (i, lane) in el.0.iter().enumerate() {
+                assert_eq!(mask & (1 << i) != 0, Field::is_zero(lane));
+            }
+            assert_eq!(*mask, el.lane_eq(&MixedGL::new()));
+        }
+    }
+
+    #[test]
+    fn test_reduce_u128x2() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let mut values: [u128; 8] = std::array::from_fn(|_| rng.gen());
+            // products of two field elements, and the limb borders
+            values[0] = (GoldilocksField::ORDER as u128 - 1) * (GoldilocksField::ORDER as u128 - 1);
+            values[1] = u128::MAX;
+            values[2] = (u64::MAX as u128) << 64;
+            values[3] = u64::MAX as u128;
+            values[4] = 0;
+
+            let lo = u64x8::from_array(values.map(|el| el as u64));
+            let hi = u64x8::from_array(values.map(|el| (el >> 64) as u64));
+            let reduced = MixedGL::reduce_u128x2(lo, hi);
+            for (value, reduced) in values.iter().zip(reduced.to_array()) {
+                assert_eq!(reduced, GoldilocksField::from_u128_with_reduction(*value).0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse_or_zero() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut a = MixedGL::zero(&mut ());
+            for i in 0..MixedGL::SIZE_FACTOR {
+                // every third lane is zero, half of them in non-canonical form
+                a.0[i] = match i % 6 {
+                    0 => GoldilocksField::ZERO,
+                    3 => GoldilocksField(GoldilocksField::ORDER),
+                    _ => rand_from_rng(&mut rng),
+                };
+            }
+
+            let inverse = a.inverse_or_zero();
+            for i in 0..MixedGL::SIZE_FACTOR {
+                let value = a.get_lane(i);
+                if value.is_zero() {
+                    assert_eq!(inverse.get_lane(i), GoldilocksField::ZERO);
+                } else {
+                    let mut t = value;
+                    Field::mul_assign(&mut t, &inverse.get_lane(i));
+                    assert_eq!(t, GoldilocksField::ONE);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_u64_array() {
+        let mut rng = rand::thread_rng();
+        // non-canonical values in every other lane, including the largest one
+        let raw: [u64; MixedGL::SIZE_FACTOR] = std::array::from_fn(|i| match i % 4 {
+            0 => GoldilocksField::ORDER + i as u64,
+            1 => rand_from_rng::<_, GoldilocksField>(&mut rng).0,
+            2 => u64::MAX,
+            _ => i as u64,
+        });
+
+        let reduced = MixedGL::from_u64_array_reduced(raw);
+        for (i, (el, raw)) in reduced.0.iter().zip(raw.iter()).enumerate() {
+            assert!(el.0 < GoldilocksField::ORDER, "lane {} is not reduced", i);
+            assert_eq!(el.0, raw % GoldilocksField::ORDER, "invalid lane {}", i);
+        }
+        assert_eq!(MixedGL::from(raw).0, reduced.0);
+
+        let unchecked = MixedGL::from_u64_array(raw);
+        for (i, (el, raw)) in unchecked.0.iter().zip(raw.iter()).enumerate() {
+            assert_eq!(el.0, *raw, "invalid lane {}", i);
+        }
+        assert_eq!(unchecked, reduced);
+    }
+
+    #[test]
+    fn test_mul_small() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..64 {
+            let mut input = MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng)));
+            input.0[0] = GoldilocksField(u64::MAX);
+            input.0[1] = GoldilocksField(GoldilocksField::ORDER);
+
+            for c in (0..=16).chain([0x1234, u16::MAX]) {
+                let mut result = input;
+                result.mul_small_assign(c);
+                let mut expected = input;
+                expected.mul_constant_assign(&GoldilocksField(c as u64));
+                result.to_reduced();
+                expected.to_reduced();
+                assert_eq!(result, expected, "invalid product by {}", c);
+            }
+
+            for k in [0, 1, 32, 64, 95, 96, 150] {
+                let mut result = input;
+                result.mul_by_two_adic_power_assign(k);
+                let mut expected = input;
+                expected.mul_constant_assign(&Field::pow_u64(&GoldilocksField(2), k as u64));
+                result.to_reduced();
+                expected.to_reduced();
+                assert_eq!(result, expected, "invalid product by 2^{}", k);
+            }
+        }
+    }
+}
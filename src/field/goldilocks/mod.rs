@@ -2,7 +2,10 @@
 // with extra modifications for compile-time evaluations. Even though we can not use "const trait"
 // for now, one can use "_impl" const fn methods in non-generic contexts
 
-use std::hash::{Hash, Hasher};
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
 
 use crate::{
     field::{
@@ -13,7 +16,13 @@ use crate::{
 };
 
 mod extension;
+#[cfg(feature = "ff_impls")]
+mod ff_impls;
 mod inversion;
+mod mixedgl8;
+mod mixedgl_common;
+pub mod poly;
+mod seeded_rng;
 
 #[cfg(all(
     any(target_feature = "neon", target_feature = "avx2"),
@@ -77,8 +86,27 @@ pub use generic_impl::*;
 ))]
 pub use x86_64_asm_impl::*;
 
-pub use self::extension::GoldilocksExt2;
+/// Implementation of `MixedGL` selected at compile time from the target features, see
+/// `MixedGL::active_backend`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// Plain arrays of `GoldilocksField`, `generic_impl`.
+    Generic,
+    /// `std::simd` over 256-bit vectors with AVX2, `arm_asm_impl`.
+    Avx2,
+    /// `std::simd` over 256-bit vectors with NEON, `arm_asm_impl`.
+    Neon,
+    /// AVX-512 F and VL only, `x86_64_asm_impl`.
+    Avx512F,
+    /// Full AVX-512 with BW, CD and DQ, `avx512_impl`. The only one with 8 lanes instead of 16.
+    Avx512,
+}
+
 use self::inversion::try_inverse_u64;
+pub use self::{
+    extension::GoldilocksExt2, mixedgl8::MixedGL8, mixedgl_common::CanonicalMixedGL,
+    seeded_rng::SeededFieldRng,
+};
 use super::SqrtField;
 
 const EPSILON: u64 = (1 << 32) - 1;
@@ -91,7 +119,7 @@ const EPSILON: u64 = (1 << 32) - 1;
 ///   = 2**64 - 2**32 + 1
 ///   = 2**32 * (2**32 - 1) + 1
 /// ```
-#[derive(Clone, Copy, Default, serde::Deserialize)]
+#[derive(Clone, Copy, Default)]
 #[repr(transparent)]
 pub struct GoldilocksField(pub u64);
 
@@ -106,6 +134,25 @@ impl serde::Serialize for GoldilocksField {
     }
 }
 
+// and only accept the normalized form, so every value has a single encoding
+impl<'de> serde::Deserialize<'de> for GoldilocksField {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = <u64 as serde::Deserialize>::deserialize(deserializer)?;
+        if value >= Self::ORDER {
+            return Err(serde::de::Error::custom(format_args!(
+                "non-canonical Goldilocks field element {}",
+                value
+            )));
+        }
+
+        Ok(Self(value))
+    }
+}
+
 impl GoldilocksField {
     pub const MULTIPLICATIVE_GROUP_GENERATOR: Self = Self(7);
     pub const RADIX_2_SUBGROUP_GENERATOR: Self = Self(0x185629dcda58878c);
@@ -170,6 +217,27 @@ impl GoldilocksField {
         Self(c)
     }
 
+    /// Balanced representative of `self` in `[-(ORDER - 1) / 2, (ORDER - 1) / 2]`, that is
+    /// handy to read values that encode small signed numbers. `ORDER` is odd, so there is no
+    /// element that sits exactly at `ORDER / 2` and the range is symmetric.
+    #[inline(always)]
+    pub const fn to_balanced_i64(&self) -> i64 {
+        let c = self.to_reduced_u64();
+        if c <= (Self::ORDER - 1) / 2 {
+            c as i64
+        } else {
+            // |c - ORDER| <= (ORDER - 1) / 2 < 2^63
+            -((Self::ORDER - c) as i64)
+        }
+    }
+
+    /// Maps `x` to `x mod ORDER`, so it's the inverse of [`GoldilocksField::to_balanced_i64`].
+    #[inline(always)]
+    pub const fn from_i64(x: i64) -> Self {
+        // |x| <= 2^63 < ORDER, so a single subtraction is enough
+        if x >= 0 { Self(x as u64) } else { Self(Self::ORDER - x.unsigned_abs()) }
+    }
+
     #[inline(always)]
     pub const fn add_reduced_u64(&self, rhs: u64) -> Self {
         let (res_wrapped, carry) = self.0.overflowing_add(rhs);
@@ -254,6 +322,140 @@ impl GoldilocksField {
 
         self
     }
+
+    /// Same as `PrimeField::multiplicative_generator`, but usable in const contexts.
+    #[inline(always)]
+    pub const fn multiplicative_generator() -> Self {
+        Self::MULTIPLICATIVE_GROUP_GENERATOR
+    }
+
+    /// Generator of the subgroup of order `2^TWO_ADICITY`, same as
+    /// `PrimeField::radix_2_subgroup_generator`.
+    #[inline(always)]
+    pub const fn two_adic_generator() -> Self {
+        Self::RADIX_2_SUBGROUP_GENERATOR
+    }
+
+    /// Primitive `2^log_n`-th root of unity in canonical form, or `None` if `log_n` is larger than
+    /// `TWO_ADICITY`. It's obtained by repeated squaring of [`Self::two_adic_generator`], so it's
+    /// the same root as `domain_generator_for_size(1 << log_n)` used for the FFT twiddles.
+    pub const fn root_of_unity(log_n: usize) -> Option<Self> {
+        if log_n > Self::TWO_ADICITY {
+            return None;
+        }
+
+        let mut result = Self::RADIX_2_SUBGROUP_GENERATOR;
+        let mut i = log_n;
+        while i < Self::TWO_ADICITY {
+            result.square_impl();
+            i += 1;
+        }
+
+        Some(Self(result.to_reduced_u64()))
+    }
+
+    /// Inverse of `self`, or zero if `self` is zero (in any representation). Unlike
+    /// `PrimeField::inverse` this never fails, which is convenient in data dependent code.
+    #[inline]
+    pub fn inverse_or_zero(&self) -> Self {
+        try_inverse_u64(&Self(self.to_reduced_u64())).unwrap_or(Self::ZERO)
+    }
+
+    /// Canonical `[base, base^2, base^4, ..., base^(2^(n - 1))]`, i.e. `base` followed by `n - 1`
+    /// successive squarings.
+    pub fn square_ladder(base: Self, n: usize) -> Vec<Self> {
+        let mut result = Vec::with_capacity(n);
+        let mut current = base;
+        for _ in 0..n {
+            result.push(Self(current.to_reduced_u64()));
+            current.square_impl();
+        }
+
+        result
+    }
+
+    /// Canonical `[base, base^2, base^3, ..., base^n]`. Long tables are filled
+    /// `MixedGL::SIZE_FACTOR` powers at a time, like [`MixedGL::fill_powers`], but on the current
+    /// thread.
+    pub fn power_table(base: Self, n: usize) -> Vec<Self> {
+        use crate::field::traits::field_like::PrimeFieldLikeVectorized;
+
+        if n < MixedGL::SIZE_FACTOR * 4 {
+            let mut result = Vec::with_capacity(n);
+            let mut current = base;
+            for _ in 0..n {
+                result.push(Self(current.to_reduced_u64()));
+                current.mul_assign_impl(&base);
+            }
+
+            return result;
+        }
+
+        let mut result = vec![Self::ZERO; n];
+        let step = Field::pow_u64(&base, MixedGL::SIZE_FACTOR as u64);
+        MixedGL::fill_powers_serial(base, base, step, &mut result);
+
+        result
+    }
+
+    /// `self * c` for a small constant, e.g. an MDS matrix entry. The product has at most 80 bits,
+    /// so instead of the full reduction its high part is folded with a single `2^64 = EPSILON`
+    /// step and an addition.
+    #[inline(always)]
+    pub const fn mul_small(&self, c: u16) -> Self {
+        let product = (self.0 as u128) * (c as u128);
+        let (lo, hi) = split(product);
+        // hi < 2^16, so hi * EPSILON < 2^48 is canonical
+        Self(lo).add_reduced_u64(hi * EPSILON)
+    }
+
+    /// `self * 2^k` by shifts. Powers of two repeat with period 192, and `2^96 = -1`, so larger
+    /// exponents only cost a negation.
+    #[inline]
+    pub const fn mul_by_two_adic_power(&self, k: u32) -> Self {
+        let k = k % 192;
+        let (mut k, negate) = if k >= 96 { (k - 96, true) } else { (k, false) };
+
+        let mut result = *self;
+        while k > 0 {
+            let step = if k > 64 { 64 } else { k };
+            result = Self::from_u128_with_reduction((result.0 as u128) << step);
+            k -= step;
+        }
+
+        let reduced = result.to_reduced_u64();
+        if negate && reduced != 0 { Self(Self::ORDER - reduced) } else { result }
+    }
+
+    /// Canonical value as 8 little-endian bytes, the same encoding as `serde` with `bincode`.
+    #[inline]
+    pub const fn to_le_bytes(&self) -> [u8; 8] {
+        self.to_reduced_u64().to_le_bytes()
+    }
+
+    /// Canonical value as 8 big-endian bytes.
+    #[inline]
+    pub const fn to_be_bytes(&self) -> [u8; 8] {
+        self.to_reduced_u64().to_be_bytes()
+    }
+
+    /// Inverse of [`Self::to_le_bytes`]. Returns `None` if the value is not canonical, i.e. not
+    /// below the field order, so every element has exactly one accepted encoding.
+    #[inline]
+    pub const fn from_le_bytes_reduced(bytes: [u8; 8]) -> Option<Self> {
+        Self::from_canonical_u64(u64::from_le_bytes(bytes))
+    }
+
+    /// Inverse of [`Self::to_be_bytes`]. Returns `None` if the value is not canonical.
+    #[inline]
+    pub const fn from_be_bytes_reduced(bytes: [u8; 8]) -> Option<Self> {
+        Self::from_canonical_u64(u64::from_be_bytes(bytes))
+    }
+
+    #[inline]
+    const fn from_canonical_u64(value: u64) -> Option<Self> {
+        if value < Self::ORDER { Some(Self(value)) } else { None }
+    }
 }
 
 impl PartialEq for GoldilocksField {
@@ -270,22 +472,122 @@ impl PartialEq<u64> for GoldilocksField {
 
 impl Eq for GoldilocksField {}
 
+// Both hashing and ordering work over the canonical representation, so values that only differ by
+// a multiple of the modulus (as left by lazy reduction) are the same map key and sort together.
+// It costs one conditional subtraction per operand.
 impl Hash for GoldilocksField {
     fn hash<H: Hasher>(&self, state: &mut H) {
         state.write_u64(self.to_reduced_u64())
     }
 }
 
+impl PartialOrd for GoldilocksField {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GoldilocksField {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_reduced_u64().cmp(&other.to_reduced_u64())
+    }
+}
+
 impl std::fmt::Display for GoldilocksField {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "0x{:016x}", self.to_reduced_u64())
     }
 }
 
+// Both print the canonical value. Debug additionally shows the stored one if it's not canonical,
+// as it's handy when chasing lazy reduction bugs
 impl std::fmt::Debug for GoldilocksField {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "0x{:016x}", self.to_reduced_u64())
-        // write!(f, "0x{:016x}", self.0)
+        let reduced = self.to_reduced_u64();
+        if reduced == self.0 {
+            write!(f, "0x{:016x}", reduced)
+        } else {
+            write!(f, "0x{:016x} (raw: 0x{:016x})", reduced, self.0)
+        }
+    }
+}
+
+// Everything narrower than 64 bits is below the modulus, so those conversions are infallible and
+// always canonical. Arbitrary `u64` either goes through `TryFrom`, or explicitly through
+// `from_nonreduced_u64`
+macro_rules! impl_from_small_uint {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for GoldilocksField {
+                #[inline(always)]
+                fn from(value: $t) -> Self {
+                    Self(value as u64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_small_uint!(bool, u8, u16, u32);
+
+/// The value is not below [`GoldilocksField::ORDER`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonCanonicalValueError(pub u64);
+
+impl std::fmt::Display for NonCanonicalValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:016x} is not a canonical Goldilocks field element", self.0)
+    }
+}
+
+impl std::error::Error for NonCanonicalValueError {}
+
+impl TryFrom<u64> for GoldilocksField {
+    type Error = NonCanonicalValueError;
+
+    #[inline(always)]
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        if value >= Self::ORDER { Err(NonCanonicalValueError(value)) } else { Ok(Self(value)) }
+    }
+}
+
+#[cfg(feature = "num_traits_impls")]
+impl num_traits::Zero for GoldilocksField {
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    // canonical, so `ORDER` is zero too
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        Field::is_zero(self)
+    }
+}
+
+#[cfg(feature = "num_traits_impls")]
+impl num_traits::One for GoldilocksField {
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    fn is_one(&self) -> bool {
+        self.to_reduced_u64() == 1
+    }
+}
+
+#[cfg(feature = "num_traits_impls")]
+impl num_traits::Inv for GoldilocksField {
+    type Output = Self;
+
+    /// Panics on zero, see [`GoldilocksField::inverse_or_zero`] for the total version.
+    #[inline(always)]
+    fn inv(self) -> Self {
+        PrimeField::inverse(&self).expect("zero has no inverse")
     }
 }
 
@@ -357,9 +659,12 @@ impl Field for GoldilocksField {
 }
 
 impl SqrtField for GoldilocksField {
+    /// Tonelli-Shanks over the 2^32 subgroup. Returns `None` for non-residues, and otherwise
+    /// the root that is smaller of the two in canonical form (and is itself in canonical form), so
+    /// the result doesn't depend on the path taken by the algorithm.
     fn sqrt(&self) -> Option<Self> {
         if self.is_zero() {
-            return Some(*self);
+            return Some(GoldilocksField::ZERO);
         }
 
         // "Square root computation over even extension fields"
@@ -418,7 +723,11 @@ impl SqrtField for GoldilocksField {
             tmp == *self
         });
 
-        Some(x)
+        let mut minus_x = x;
+        minus_x.negate();
+        let root = std::cmp::min(x.to_reduced_u64(), minus_x.to_reduced_u64());
+
+        Some(GoldilocksField(root))
     }
 }
 
@@ -663,6 +972,37 @@ mod test {
         dbg!(z.as_u64_reduced());
     }
 
+    #[test]
+    fn test_canonical_hash_and_ordering() {
+        use std::{
+            collections::{hash_map::DefaultHasher, HashMap},
+            hash::{Hash, Hasher},
+        };
+
+        fn hash_of(value: &GoldilocksField) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let x = GoldilocksField(5);
+        let x_non_reduced = GoldilocksField(5 + GoldilocksField::ORDER);
+
+        assert_eq!(hash_of(&x), hash_of(&x_non_reduced));
+        assert_eq!(x.cmp(&x_non_reduced), Ordering::Equal);
+        assert!(x_non_reduced < GoldilocksField(6));
+
+        let mut map = HashMap::new();
+        map.insert(x, 1);
+        map.insert(x_non_reduced, 2);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map[&x], 2);
+
+        let mut values = vec![GoldilocksField(7), x_non_reduced, GoldilocksField(6)];
+        values.sort();
+        assert_eq!(values, vec![x, GoldilocksField(6), GoldilocksField(7)]);
+    }
+
     #[test]
     fn some_square_root() {
         let x = GoldilocksField::MINUS_ONE.sqrt();
@@ -671,4 +1011,385 @@ mod test {
         let x = GoldilocksField::TWO.sqrt();
         dbg!(&x);
     }
+
+    #[test]
+    fn test_sqrt_canonical_root() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let x: GoldilocksField = crate::field::rand_from_rng(&mut rng);
+            let mut minus_x = x;
+            minus_x.negate();
+            let mut square = x;
+            square.square();
+
+            let expected = std::cmp::min(x.to_reduced_u64(), minus_x.to_reduced_u64());
+            let root = square.sqrt().unwrap();
+            assert_eq!(root.0, expected);
+            assert!(root.0 <= GoldilocksField::ORDER / 2);
+        }
+
+        assert_eq!(GoldilocksField::ZERO.sqrt(), Some(GoldilocksField::ZERO));
+        assert_eq!(GoldilocksField::ONE.sqrt(), Some(GoldilocksField::ONE));
+        assert_eq!(GoldilocksField(16).sqrt(), Some(GoldilocksField(4)));
+        // non-reduced representation of 4
+        assert_eq!(GoldilocksField(4 + GoldilocksField::ORDER).sqrt(), Some(GoldilocksField(2)));
+
+        // multiplicative generator is a non-residue
+        assert_eq!(GoldilocksField::MULTIPLICATIVE_GROUP_GENERATOR.sqrt(), None);
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        let values: Vec<GoldilocksField> = (1..=20).map(GoldilocksField).collect();
+
+        let mut expected_sum = GoldilocksField::ZERO;
+        let mut expected_product = GoldilocksField::ONE;
+        for el in values.iter() {
+            expected_sum.add_assign(el);
+            expected_product.mul_assign(el);
+        }
+
+        assert_eq!(values.iter().sum::<GoldilocksField>(), expected_sum);
+        assert_eq!(values.iter().copied().sum::<GoldilocksField>(), GoldilocksField(210));
+        assert_eq!(values.iter().product::<GoldilocksField>(), expected_product);
+        // 20! mod p
+        assert_eq!(
+            values.iter().copied().product::<GoldilocksField>(),
+            GoldilocksField::from_nonreduced_u64(2432902008176640000)
+        );
+
+        // wraps around the modulus
+        let minus_one = [GoldilocksField::MINUS_ONE; 2];
+        assert_eq!(
+            minus_one.iter().sum::<GoldilocksField>(),
+            GoldilocksField(GoldilocksField::ORDER - 2)
+        );
+        assert_eq!(minus_one.iter().product::<GoldilocksField>(), GoldilocksField::ONE);
+
+        assert_eq!(
+            std::iter::empty::<GoldilocksField>().sum::<GoldilocksField>(),
+            GoldilocksField::ZERO
+        );
+        assert_eq!(
+            std::iter::empty::<&GoldilocksField>().product::<GoldilocksField>(),
+            GoldilocksField::ONE
+        );
+    }
+
+    #[test]
+    fn test_formatting_is_canonical() {
+        let x = GoldilocksField(5);
+        let x_non_reduced = GoldilocksField(5 + GoldilocksField::ORDER);
+
+        assert_eq!(format!("{}", x), "0x0000000000000005");
+        assert_eq!(format!("{}", x_non_reduced), format!("{}", x));
+        assert_eq!(format!("{:?}", x), "0x0000000000000005");
+        assert_eq!(format!("{:?}", x_non_reduced), "0x0000000000000005 (raw: 0xffffffff00000006)");
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(GoldilocksField::from(false).0, 0);
+        assert_eq!(GoldilocksField::from(true).0, 1);
+        assert_eq!(GoldilocksField::from(u8::MAX).0, u8::MAX as u64);
+        assert_eq!(GoldilocksField::from(u16::MAX).0, u16::MAX as u64);
+        assert_eq!(GoldilocksField::from(u32::MAX).0, u32::MAX as u64);
+
+        let max = GoldilocksField::try_from(GoldilocksField::ORDER - 1).unwrap();
+        assert_eq!(max, GoldilocksField::MINUS_ONE);
+        assert_eq!(max.0, GoldilocksField::ORDER - 1);
+        assert_eq!(GoldilocksField::try_from(0u64).unwrap().0, 0);
+
+        assert_eq!(
+            GoldilocksField::try_from(GoldilocksField::ORDER),
+            Err(NonCanonicalValueError(GoldilocksField::ORDER))
+        );
+        assert!(GoldilocksField::try_from(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        use crate::cs::implementations::utils::domain_generator_for_size;
+
+        const ROOT_OF_UNITY_8: Option<GoldilocksField> = GoldilocksField::root_of_unity(3);
+        assert_eq!(ROOT_OF_UNITY_8, GoldilocksField::root_of_unity(3));
+
+        assert_eq!(
+            GoldilocksField::multiplicative_generator(),
+            <GoldilocksField as PrimeField>::multiplicative_generator()
+        );
+        assert_eq!(
+            GoldilocksField::two_adic_generator(),
+            GoldilocksField::root_of_unity(GoldilocksField::TWO_ADICITY).unwrap()
+        );
+
+        for log_n in 0..=GoldilocksField::TWO_ADICITY {
+            let root = GoldilocksField::root_of_unity(log_n).unwrap();
+            assert!(root.0 < GoldilocksField::ORDER);
+            assert_eq!(root.pow_u64(1u64 << log_n), GoldilocksField::ONE);
+            if log_n > 0 {
+                // so the order divides 2^log_n, but not 2^(log_n - 1)
+                assert_ne!(root.pow_u64(1u64 << (log_n - 1)), GoldilocksField::ONE);
+            }
+            assert_eq!(root, domain_generator_for_size::<GoldilocksField>(1u64 << log_n));
+        }
+
+        assert!(GoldilocksField::root_of_unity(GoldilocksField::TWO_ADICITY + 1).is_none());
+    }
+
+    #[test]
+    fn test_inverse_or_zero() {
+        let mut rng = rand::thread_rng();
+        assert_eq!(GoldilocksField::ZERO.inverse_or_zero(), GoldilocksField::ZERO);
+        // non-canonical zero
+        assert_eq!(
+            GoldilocksField(GoldilocksField::ORDER).inverse_or_zero(),
+            GoldilocksField::ZERO
+        );
+        assert_eq!(GoldilocksField::ONE.inverse_or_zero(), GoldilocksField::ONE);
+
+        for _ in 0..1000 {
+            let x: GoldilocksField = crate::field::rand_from_rng(&mut rng);
+            if x.is_zero() {
+                continue;
+            }
+            let mut t = x;
+            t.mul_assign(&x.inverse_or_zero());
+            assert_eq!(t, GoldilocksField::ONE);
+            assert_eq!(Some(x.inverse_or_zero()), x.inverse());
+        }
+    }
+
+    #[test]
+    fn test_power_tables() {
+        let mut rng = rand::thread_rng();
+        let base: GoldilocksField = crate::field::rand_from_rng(&mut rng);
+        for n in [0, 1, 2, 17, 63, 64, 65, 1000] {
+            let ladder = GoldilocksField::square_ladder(base, n);
+            assert_eq!(ladder.len(), n);
+            let mut current = base;
+            for el in ladder.iter() {
+                assert_eq!(el.0, current.to_reduced_u64());
+                current.square();
+            }
+
+            let table = GoldilocksField::power_table(base, n);
+            assert_eq!(table.len(), n);
+            let mut current = base;
+            for (i, el) in table.iter().enumerate() {
+                assert_eq!(el.0, current.to_reduced_u64());
+                assert_eq!(*el, base.pow_u64(i as u64 + 1));
+                current.mul_assign(&base);
+            }
+        }
+
+        // noncanonical base
+        let table = GoldilocksField::power_table(GoldilocksField(GoldilocksField::ORDER + 2), 100);
+        assert!(table.iter().all(|el| el.0 < GoldilocksField::ORDER));
+        assert_eq!(table, GoldilocksField::power_table(GoldilocksField(2), 100));
+    }
+
+    #[test]
+    fn test_balanced_representation() {
+        let half = ((GoldilocksField::ORDER - 1) / 2) as i64;
+        for x in (-1000..=1000).chain([i64::MIN, i64::MIN + 1, i64::MAX, -half, half]) {
+            let el = GoldilocksField::from_i64(x);
+            assert!(el.0 < GoldilocksField::ORDER);
+            let mut expected = GoldilocksField::from_u64_with_reduction(x.unsigned_abs());
+            if x < 0 {
+                expected.negate();
+            }
+            assert_eq!(el, expected, "invalid reduction of {}", x);
+            if (-half..=half).contains(&x) {
+                assert_eq!(el.to_balanced_i64(), x);
+            }
+        }
+
+        // the edges of the range
+        assert_eq!(GoldilocksField(half as u64).to_balanced_i64(), half);
+        assert_eq!(GoldilocksField(half as u64 + 1).to_balanced_i64(), -half);
+        assert_eq!(GoldilocksField(GoldilocksField::ORDER - 1).to_balanced_i64(), -1);
+        // non-canonical representation
+        assert_eq!(GoldilocksField(GoldilocksField::ORDER + 5).to_balanced_i64(), 5);
+    }
+
+    #[test]
+    fn test_operators() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let a: GoldilocksField = crate::field::rand_from_rng(&mut rng);
+            let b: GoldilocksField = crate::field::rand_from_rng(&mut rng);
+            let c: GoldilocksField = crate::field::rand_from_rng(&mut rng);
+
+            let mut sum = a;
+            Field::add_assign(&mut sum, &b);
+            let mut difference = a;
+            Field::sub_assign(&mut difference, &b);
+            let mut product = a;
+            Field::mul_assign(&mut product, &b);
+            let mut negated = a;
+            Field::negate(&mut negated);
+
+            assert_eq!(a + b, sum);
+            assert_eq!(&a + &b, sum);
+            assert_eq!(a - b, difference);
+            assert_eq!(a - &b, difference);
+            assert_eq!(a * b, product);
+            assert_eq!(&a * b, product);
+            assert_eq!(-a, negated);
+            assert_eq!(-&a, negated);
+
+            let mut t = a;
+            t += b;
+            assert_eq!(t, sum);
+            t -= &b;
+            assert_eq!(t, a);
+            t *= b;
+            assert_eq!(t, product);
+
+            // (a + b) * c - a * b
+            let mut expected = sum;
+            Field::mul_assign(&mut expected, &c);
+            Field::sub_assign(&mut expected, &product);
+            assert_eq!((a + b) * c - a * b, expected);
+            assert_eq!(-(a - b), b - a);
+        }
+
+        // same representation as the methods, including non-canonical inputs
+        let x = GoldilocksField(GoldilocksField::ORDER + 3);
+        let mut expected = x;
+        Field::add_assign(&mut expected, &GoldilocksField::ONE);
+        assert_eq!((x + GoldilocksField::ONE).0, expected.0);
+        assert_eq!(-GoldilocksField::ZERO, GoldilocksField::ZERO);
+    }
+
+    #[cfg(feature = "num_traits_impls")]
+    #[test]
+    fn test_num_traits() {
+        use num_traits::{Inv, One, Zero};
+
+        assert_eq!(<GoldilocksField as Zero>::zero(), GoldilocksField::ZERO);
+        assert_eq!(<GoldilocksField as One>::one(), GoldilocksField::ONE);
+
+        assert!(Zero::is_zero(&GoldilocksField(0)));
+        assert!(Zero::is_zero(&GoldilocksField(GoldilocksField::ORDER)));
+        assert!(!Zero::is_zero(&GoldilocksField(1)));
+        assert!(One::is_one(&GoldilocksField(1)));
+        assert!(One::is_one(&GoldilocksField(GoldilocksField::ORDER + 1)));
+        assert!(!One::is_one(&GoldilocksField(0)));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let a: GoldilocksField = crate::field::rand_from_rng(&mut rng);
+            let b: GoldilocksField = crate::field::rand_from_rng(&mut rng);
+            let mut sum = a;
+            sum.add_assign(&b);
+            assert_eq!(a + b, sum);
+            let mut product = a;
+            product.mul_assign(&b);
+            assert_eq!(a * b, product);
+            if !Field::is_zero(&a) {
+                assert_eq!(a.inv() * a, GoldilocksField::ONE);
+            }
+        }
+    }
+
+    #[test]
+    fn test_serde_canonical() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut x: GoldilocksField = crate::field::rand_from_rng(&mut rng);
+            x.0 %= EPSILON;
+            let nonreduced = GoldilocksField(x.0 + GoldilocksField::ORDER);
+
+            let bytes = bincode::serialize(&x).unwrap();
+            assert_eq!(bytes, x.0.to_le_bytes());
+            assert_eq!(bincode::serialize(&nonreduced).unwrap(), bytes);
+            let json = serde_json::to_string(&x).unwrap();
+            assert_eq!(serde_json::to_string(&nonreduced).unwrap(), json);
+
+            let decoded: GoldilocksField = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(decoded.0, x.0);
+            let decoded: GoldilocksField = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded.0, x.0);
+
+            // raw non-canonical encodings are rejected
+            let raw = nonreduced.0;
+            assert!(bincode::deserialize::<GoldilocksField>(&raw.to_le_bytes()).is_err());
+            assert!(serde_json::from_str::<GoldilocksField>(&raw.to_string()).is_err());
+        }
+        assert!(bincode::deserialize::<GoldilocksField>(&u64::MAX.to_le_bytes()).is_err());
+        let max: GoldilocksField =
+            bincode::deserialize(&(GoldilocksField::ORDER - 1).to_le_bytes()).unwrap();
+        assert_eq!(max, GoldilocksField::MINUS_ONE);
+    }
+
+    #[test]
+    fn test_byte_conversions() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..1024 {
+            let x: GoldilocksField = crate::field::rand_from_rng(&mut rng);
+            let le = x.to_le_bytes();
+            let be = x.to_be_bytes();
+            assert_eq!(GoldilocksField::from_le_bytes_reduced(le), Some(x));
+            assert_eq!(GoldilocksField::from_be_bytes_reduced(be), Some(x));
+
+            let mut reversed = le;
+            reversed.reverse();
+            assert_eq!(reversed, be);
+            assert_eq!(le.to_vec(), bincode::serialize(&x).unwrap());
+
+            // canonical output for a non-reduced representation
+            if x.0 < EPSILON {
+                let nonreduced = GoldilocksField(x.0 + GoldilocksField::ORDER);
+                assert_eq!(nonreduced.to_le_bytes(), le);
+                assert_eq!(nonreduced.to_be_bytes(), be);
+            }
+        }
+
+        for raw in [GoldilocksField::ORDER, GoldilocksField::ORDER + 1, u64::MAX] {
+            assert_eq!(GoldilocksField::from_le_bytes_reduced(raw.to_le_bytes()), None);
+            assert_eq!(GoldilocksField::from_be_bytes_reduced(raw.to_be_bytes()), None);
+        }
+        let max = (GoldilocksField::ORDER - 1).to_be_bytes();
+        assert_eq!(GoldilocksField::from_be_bytes_reduced(max), Some(GoldilocksField::MINUS_ONE));
+        assert_eq!(GoldilocksField::ONE.to_be_bytes(), [0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(GoldilocksField::ONE.to_le_bytes(), [1, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_mul_small() {
+        let mut rng = rand::thread_rng();
+        let mut inputs: Vec<GoldilocksField> = (0..256)
+            .map(|_| crate::field::rand_from_rng(&mut rng))
+            .collect();
+        // non-canonical and extreme representations
+        inputs.extend([
+            GoldilocksField(0),
+            GoldilocksField(GoldilocksField::ORDER),
+            GoldilocksField(GoldilocksField::ORDER - 1),
+            GoldilocksField(u64::MAX),
+        ]);
+
+        for x in inputs.iter() {
+            for c in (0..=16).chain([255, 0x1234, u16::MAX]) {
+                let mut expected = *x;
+                Field::mul_assign(&mut expected, &GoldilocksField(c as u64));
+                assert_eq!(x.mul_small(c), expected, "invalid product of {:?} by {}", x, c);
+            }
+
+            for k in [0, 1, 2, 5, 31, 32, 63, 64, 65, 95, 96, 97, 127, 191, 192, 200, u32::MAX] {
+                let mut expected = GoldilocksField(2);
+                expected = Field::pow_u64(&expected, k as u64);
+                Field::mul_assign(&mut expected, x);
+                assert_eq!(
+                    x.mul_by_two_adic_power(k),
+                    expected,
+                    "invalid product of {:?} by 2^{}",
+                    x,
+                    k
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,853 @@
+//! Polynomial helpers over the Goldilocks field, e.g. evaluation, interpolation, FFTs of
+//! arbitrary inputs and FRI folding. They run on `MixedGL` vectors wherever the sizes allow and
+//! fall back to the scalar code otherwise.
+
+use super::{GoldilocksField, MixedGL};
+use crate::field::{Field, PrimeField};
+
+/// Evaluates every polynomial of `polys` (constant term first) at `point`. Powers of `point` up to
+/// the longest polynomial are computed once, and then every evaluation is a dot product with them
+/// over `MixedGL` lanes, so the polynomials don't depend on each other and are split between the
+/// worker threads. Lengths may differ, an empty polynomial evaluates to zero.
+pub fn batch_horner(
+    polys: &[&[GoldilocksField]],
+    point: GoldilocksField,
+    worker: &crate::worker::Worker,
+) -> Vec<GoldilocksField> {
+    use crate::field::traits::field_like::PrimeFieldLikeVectorized;
+
+    let max_len = polys.iter().map(|el| el.len()).max().unwrap_or(0);
+    // padded to full vectors, so every chunk of coefficients has a full chunk of powers
+    let mut powers = vec![GoldilocksField::ZERO; max_len.next_multiple_of(MixedGL::SIZE_FACTOR)];
+    MixedGL::fill_powers(point, &mut powers, worker);
+
+    let mut result = vec![GoldilocksField::ZERO; polys.len()];
+    let powers = &powers[..];
+    worker.scope(polys.len(), |scope, chunk_size| {
+        for (dst, src) in result.chunks_mut(chunk_size).zip(polys.chunks(chunk_size)) {
+            scope.spawn(move |_| {
+                for (dst, poly) in dst.iter_mut().zip(src.iter()) {
+                    *dst = dot_product_with_powers(poly, powers);
+                }
+            });
+        }
+    });
+
+    result
+}
+
+fn dot_product_with_powers(
+    coeffs: &[GoldilocksField],
+    powers: &[GoldilocksField],
+) -> GoldilocksField {
+    use crate::field::traits::field_like::{PrimeFieldLike, PrimeFieldLikeVectorized};
+
+    let mut acc = MixedGL::zero(&mut ());
+    for (coeffs, powers) in coeffs
+        .chunks(MixedGL::SIZE_FACTOR)
+        .zip(powers.chunks_exact(MixedGL::SIZE_FACTOR))
+    {
+        let mut term = MixedGL::zero(&mut ());
+        term.0[..coeffs.len()].copy_from_slice(coeffs);
+        let mut powers_vec = MixedGL::zero(&mut ());
+        powers_vec.0.copy_from_slice(powers);
+        term.mul_assign(&powers_vec, &mut ());
+        acc.add_assign(&term, &mut ());
+    }
+
+    let mut result = GoldilocksField::ZERO;
+    for lane in acc.0.iter() {
+        Field::add_assign(&mut result, lane);
+    }
+
+    result
+}
+
+/// Smallest number of base field elements transformed by the `MixedGL` FFTs of [`crate::fft`]
+/// (e.g. [`crate::fft::fft_natural_to_bitreversed_mixedgl`]). They take twiddles for half of
+/// `input.len() * 16` elements, and a single vector would need the ones of twice its size, so at
+/// least two 16 lanes wide vectors are needed. Shorter inputs go through the scalar FFTs.
+pub const MIXEDGL_FFT_THRESHOLD: usize = 32;
+
+/// Whether [`fft`], [`interpolate`] and [`negacyclic_mul`] go through the `MixedGL` FFTs for size
+/// `n`. Those hardcode 16 lanes, so on backends where `MixedGL` has a different width (8 with full
+/// avx512) everything takes the scalar path.
+const fn use_mixedgl_fft(n: usize) -> bool {
+    use crate::field::traits::field_like::PrimeFieldLikeVectorized;
+
+    MixedGL::SIZE_FACTOR == 16 && n >= MIXEDGL_FFT_THRESHOLD
+}
+
+/// Product of `a` and `b` (constant term first) modulo `x^n + 1`, where `n` is their common length
+/// and must be a power of two. Both operands are twisted by the powers of a primitive `2n`-th root
+/// of unity `psi` (as the coset of the forward FFT), multiplied pointwise in the bitreversed
+/// evaluation order, and transformed back, after which the result is untwisted by the powers of
+/// `psi^-1` and scaled by `1/n`. `forward_twiddles` and `inverse_twiddles` are the `MixedGL` ones
+/// for size `n`, they are not used where the `MixedGL` FFTs aren't (see [`MIXEDGL_FFT_THRESHOLD`]),
+/// and the schoolbook method is used instead.
+pub fn negacyclic_mul(
+    a: &[GoldilocksField],
+    b: &[GoldilocksField],
+    forward_twiddles: &[GoldilocksField],
+    inverse_twiddles: &[GoldilocksField],
+    worker: &crate::worker::Worker,
+) -> Vec<GoldilocksField> {
+    use std::alloc::Global;
+
+    use crate::{
+        fft::{fft_bitreversed_to_natural_mixedgl, fft_natural_to_bitreversed_mixedgl},
+        field::traits::field_like::{PrimeFieldLike, PrimeFieldLikeVectorized},
+        utils::allocate_in_with_alignment_of,
+    };
+
+    assert_eq!(a.len(), b.len(), "operands must have the same length");
+    let n = a.len();
+    assert!(n.is_power_of_two(), "length must be a power of two, got {}", n);
+
+    if !use_mixedgl_fft(n) {
+        return negacyclic_mul_naive(a, b);
+    }
+
+    let psi = GoldilocksField::root_of_unity(n.trailing_zeros() as usize + 1)
+        .expect("length is too large for the two-adic FFT");
+
+    let to_mixedgl = |input: &[GoldilocksField]| {
+        let mut result =
+            allocate_in_with_alignment_of::<GoldilocksField, MixedGL, Global>(n, Global);
+        result.extend_from_slice(input);
+        MixedGL::vec_from_base_vec(result)
+    };
+    let mut a = to_mixedgl(a);
+    let mut b = to_mixedgl(b);
+
+    fft_natural_to_bitreversed_mixedgl(&mut a, psi, forward_twiddles);
+    fft_natural_to_bitreversed_mixedgl(&mut b, psi, forward_twiddles);
+
+    worker.scope(a.len(), |scope, chunk_size| {
+        for (a, b) in a.chunks_mut(chunk_size).zip(b.chunks(chunk_size)) {
+            scope.spawn(move |_| {
+                for (a, b) in a.iter_mut().zip(b.iter()) {
+                    PrimeFieldLike::mul_assign(a, b, &mut ());
+                }
+            });
+        }
+    });
+
+    fft_bitreversed_to_natural_mixedgl(&mut a, &[], inverse_twiddles);
+
+    let mut untwist = vec![GoldilocksField::ZERO; n];
+    MixedGL::fill_powers(PrimeField::inverse(&psi).unwrap(), &mut untwist, worker);
+    let n_inv = PrimeField::inverse(&GoldilocksField::from_u64_with_reduction(n as u64)).unwrap();
+
+    let mut result = MixedGL::vec_into_base_vec(a);
+    for (dst, power) in result.iter_mut().zip(untwist.iter()) {
+        Field::mul_assign(dst, power);
+        Field::mul_assign(dst, &n_inv);
+    }
+
+    result
+}
+
+fn negacyclic_mul_naive(a: &[GoldilocksField], b: &[GoldilocksField]) -> Vec<GoldilocksField> {
+    let n = a.len();
+    let mut result = vec![GoldilocksField::ZERO; n];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            let mut product = *a;
+            product.mul_assign(b);
+            // x^n = -1
+            if i + j < n {
+                result[i + j].add_assign(&product);
+            } else {
+                result[i + j - n].sub_assign(&product);
+            }
+        }
+    }
+
+    result
+}
+
+/// Transposes the row-major `rows x cols` matrix `src` into the row-major `cols x rows` matrix
+/// `dst`. The matrix is processed in square tiles of `MixedGL::SIZE_FACTOR` elements, and rows of
+/// `dst` are split between the worker threads. Tiles that fit into the matrix completely are
+/// copied through fixed size arrays, so if both dimensions are multiples of the `MixedGL` width
+/// all of them take this path, and only the border tiles fall back to the bounds checked loop
+/// otherwise.
+pub fn transpose(
+    src: &[GoldilocksField],
+    rows: usize,
+    cols: usize,
+    dst: &mut [GoldilocksField],
+    worker: &crate::worker::Worker,
+) {
+    use crate::field::traits::field_like::PrimeFieldLikeVectorized;
+
+    assert_eq!(src.len(), rows * cols, "source must have rows * cols elements");
+    assert_eq!(dst.len(), rows * cols, "destination must have rows * cols elements");
+    if src.is_empty() {
+        return;
+    }
+
+    let num_column_tiles = cols.div_ceil(MixedGL::SIZE_FACTOR);
+    worker.scope(num_column_tiles, |scope, chunk_size| {
+        for (chunk_idx, dst) in dst
+            .chunks_mut(chunk_size * MixedGL::SIZE_FACTOR * rows)
+            .enumerate()
+        {
+            scope.spawn(move |_| {
+                let column_start = chunk_idx * chunk_size * MixedGL::SIZE_FACTOR;
+                transpose_columns(src, rows, cols, column_start, dst);
+            });
+        }
+    });
+}
+
+// transposes columns starting from `column_start` of `src` into `dst`, that holds the
+// corresponding rows of the result
+fn transpose_columns(
+    src: &[GoldilocksField],
+    rows: usize,
+    cols: usize,
+    column_start: usize,
+    dst: &mut [GoldilocksField],
+) {
+    use crate::field::traits::field_like::PrimeFieldLikeVectorized;
+
+    const TILE: usize = MixedGL::SIZE_FACTOR;
+
+    let num_columns = dst.len() / rows;
+    for row in (0..rows).step_by(TILE) {
+        for column in (0..num_columns).step_by(TILE) {
+            let src_column = column_start + column;
+            if row + TILE <= rows && column + TILE <= num_columns {
+                let mut tile = [[GoldilocksField::ZERO; TILE]; TILE];
+                for (i, dst) in tile.iter_mut().enumerate() {
+                    let offset = (row + i) * cols + src_column;
+                    dst.copy_from_slice(&src[offset..][..TILE]);
+                }
+                for j in 0..TILE {
+                    let dst = &mut dst[(column + j) * rows + row..][..TILE];
+                    for (dst, tile_row) in dst.iter_mut().zip(tile.iter()) {
+                        *dst = tile_row[j];
+                    }
+                }
+            } else {
+                for i in row..std::cmp::min(row + TILE, rows) {
+                    for j in column..std::cmp::min(column + TILE, num_columns) {
+                        dst[j * rows + i] = src[i * cols + column_start + j];
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// One step of FRI folding with a base field `challenge`. `layer` holds evaluations of `f` over
+/// the domain `coset * <omega>` of size `n = layer.len()`, in bitreversed order, so every pair
+/// `(layer[2i], layer[2i + 1])` is `(f(x), f(-x))` for `x = coset * omega^bitreverse(i)`. Use
+/// `coset = 1` for the subgroup itself. The result at `i` is
+/// `f(x) + f(-x) + challenge * (f(x) - f(-x)) / x = 2 * (f_even(x^2) + challenge * f_odd(x^2))`,
+/// i.e. evaluations over the domain `coset^2 * <omega^2>` in bitreversed order, the same as in
+/// the prover's FRI. `inverse_twiddles` are the ones for an inverse FFT of size `n` (at least
+/// `n / 2` bitreversed powers of `omega^-1`), and their first half serves the next layer.
+pub fn fri_fold(
+    layer: &[GoldilocksField],
+    challenge: GoldilocksField,
+    coset: GoldilocksField,
+    inverse_twiddles: &[GoldilocksField],
+    worker: &crate::worker::Worker,
+) -> Vec<GoldilocksField> {
+    let n = layer.len();
+    assert!(n >= 2 && n.is_power_of_two(), "layer size must be a power of two, got {}", n);
+    assert!(inverse_twiddles.len() >= n / 2, "not enough twiddles for size {}", n);
+
+    // division by the coset is the same for all the pairs, so it's merged into the challenge
+    let mut scale = PrimeField::inverse(&coset).expect("coset must not be zero");
+    scale.mul_assign(&challenge);
+
+    let mut result = vec![GoldilocksField::ZERO; n / 2];
+    let roots = &inverse_twiddles[..n / 2];
+    worker.scope(n / 2, |scope, chunk_size| {
+        for ((dst, pairs), roots) in result
+            .chunks_mut(chunk_size)
+            .zip(layer.chunks(chunk_size * 2))
+            .zip(roots.chunks(chunk_size))
+        {
+            scope.spawn(move |_| {
+                fri_fold_chunk(pairs, roots, &scale, dst);
+            });
+        }
+    });
+
+    result
+}
+
+fn fri_fold_chunk(
+    pairs: &[GoldilocksField],
+    roots: &[GoldilocksField],
+    scale: &GoldilocksField,
+    dst: &mut [GoldilocksField],
+) {
+    use crate::field::traits::field_like::{PrimeFieldLike, PrimeFieldLikeVectorized};
+
+    let mut dst_chunks = dst.chunks_exact_mut(MixedGL::SIZE_FACTOR);
+    let mut pairs_chunks = pairs.chunks_exact(MixedGL::SIZE_FACTOR * 2);
+    let mut roots_chunks = roots.chunks_exact(MixedGL::SIZE_FACTOR);
+    for ((dst, pairs), roots) in (&mut dst_chunks)
+        .zip(&mut pairs_chunks)
+        .zip(&mut roots_chunks)
+    {
+        let mut even = MixedGL::zero(&mut ());
+        let mut odd = MixedGL::zero(&mut ());
+        for (i, pair) in pairs.chunks_exact(2).enumerate() {
+            even.0[i] = pair[0];
+            odd.0[i] = pair[1];
+        }
+        let mut roots_vec = MixedGL::zero(&mut ());
+        roots_vec.0.copy_from_slice(roots);
+
+        let mut diff = even;
+        diff.sub_assign(&odd, &mut ());
+        diff.mul_assign(&roots_vec, &mut ());
+        diff.mul_constant_assign(scale);
+        even.add_assign(&odd, &mut ()).add_assign(&diff, &mut ());
+        dst.copy_from_slice(&even.0);
+    }
+
+    for ((dst, pair), root) in dst_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(pairs_chunks.remainder().chunks_exact(2))
+        .zip(roots_chunks.remainder().iter())
+    {
+        let mut diff = pair[0];
+        Field::sub_assign(&mut diff, &pair[1]);
+        Field::mul_assign(&mut diff, root);
+        Field::mul_assign(&mut diff, scale);
+        *dst = pair[0];
+        Field::add_assign(dst, &pair[1]);
+        Field::add_assign(dst, &diff);
+    }
+}
+
+/// Forward FFT of `input`, that doesn't need to be packed, aligned or of power of two length. The
+/// input is zero-extended to `n = input.len().next_power_of_two()` coefficients, and the result
+/// holds its evaluations over the domain `coset * <omega_n>` in bitreversed order. Note that the
+/// padding defines the transform: for a length that is not a power of two these are evaluations
+/// of the polynomial with `input` as coefficients over a larger domain, not a DFT of size
+/// `input.len()`. Inputs of at least [`MIXEDGL_FFT_THRESHOLD`] elements go through the vectorized
+/// FFT, shorter ones through the scalar one.
+pub fn fft(
+    input: &[GoldilocksField],
+    coset: GoldilocksField,
+    worker: &crate::worker::Worker,
+) -> Vec<GoldilocksField> {
+    use std::alloc::Global;
+
+    use crate::field::traits::field_like::PrimeFieldLikeVectorized;
+
+    if input.is_empty() {
+        return vec![];
+    }
+    let n = input.len().next_power_of_two();
+
+    if !use_mixedgl_fft(n) {
+        let mut result = input.to_vec();
+        result.resize(n, GoldilocksField::ZERO);
+        let twiddles =
+            GoldilocksField::precompute_forward_twiddles_for_fft::<Global>(n, worker, &mut ());
+        crate::fft::fft_natural_to_bitreversed(&mut result, coset, &twiddles);
+
+        return result;
+    }
+
+    let mut padded = crate::utils::allocate_in_with_alignment_of::<_, MixedGL, Global>(n, Global);
+    padded.extend_from_slice(input);
+    padded.resize(n, GoldilocksField::ZERO);
+    let mut packed = MixedGL::vec_from_base_vec(padded);
+    let twiddles = MixedGL::precompute_forward_twiddles_for_fft::<Global>(n, worker, &mut ());
+    MixedGL::fft_natural_to_bitreversed(&mut packed, coset, &twiddles, &mut ());
+
+    MixedGL::vec_into_base_vec(packed)
+}
+
+/// Evaluations of the polynomial with coefficients `coeffs` over the subgroup `<omega_n>` in
+/// natural order, i.e. `omega_n^i` at position `i`. Same as [`fft`] with a trivial coset, so the
+/// coefficients are zero-extended to `n = coeffs.len().next_power_of_two()`.
+pub fn evaluate_over_subgroup(
+    coeffs: &[GoldilocksField],
+    worker: &crate::worker::Worker,
+) -> Vec<GoldilocksField> {
+    evaluate_over_subgroup_with_backend(coeffs, &crate::backend::CpuBackend, worker)
+}
+
+/// Same as [`evaluate_over_subgroup`], with the FFT done by `backend`.
+pub fn evaluate_over_subgroup_with_backend<B: crate::backend::FftBackend>(
+    coeffs: &[GoldilocksField],
+    backend: &B,
+    worker: &crate::worker::Worker,
+) -> Vec<GoldilocksField> {
+    if coeffs.is_empty() {
+        return vec![];
+    }
+    let mut result = backend.fft(coeffs, GoldilocksField::ONE, worker);
+    crate::fft::bitreverse_enumeration_inplace(&mut result);
+
+    result
+}
+
+/// Coefficients (constant term first) of the polynomial of degree less than `n = evals.len()`
+/// that takes the values `evals` over the subgroup `<omega_n>` in natural order, i.e. the inverse
+/// of [`evaluate_over_subgroup`]. `n` must be a power of two. Inputs of at least
+/// [`MIXEDGL_FFT_THRESHOLD`] elements go through the vectorized inverse FFT, shorter ones through
+/// the scalar one.
+pub fn interpolate(
+    evals: &[GoldilocksField],
+    worker: &crate::worker::Worker,
+) -> Vec<GoldilocksField> {
+    use std::alloc::Global;
+
+    use crate::field::traits::field_like::PrimeFieldLikeVectorized;
+
+    let n = evals.len();
+    assert!(n.is_power_of_two(), "number of evaluations must be a power of two, got {}", n);
+    let coset = GoldilocksField::ONE;
+
+    if !use_mixedgl_fft(n) {
+        let mut result = evals.to_vec();
+        let twiddles =
+            GoldilocksField::precompute_inverse_twiddles_for_fft::<Global>(n, worker, &mut ());
+        crate::fft::ifft_natural_to_natural(&mut result, coset, &twiddles);
+
+        return result;
+    }
+
+    let mut result = crate::utils::allocate_in_with_alignment_of::<_, MixedGL, Global>(n, Global);
+    result.extend_from_slice(evals);
+    let mut packed = MixedGL::vec_from_base_vec(result);
+    let twiddles = MixedGL::precompute_inverse_twiddles_for_fft::<Global>(n, worker, &mut ());
+    MixedGL::ifft_natural_to_natural(&mut packed, coset, &twiddles, &mut ());
+
+    MixedGL::vec_into_base_vec(packed)
+}
+
+/// Same as [`interpolate`], with the inverse FFT done by `backend`. [`interpolate`] itself is the
+/// CPU implementation, that [`crate::backend::CpuBackend`] runs.
+pub fn interpolate_with_backend<B: crate::backend::FftBackend>(
+    evals: &[GoldilocksField],
+    backend: &B,
+    worker: &crate::worker::Worker,
+) -> Vec<GoldilocksField> {
+    let n = evals.len();
+    assert!(n.is_power_of_two(), "number of evaluations must be a power of two, got {}", n);
+
+    let mut bitreversed = evals.to_vec();
+    crate::fft::bitreverse_enumeration_inplace(&mut bitreversed);
+
+    backend.ifft(&bitreversed, GoldilocksField::ONE, worker)
+}
+
+/// Divides `evaluations` over the domain `coset * <omega_N>` (`N = evaluations.len()`, in
+/// bitreversed order, as returned by [`fft`]) by the vanishing polynomial `x^n - 1` of the subgroup
+/// of size `n = domain_size`, e.g. for the quotient. Over this domain `x^n` only takes `N / n`
+/// distinct values `coset^n * omega_N^(n * k)`, and in bitreversed order every `n` consecutive
+/// evaluations share one of them, so only `N / n` denominators are inverted at once. Panics if the
+/// domain intersects the subgroup, i.e. if some denominator is zero.
+pub fn divide_by_vanishing(
+    evaluations: &mut [GoldilocksField],
+    coset: GoldilocksField,
+    domain_size: usize,
+    worker: &crate::worker::Worker,
+) {
+    use crate::{
+        cs::implementations::utils::{
+            batch_inverse, domain_generator_for_size, materialize_powers_serial,
+        },
+        fft::bitreverse_enumeration_inplace,
+    };
+
+    let full_size = evaluations.len();
+    assert!(
+        full_size.is_power_of_two(),
+        "number of evaluations must be a power of two, got {}",
+        full_size
+    );
+    assert!(
+        domain_size.is_power_of_two() && domain_size <= full_size,
+        "vanishing domain size must be a power of two not larger than {}, got {}",
+        full_size,
+        domain_size
+    );
+
+    let num_cosets = full_size / domain_size;
+    let generator =
+        domain_generator_for_size::<GoldilocksField>(full_size as u64).pow_u64(domain_size as u64);
+    let coset_in_n = coset.pow_u64(domain_size as u64);
+    let mut denominators =
+        materialize_powers_serial::<_, std::alloc::Global>(generator, num_cosets);
+    for (k, el) in denominators.iter_mut().enumerate() {
+        el.mul_assign(&coset_in_n);
+        el.sub_assign(&GoldilocksField::ONE);
+        assert!(
+            el.is_zero() == false,
+            "vanishing polynomial is zero at coset * omega^{} and its multiples",
+            k
+        );
+    }
+    bitreverse_enumeration_inplace(&mut denominators);
+    let mut inverses = Vec::with_capacity(num_cosets);
+    batch_inverse(&denominators, &mut inverses);
+
+    for (chunk, inverse) in evaluations
+        .chunks_mut(domain_size)
+        .zip(inverses.into_iter())
+    {
+        GoldilocksField::scale_slice(chunk, inverse, worker);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{field::rand_from_rng, worker::Worker};
+
+    #[test]
+    fn test_batch_horner() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        let point: GoldilocksField = rand_from_rng(&mut rng);
+
+        let polys: Vec<Vec<GoldilocksField>> = [0, 1, 7, 16, 17, 100, 1 << 12, 5]
+            .into_iter()
+            .map(|len| (0..len).map(|_| rand_from_rng(&mut rng)).collect())
+            .collect();
+        let refs: Vec<&[GoldilocksField]> = polys.iter().map(|el| &el[..]).collect();
+
+        let values = batch_horner(&refs, point, &worker);
+        assert_eq!(values.len(), polys.len());
+        for (value, poly) in values.iter().zip(polys.iter()) {
+            let mut expected = GoldilocksField::ZERO;
+            for c in poly.iter().rev() {
+                expected.mul_assign(&point);
+                expected.add_assign(c);
+            }
+            assert_eq!(*value, expected);
+        }
+
+        assert!(batch_horner(&[], point, &worker).is_empty());
+    }
+
+    #[test]
+    fn test_negacyclic_mul() {
+        use std::alloc::Global;
+
+        use crate::field::traits::field_like::PrimeFieldLikeVectorized;
+
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        for log_n in 0..9 {
+            let n = 1 << log_n;
+            let a: Vec<GoldilocksField> = (0..n).map(|_| rand_from_rng(&mut rng)).collect();
+            let b: Vec<GoldilocksField> = (0..n).map(|_| rand_from_rng(&mut rng)).collect();
+
+            // schoolbook product, folded by x^n = -1
+            let mut expected = vec![GoldilocksField::ZERO; n];
+            for i in 0..n {
+                for j in 0..n {
+                    let mut product = a[i];
+                    product.mul_assign(&b[j]);
+                    if i + j < n {
+                        expected[i + j].add_assign(&product);
+                    } else {
+                        expected[i + j - n].sub_assign(&product);
+                    }
+                }
+            }
+
+            let (forward, inverse) = if use_mixedgl_fft(n) {
+                (
+                    MixedGL::precompute_forward_twiddles_for_fft::<Global>(n, &worker, &mut ()),
+                    MixedGL::precompute_inverse_twiddles_for_fft::<Global>(n, &worker, &mut ()),
+                )
+            } else {
+                (Vec::new(), Vec::new())
+            };
+            let product = negacyclic_mul(&a, &b, &forward, &inverse, &worker);
+            assert_eq!(product, expected, "invalid for length {}", n);
+        }
+    }
+
+    #[test]
+    fn test_transpose() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        let shapes =
+            [(0, 5), (1, 1), (3, 5), (16, 16), (32, 48), (17, 33), (100, 7), (4096, 3), (3, 4096)];
+        for (rows, cols) in shapes {
+            let src: Vec<GoldilocksField> =
+                (0..rows * cols).map(|_| rand_from_rng(&mut rng)).collect();
+            let mut expected = vec![GoldilocksField::ZERO; rows * cols];
+            for i in 0..rows {
+                for j in 0..cols {
+                    expected[j * rows + i] = src[i * cols + j];
+                }
+            }
+
+            let mut dst = vec![GoldilocksField::ZERO; rows * cols];
+            transpose(&src, rows, cols, &mut dst, &worker);
+            assert_eq!(dst, expected, "invalid for {}x{}", rows, cols);
+
+            // and back
+            let mut back = vec![GoldilocksField::ZERO; rows * cols];
+            transpose(&dst, cols, rows, &mut back, &worker);
+            assert_eq!(back, src, "invalid for {}x{}", cols, rows);
+        }
+    }
+
+    #[test]
+    fn test_fft() {
+        use crate::{
+            cs::implementations::utils::domain_generator_for_size,
+            fft::bitreverse_enumeration_inplace,
+        };
+
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        for len in [0, 1, 2, 3, 5, 13, 16, 17, 31, 32, 100, 1 << 8] {
+            let input: Vec<GoldilocksField> = (0..len).map(|_| rand_from_rng(&mut rng)).collect();
+            for coset in [GoldilocksField::ONE, GoldilocksField::multiplicative_generator()] {
+                let result = fft(&input, coset, &worker);
+                if len == 0 {
+                    assert!(result.is_empty());
+                    continue;
+                }
+
+                // reference DFT of the explicitly padded input
+                let n = len.next_power_of_two();
+                let mut padded = input.clone();
+                padded.resize(n, GoldilocksField::ZERO);
+                let omega = domain_generator_for_size::<GoldilocksField>(n as u64);
+                let mut expected: Vec<GoldilocksField> = (0..n)
+                    .map(|i| {
+                        let mut x = omega.pow_u64(i as u64);
+                        x.mul_assign(&coset);
+                        let mut value = GoldilocksField::ZERO;
+                        for c in padded.iter().rev() {
+                            value.mul_assign(&x);
+                            value.add_assign(c);
+                        }
+                        value
+                    })
+                    .collect();
+                bitreverse_enumeration_inplace(&mut expected);
+
+                assert_eq!(result, expected, "invalid FFT of length {}", len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fri_fold() {
+        use std::alloc::Global;
+
+        use crate::{
+            cs::implementations::utils::{domain_generator_for_size, precompute_twiddles_for_fft},
+            fft::bitreverse_enumeration_inplace,
+            field::traits::field_like::PrimeFieldLikeVectorized,
+        };
+
+        fn evaluate_bitreversed(
+            coeffs: &[GoldilocksField],
+            coset: GoldilocksField,
+            size: usize,
+        ) -> Vec<GoldilocksField> {
+            let omega = domain_generator_for_size::<GoldilocksField>(size as u64);
+            let mut result: Vec<GoldilocksField> = (0..size)
+                .map(|i| {
+                    let mut x = omega.pow_u64(i as u64);
+                    x.mul_assign(&coset);
+                    let mut value = GoldilocksField::ZERO;
+                    for c in coeffs.iter().rev() {
+                        value.mul_assign(&x);
+                        value.add_assign(c);
+                    }
+                    value
+                })
+                .collect();
+            bitreverse_enumeration_inplace(&mut result);
+            result
+        }
+
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        for log_n in [1, 2, 5, 10] {
+            let n = 1usize << log_n;
+            let twiddles = MixedGL::vec_into_base_vec(precompute_twiddles_for_fft::<
+                GoldilocksField,
+                MixedGL,
+                Global,
+                true,
+            >(n, &worker, &mut ()));
+            for coset in [GoldilocksField::ONE, GoldilocksField::multiplicative_generator()] {
+                // blowup factor of 2
+                let coeffs: Vec<GoldilocksField> =
+                    (0..n / 2).map(|_| rand_from_rng(&mut rng)).collect();
+                let mut layer = evaluate_bitreversed(&coeffs, coset, n);
+                let mut coeffs = coeffs;
+                let mut coset = coset;
+                // fold while there are at least two elements
+                for _ in 0..std::cmp::min(log_n, 2) {
+                    let size = layer.len();
+                    let challenge: GoldilocksField = rand_from_rng(&mut rng);
+                    let folded = fri_fold(&layer, challenge, coset, &twiddles, &worker);
+
+                    // 2 * (f_even + challenge * f_odd)
+                    let mut next_coeffs = vec![GoldilocksField::ZERO; coeffs.len().div_ceil(2)];
+                    for (dst, pair) in next_coeffs.iter_mut().zip(coeffs.chunks(2)) {
+                        *dst = pair[0];
+                        if pair.len() > 1 {
+                            let mut t = pair[1];
+                            t.mul_assign(&challenge);
+                            dst.add_assign(&t);
+                        }
+                        dst.double();
+                    }
+                    coset.square();
+                    assert_eq!(folded, evaluate_bitreversed(&next_coeffs, coset, size / 2));
+
+                    layer = folded;
+                    coeffs = next_coeffs;
+                    if layer.len() < 2 {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_divide_by_vanishing() {
+        use crate::{
+            cs::implementations::utils::domain_generator_for_size,
+            fft::bitreverse_enumeration_inplace,
+        };
+
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        let coset = GoldilocksField::multiplicative_generator();
+        for (full_size, domain_size) in [(1, 1), (8, 2), (16, 16), (64, 8), (1 << 8, 1 << 6)] {
+            let evaluations: Vec<GoldilocksField> =
+                (0..full_size).map(|_| rand_from_rng(&mut rng)).collect();
+            let mut result = evaluations.clone();
+            divide_by_vanishing(&mut result, coset, domain_size, &worker);
+
+            // reference division at every point of the domain
+            let omega = domain_generator_for_size::<GoldilocksField>(full_size as u64);
+            let mut points: Vec<GoldilocksField> = (0..full_size)
+                .map(|i| {
+                    let mut x = omega.pow_u64(i as u64);
+                    x.mul_assign(&coset);
+                    x
+                })
+                .collect();
+            bitreverse_enumeration_inplace(&mut points);
+            for (i, ((result, value), x)) in result
+                .iter()
+                .zip(evaluations.iter())
+                .zip(points.iter())
+                .enumerate()
+            {
+                let mut denominator = x.pow_u64(domain_size as u64);
+                denominator.sub_assign(&GoldilocksField::ONE);
+                let mut expected = denominator.inverse().unwrap();
+                expected.mul_assign(value);
+                assert_eq!(
+                    *result, expected,
+                    "invalid value {} for sizes {} and {}",
+                    i, full_size, domain_size
+                );
+            }
+        }
+
+        // quotient of a multiple of the vanishing polynomial
+        let (full_size, domain_size) = (64, 16);
+        let quotient: Vec<GoldilocksField> = (0..full_size - domain_size)
+            .map(|_| rand_from_rng(&mut rng))
+            .collect();
+        let mut product = vec![GoldilocksField::ZERO; full_size];
+        for (i, c) in quotient.iter().enumerate() {
+            product[i].sub_assign(c);
+            product[i + domain_size].add_assign(c);
+        }
+        let mut result = fft(&product, coset, &worker);
+        divide_by_vanishing(&mut result, coset, domain_size, &worker);
+        let mut padded = quotient.clone();
+        padded.resize(full_size, GoldilocksField::ZERO);
+        assert_eq!(result, fft(&padded, coset, &worker));
+    }
+
+    #[test]
+    #[should_panic(expected = "vanishing polynomial is zero")]
+    fn test_divide_by_vanishing_on_subgroup() {
+        let worker = Worker::new_with_num_threads(1);
+        let mut evaluations = vec![GoldilocksField::ONE; 32];
+        divide_by_vanishing(&mut evaluations, GoldilocksField::ONE, 8, &worker);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        use crate::cs::implementations::utils::domain_generator_for_size;
+
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        for n in [1, 2, 8, 16, 32, 1 << 10] {
+            let coeffs: Vec<GoldilocksField> = (0..n).map(|_| rand_from_rng(&mut rng)).collect();
+            let evals = evaluate_over_subgroup(&coeffs, &worker);
+
+            let omega = domain_generator_for_size::<GoldilocksField>(n as u64);
+            for (i, value) in evals.iter().enumerate().step_by(std::cmp::max(1, n / 16)) {
+                let x = omega.pow_u64(i as u64);
+                let mut expected = GoldilocksField::ZERO;
+                for c in coeffs.iter().rev() {
+                    expected.mul_assign(&x);
+                    expected.add_assign(c);
+                }
+                assert_eq!(*value, expected, "invalid evaluation {} of size {}", i, n);
+            }
+
+            assert_eq!(interpolate(&evals, &worker), coeffs, "invalid roundtrip of size {}", n);
+        }
+    }
+
+    #[test]
+    fn test_subgroup_transforms_match_scalar() {
+        use std::alloc::Global;
+
+        use crate::{
+            fft::{
+                bitreverse_enumeration_inplace, fft_natural_to_bitreversed, ifft_natural_to_natural,
+            },
+            field::traits::field_like::PrimeFieldLikeVectorized,
+        };
+
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        // right below and at the smallest size of the MixedGL FFTs
+        for n in [16, 32] {
+            let input: Vec<GoldilocksField> = (0..n).map(|_| rand_from_rng(&mut rng)).collect();
+
+            let mut expected = input.clone();
+            let twiddles =
+                GoldilocksField::precompute_forward_twiddles_for_fft::<Global>(n, &worker, &mut ());
+            fft_natural_to_bitreversed(&mut expected, GoldilocksField::ONE, &twiddles);
+            bitreverse_enumeration_inplace(&mut expected);
+            assert_eq!(evaluate_over_subgroup(&input, &worker), expected, "invalid for {}", n);
+
+            let mut expected = input.clone();
+            let twiddles =
+                GoldilocksField::precompute_inverse_twiddles_for_fft::<Global>(n, &worker, &mut ());
+            ifft_natural_to_natural(&mut expected, GoldilocksField::ONE, &twiddles);
+            assert_eq!(interpolate(&input, &worker), expected, "invalid for {}", n);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Deterministic source of field elements, for reproducible tests and for deriving challenges
+//! from a transcript seed.
+//!
+//! The stream is fully specified: draws are 64-bit outputs of ChaCha20 (`rand_chacha::ChaCha20Rng`,
+//! 64-bit block counter starting at 0, stream 0, little-endian words), and every draw that is not
+//! below the field order is rejected. So the output is uniform over the field and doesn't depend
+//! on the platform or on the vectorized backend.
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use super::{GoldilocksField, MixedGL};
+
+/// Infinite iterator of uniformly distributed canonical field elements.
+#[derive(Clone, Debug)]
+pub struct SeededFieldRng {
+    rng: ChaCha20Rng,
+}
+
+impl SeededFieldRng {
+    /// Uses the full 32 byte ChaCha20 key as the seed.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self { rng: ChaCha20Rng::from_seed(seed) }
+    }
+
+    /// Expands `seed` into the ChaCha20 key the same way as `rand_core::SeedableRng::seed_from_u64`
+    /// (PCG32 output over the state initialized by `seed`).
+    pub fn from_u64_seed(seed: u64) -> Self {
+        Self { rng: ChaCha20Rng::seed_from_u64(seed) }
+    }
+
+    #[inline]
+    pub fn next_element(&mut self) -> GoldilocksField {
+        loop {
+            let candidate = self.rng.next_u64();
+            if candidate < GoldilocksField::ORDER {
+                return GoldilocksField(candidate);
+            }
+        }
+    }
+
+    pub fn fill(&mut self, dst: &mut [GoldilocksField]) {
+        for el in dst.iter_mut() {
+            *el = self.next_element();
+        }
+    }
+}
+
+impl Iterator for SeededFieldRng {
+    type Item = GoldilocksField;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_element())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl GoldilocksField {
+    /// See [`SeededFieldRng`] for the exact definition of the stream.
+    pub fn sample_from_seed(seed: u64) -> SeededFieldRng {
+        SeededFieldRng::from_u64_seed(seed)
+    }
+}
+
+impl MixedGL {
+    /// `num_vectors` vectors filled lane by lane from [`GoldilocksField::sample_from_seed`], so
+    /// unpacked they are exactly the first `num_vectors * SIZE_FACTOR` elements of that stream.
+    /// For benchmarks and tests whose inputs must be reproducible across machines.
+    pub fn random_vec(num_vectors: usize, seed: u64) -> Vec<Self> {
+        let mut rng = SeededFieldRng::from_u64_seed(seed);
+
+        (0..num_vectors)
+            .map(|_| Self::from_array(std::array::from_fn(|_| rng.next_element())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sample_from_seed_is_stable() {
+        let expected = [
+            0x063cded681f5f7b2,
+            0xfb65827e6efd22a8,
+            0xdc5b6a6901840fc0,
+            0x2f923fffd2a6f534,
+            0xe5716b57188ca258,
+            0x3ca0557886321ce6,
+            0x1c9a1f731ec9a8d0,
+            0x402e9d0d87c0a600,
+        ];
+        let sampled: Vec<u64> = GoldilocksField::sample_from_seed(0)
+            .take(8)
+            .map(|el| el.0)
+            .collect();
+        assert_eq!(sampled, expected);
+
+        let expected =
+            [0x8398bc11d7b54878, 0x6902c9f9a3176399, 0x190a545d007167d5, 0x2adbd0e8c9394918];
+        let sampled: Vec<u64> = GoldilocksField::sample_from_seed(42)
+            .take(4)
+            .map(|el| el.0)
+            .collect();
+        assert_eq!(sampled, expected);
+
+        // key only, no seed expansion involved. Those are the words of the well known ChaCha20
+        // block for the all-zero key
+        let expected =
+            [0x903df1a0ade0b876, 0x28bd8653e56a5d40, 0x1aed8da0b819d2bd, 0xc70d778bccef36a8];
+        let mut rng = SeededFieldRng::from_seed([0u8; 32]);
+        let mut sampled = [GoldilocksField(0); 4];
+        rng.fill(&mut sampled);
+        assert_eq!(sampled.map(|el| el.0), expected);
+    }
+
+    #[test]
+    fn test_sample_from_seed_is_canonical() {
+        for seed in 0..16 {
+            for el in GoldilocksField::sample_from_seed(seed).take(1 << 12) {
+                assert!(el.0 < GoldilocksField::ORDER);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_vec() {
+        use crate::field::traits::field_like::PrimeFieldLikeVectorized;
+
+        let num_vectors = 5;
+        let vectors = MixedGL::random_vec(num_vectors, 42);
+        assert_eq!(vectors.len(), num_vectors);
+        let expected: Vec<GoldilocksField> = GoldilocksField::sample_from_seed(42)
+            .take(num_vectors * MixedGL::SIZE_FACTOR)
+            .collect();
+        let unpacked: Vec<GoldilocksField> = vectors.iter().flat_map(|el| el.0).collect();
+        assert_eq!(unpacked, expected);
+
+        assert!(MixedGL::random_vec(0, 42).is_empty());
+        assert_ne!(MixedGL::random_vec(1, 43)[0], vectors[0]);
+    }
+}
@@ -276,6 +276,127 @@ macro_rules! impl_std_ops_for_field {
                 this
             }
         }
+
+        impl std::ops::Add<$type_name> for &'_ $type_name {
+            type Output = $type_name;
+
+            #[inline(always)]
+            fn add(self, rhs: $type_name) -> Self::Output {
+                let mut this = *self;
+                <$type_name as Field>::add_assign(&mut this, &rhs);
+
+                this
+            }
+        }
+
+        impl std::ops::Add<&'_ $type_name> for &'_ $type_name {
+            type Output = $type_name;
+
+            #[inline(always)]
+            fn add(self, rhs: & $type_name) -> Self::Output {
+                let mut this = *self;
+                <$type_name as Field>::add_assign(&mut this, rhs);
+
+                this
+            }
+        }
+
+        impl std::ops::Sub<$type_name> for &'_ $type_name {
+            type Output = $type_name;
+
+            #[inline(always)]
+            fn sub(self, rhs: $type_name) -> Self::Output {
+                let mut this = *self;
+                <$type_name as Field>::sub_assign(&mut this, &rhs);
+
+                this
+            }
+        }
+
+        impl std::ops::Sub<&'_ $type_name> for &'_ $type_name {
+            type Output = $type_name;
+
+            #[inline(always)]
+            fn sub(self, rhs: & $type_name) -> Self::Output {
+                let mut this = *self;
+                <$type_name as Field>::sub_assign(&mut this, rhs);
+
+                this
+            }
+        }
+
+        impl std::ops::Mul<$type_name> for &'_ $type_name {
+            type Output = $type_name;
+
+            #[inline(always)]
+            fn mul(self, rhs: $type_name) -> Self::Output {
+                let mut this = *self;
+                <$type_name as Field>::mul_assign(&mut this, &rhs);
+
+                this
+            }
+        }
+
+        impl std::ops::Mul<&'_ $type_name> for &'_ $type_name {
+            type Output = $type_name;
+
+            #[inline(always)]
+            fn mul(self, rhs: & $type_name) -> Self::Output {
+                let mut this = *self;
+                <$type_name as Field>::mul_assign(&mut this, rhs);
+
+                this
+            }
+        }
+
+        impl std::ops::Neg for &'_ $type_name {
+            type Output = $type_name;
+
+            #[inline(always)]
+            fn neg(self) -> Self::Output {
+                -*self
+            }
+        }
+
+        impl std::iter::Sum<$type_name> for $type_name {
+            #[inline]
+            fn sum<I: Iterator<Item = $type_name>>(iter: I) -> Self {
+                iter.fold(<Self as Field>::ZERO, |mut acc, el| {
+                    <Self as Field>::add_assign(&mut acc, &el);
+                    acc
+                })
+            }
+        }
+
+        impl<'a> std::iter::Sum<&'a $type_name> for $type_name {
+            #[inline]
+            fn sum<I: Iterator<Item = &'a $type_name>>(iter: I) -> Self {
+                iter.fold(<Self as Field>::ZERO, |mut acc, el| {
+                    <Self as Field>::add_assign(&mut acc, el);
+                    acc
+                })
+            }
+        }
+
+        impl std::iter::Product<$type_name> for $type_name {
+            #[inline]
+            fn product<I: Iterator<Item = $type_name>>(iter: I) -> Self {
+                iter.fold(<Self as Field>::ONE, |mut acc, el| {
+                    <Self as Field>::mul_assign(&mut acc, &el);
+                    acc
+                })
+            }
+        }
+
+        impl<'a> std::iter::Product<&'a $type_name> for $type_name {
+            #[inline]
+            fn product<I: Iterator<Item = &'a $type_name>>(iter: I) -> Self {
+                iter.fold(<Self as Field>::ONE, |mut acc, el| {
+                    <Self as Field>::mul_assign(&mut acc, el);
+                    acc
+                })
+            }
+        }
     }
 }
 
@@ -523,6 +644,19 @@ impl<F: Field, E: FieldExtension<2, BaseField = F>> ExtensionField<F, 2, E> {
     pub const fn from_coeff_in_base(coeffs: [F; 2]) -> Self {
         Self { coeffs, _marker: std::marker::PhantomData }
     }
+    /// Inverse of the base field embedding (`From<F>`), `None` if `self` is not in the base field.
+    #[inline(always)]
+    pub fn try_into_base(&self) -> Option<F> {
+        if self.coeffs[1].is_zero() { Some(self.coeffs[0]) } else { None }
+    }
+}
+
+/// Embedding of the base field, i.e. `base + 0 * u`.
+impl<F: Field, E: FieldExtension<2, BaseField = F>> From<F> for ExtensionField<F, 2, E> {
+    #[inline(always)]
+    fn from(base: F) -> Self {
+        Self::from_coeff_in_base([base, F::ZERO])
+    }
 }
 
 #[cfg(test)]
@@ -586,4 +720,30 @@ mod test {
 
         assert_eq!(product, F::ONE);
     }
+
+    #[test]
+    fn test_base_embedding() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let a: Base = crate::field::rand_from_rng(&mut rng);
+            let b: Base = crate::field::rand_from_rng(&mut rng);
+            let embedded = F::from(a);
+            assert_eq!(embedded.try_into_base(), Some(a));
+
+            // embedding is a homomorphism
+            let mut product = embedded;
+            product.mul_assign(&F::from(b));
+            let mut expected = a;
+            expected.mul_assign(&b);
+            assert_eq!(product.try_into_base(), Some(expected));
+
+            let mut not_base = embedded;
+            not_base.coeffs[1].add_assign(&Base::ONE);
+            assert_eq!(not_base.try_into_base(), None);
+        }
+
+        // non-canonical zero is still in the base field
+        let el = F::from_coeff_in_base([Base::TWO, GoldilocksField(GoldilocksField::ORDER)]);
+        assert_eq!(el.try_into_base(), Some(Base::TWO));
+    }
 }
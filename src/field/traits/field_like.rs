@@ -110,6 +110,8 @@ pub trait PrimeFieldLike:
 pub trait PrimeFieldLikeVectorized: PrimeFieldLike<Context = ()> {
     type Twiddles<A: GoodAllocator>: Send + Sync + std::fmt::Debug;
     type InverseTwiddles<A: GoodAllocator>: Send + Sync + std::fmt::Debug;
+    /// Number of base field elements packed into a single element of `Self`. Generic code should
+    /// use it instead of assuming a particular SIMD width, as it differs between the backends.
     const SIZE_FACTOR: usize = std::mem::size_of::<Self>() / std::mem::size_of::<Self::Base>();
     // zero check
     fn is_zero(&self) -> bool;
@@ -132,6 +134,24 @@ pub trait PrimeFieldLikeVectorized: PrimeFieldLike<Context = ()> {
 
     fn slice_into_base_slice_mut(input: &mut [Self]) -> &mut [Self::Base]; // no context here
 
+    /// Splits `input` into the longest prefix that can be reinterpreted as `&[Self]` and the tail
+    /// of less than `SIZE_FACTOR` base elements, so generic code can process the vectors and
+    /// then the tail without any checks of its own. If `input` doesn't start at the alignment of
+    /// `Self` nothing can be cast, and the whole of it is returned as the tail.
+    fn chunks_vectorized(input: &[Self::Base]) -> (&[Self], &[Self::Base]) {
+        let num_vectors = if input.as_ptr().addr() % std::mem::align_of::<Self>() == 0 {
+            input.len() / Self::SIZE_FACTOR
+        } else {
+            0
+        };
+        if num_vectors == 0 {
+            return (&[], input);
+        }
+        let (vectors, tail) = input.split_at(num_vectors * Self::SIZE_FACTOR);
+
+        (Self::slice_from_base_slice(vectors), tail)
+    }
+
     fn vec_from_base_vec<A: GoodAllocator>(input: Vec<Self::Base, A>) -> Vec<Self, A>; // no context here
     fn vec_into_base_vec<A: GoodAllocator>(input: Vec<Self, A>) -> Vec<Self::Base, A>; // no context here
     // and FFT to allow to switch to any accelerated implementations for free
@@ -160,6 +180,53 @@ pub trait PrimeFieldLikeVectorized: PrimeFieldLike<Context = ()> {
     ) -> Self::InverseTwiddles<A>;
 }
 
+/// Checks that `len` base field elements (within an allocation of `capacity` elements, if it's
+/// transferred too) starting at `addr` can be reinterpreted as elements of `P`, and panics with a
+/// description of the violated requirement otherwise.
+#[inline(always)]
+#[track_caller]
+pub fn assert_castable_from_base<P: PrimeFieldLikeVectorized>(
+    addr: usize,
+    len: usize,
+    capacity: Option<usize>,
+) {
+    let name = std::any::type_name::<P>();
+    assert!(
+        len >= P::SIZE_FACTOR,
+        "can not cast {} base field elements into {}: at least SIZE_FACTOR = {} are required",
+        len,
+        name,
+        P::SIZE_FACTOR
+    );
+    assert!(
+        len % P::SIZE_FACTOR == 0,
+        "can not cast {} base field elements into {}: length must be a multiple of SIZE_FACTOR = {}",
+        len,
+        name,
+        P::SIZE_FACTOR
+    );
+    if let Some(capacity) = capacity {
+        assert!(
+            capacity % P::SIZE_FACTOR == 0,
+            "can not cast a vector with capacity {} into {}: capacity must be a multiple of \
+             SIZE_FACTOR = {}",
+            capacity,
+            name,
+            P::SIZE_FACTOR
+        );
+    }
+    let alignment = std::mem::align_of::<P>();
+    assert!(
+        addr % alignment == 0,
+        "can not cast base field elements at 0x{:x} into {}: address must be aligned to {} bytes, \
+         but is off by {}",
+        addr,
+        name,
+        alignment,
+        addr % alignment
+    );
+}
+
 pub trait BaseField: PrimeField {}
 
 impl<F: SmallField> BaseField for F {}
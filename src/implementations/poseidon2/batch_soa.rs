@@ -0,0 +1,258 @@
+//! Poseidon2 over many states at once, with states stored as a structure of arrays, so every state
+//! element of `MixedGL::SIZE_FACTOR` consecutive states forms a single `MixedGL` vector.
+use super::{params::*, *};
+use crate::{
+    field::{
+        goldilocks::MixedGL,
+        traits::field_like::{PrimeFieldLike, PrimeFieldLikeVectorized},
+    },
+    implementations::poseidon_goldilocks_params::{HALF_NUM_FULL_ROUNDS, NUM_PARTIAL_ROUNDS},
+};
+
+pub(super) type VecState = [MixedGL; STATE_WIDTH];
+
+impl Poseidon2Goldilocks {
+    /// Applies `poseidon2_permutation` to `num_states` states stored in `states` as a structure of
+    /// arrays: element `i` of state `j` is `states[i * num_states + j]`, i.e. the first
+    /// `num_states` elements are the elements 0 of all the states, then the elements 1 and so on,
+    /// so `states` must have exactly `12 * num_states` elements. Every group of
+    /// `MixedGL::SIZE_FACTOR` states is permuted as 12 vectors loaded from contiguous memory, and
+    /// a group at the end that has less states is padded with zeroes.
+    pub fn permute_batch_soa(states: &mut [GoldilocksField], num_states: usize) {
+        assert_eq!(
+            states.len(),
+            STATE_WIDTH * num_states,
+            "SoA layout of {} states must have {} elements",
+            num_states,
+            STATE_WIDTH * num_states
+        );
+
+        for start in (0..num_states).step_by(MixedGL::SIZE_FACTOR) {
+            let len = std::cmp::min(MixedGL::SIZE_FACTOR, num_states - start);
+            let mut state = [MixedGL::zero(&mut ()); STATE_WIDTH];
+            for (i, dst) in state.iter_mut().enumerate() {
+                dst.0[..len].copy_from_slice(&states[i * num_states + start..][..len]);
+            }
+
+            permutation(&mut state);
+
+            for (i, src) in state.iter().enumerate() {
+                states[i * num_states + start..][..len].copy_from_slice(&src.0[..len]);
+            }
+        }
+    }
+
+    /// Lazily applies `poseidon2_permutation` to every state of `states`, e.g. for streaming
+    /// pipelines that don't materialize a slice. States are taken `MixedGL::SIZE_FACTOR` at a
+    /// time and permuted together, and a shorter group at the end is permuted one by one.
+    pub fn permute_iter<I>(states: I) -> impl Iterator<Item = [GoldilocksField; STATE_WIDTH]>
+    where
+        I: IntoIterator<Item = [GoldilocksField; STATE_WIDTH]>,
+    {
+        PermuteIter {
+            states: states.into_iter(),
+            batch: Vec::with_capacity(MixedGL::SIZE_FACTOR),
+            position: 0,
+        }
+    }
+}
+
+struct PermuteIter<I> {
+    states: I,
+    // permuted states, of which the ones from `position` are not yielded yet
+    batch: Vec<[GoldilocksField; STATE_WIDTH]>,
+    position: usize,
+}
+
+impl<I: Iterator<Item = [GoldilocksField; STATE_WIDTH]>> Iterator for PermuteIter<I> {
+    type Item = [GoldilocksField; STATE_WIDTH];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == self.batch.len() {
+            self.batch.clear();
+            self.position = 0;
+            self.batch
+                .extend(self.states.by_ref().take(MixedGL::SIZE_FACTOR));
+
+            if self.batch.len() == MixedGL::SIZE_FACTOR {
+                let mut state: VecState = std::array::from_fn(|i| {
+                    MixedGL::from_array(std::array::from_fn(|j| self.batch[j][i]))
+                });
+                permutation(&mut state);
+                for (j, dst) in self.batch.iter_mut().enumerate() {
+                    *dst = std::array::from_fn(|i| state[i].0[j]);
+                }
+            } else {
+                self.batch.iter_mut().for_each(poseidon2_permutation);
+            }
+        }
+
+        let result = self.batch.get(self.position).copied();
+        self.position += 1;
+
+        result
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.batch.len().saturating_sub(self.position);
+        let (lower, upper) = self.states.size_hint();
+
+        (lower + buffered, upper.map(|el| el + buffered))
+    }
+}
+
+#[inline(always)]
+pub(super) fn permutation(state: &mut VecState) {
+    external_matrix_mul(state);
+    for round in 0..HALF_NUM_FULL_ROUNDS {
+        full_round(state, round);
+    }
+    for round in 0..NUM_PARTIAL_ROUNDS {
+        partial_round(state, round);
+    }
+    for round in HALF_NUM_FULL_ROUNDS..(2 * HALF_NUM_FULL_ROUNDS) {
+        full_round(state, round);
+    }
+}
+
+#[inline(always)]
+fn full_round(state: &mut VecState, round: usize) {
+    for (el, constant) in state.iter_mut().zip(FULL_ROUND_CONSTANTS[round].iter()) {
+        el.add_assign(&MixedGL::constant(*constant, &mut ()), &mut ());
+        sbox(el);
+    }
+    external_matrix_mul(state);
+}
+
+#[inline(always)]
+fn partial_round(state: &mut VecState, round: usize) {
+    state[0].add_assign(&MixedGL::constant(PARTIAL_ROUND_CONSTANTS[round], &mut ()), &mut ());
+    sbox(&mut state[0]);
+    inner_matrix_mul(state);
+}
+
+#[inline(always)]
+fn sbox(el: &mut MixedGL) {
+    let mut square = *el;
+    square.square(&mut ());
+    let mut cube = square;
+    cube.mul_assign(el, &mut ());
+    square.square(&mut ());
+    *el = cube;
+    el.mul_assign(&square, &mut ());
+}
+
+// multiplication by `EXTERNAL_MDS_MATRIX_BLOCK` with additions and doublings only
+#[inline(always)]
+fn block_mul(x: &mut [MixedGL]) {
+    let mut t0 = x[0];
+    t0.add_assign(&x[1], &mut ());
+    let mut t1 = x[2];
+    t1.add_assign(&x[3], &mut ());
+    let mut t2 = x[1];
+    t2.double(&mut ()).add_assign(&t1, &mut ());
+    let mut t3 = x[3];
+    t3.double(&mut ()).add_assign(&t0, &mut ());
+    let mut t4 = t1;
+    t4.double(&mut ()).double(&mut ()).add_assign(&t3, &mut ());
+    let mut t5 = t0;
+    t5.double(&mut ()).double(&mut ()).add_assign(&t2, &mut ());
+    let mut t6 = t3;
+    t6.add_assign(&t5, &mut ());
+    let mut t7 = t2;
+    t7.add_assign(&t4, &mut ());
+
+    x[0] = t6;
+    x[1] = t5;
+    x[2] = t7;
+    x[3] = t4;
+}
+
+// `EXTERNAL_MDS_MATRIX` is `circ(2M, M, M)`, so every block of the result is the product of the
+// same block of the state with `M`, plus the sum of those products over all blocks
+#[inline(always)]
+fn external_matrix_mul(state: &mut VecState) {
+    for block in state.chunks_exact_mut(4) {
+        block_mul(block);
+    }
+    let mut sums = [MixedGL::zero(&mut ()); 4];
+    for block in state.chunks_exact(4) {
+        for (dst, src) in sums.iter_mut().zip(block.iter()) {
+            dst.add_assign(src, &mut ());
+        }
+    }
+    for block in state.chunks_exact_mut(4) {
+        for (dst, src) in block.iter_mut().zip(sums.iter()) {
+            dst.add_assign(src, &mut ());
+        }
+    }
+}
+
+// `INNER_ROUNDS_MATRIX` is all ones plus a diagonal
+#[inline(always)]
+fn inner_matrix_mul(state: &mut VecState) {
+    let mut sum = MixedGL::zero(&mut ());
+    for el in state.iter() {
+        sum.add_assign(el, &mut ());
+    }
+    for (el, diagonal) in state
+        .iter_mut()
+        .zip(INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS_MINUS_ONE.iter())
+    {
+        el.mul_constant_assign(diagonal);
+        el.add_assign(&sum, &mut ());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::rand_from_rng;
+
+    #[test]
+    fn test_permute_batch_soa() {
+        let mut rng = rand::thread_rng();
+        for num_states in [0, 1, 7, MixedGL::SIZE_FACTOR, MixedGL::SIZE_FACTOR * 2 + 3, 100] {
+            let original: Vec<GoldilocksField> = (0..STATE_WIDTH * num_states)
+                .map(|_| rand_from_rng(&mut rng))
+                .collect();
+
+            let mut states = original.clone();
+            Poseidon2Goldilocks::permute_batch_soa(&mut states, num_states);
+
+            // the same states in the array of structures layout, batched by `permute_iter`
+            let aos: Vec<[GoldilocksField; STATE_WIDTH]> = (0..num_states)
+                .map(|j| std::array::from_fn(|i| original[i * num_states + j]))
+                .collect();
+            let aos_results: Vec<_> = Poseidon2Goldilocks::permute_iter(aos.clone()).collect();
+            assert_eq!(aos_results.len(), num_states);
+
+            for (j, (input, aos_result)) in aos.iter().zip(aos_results.iter()).enumerate() {
+                let mut expected = *input;
+                poseidon2_permutation(&mut expected);
+                let result: [GoldilocksField; STATE_WIDTH] =
+                    std::array::from_fn(|i| states[i * num_states + j]);
+                assert_eq!(result, expected, "invalid state {} of {}", j, num_states);
+                assert_eq!(result, *aos_result, "layouts differ for state {} of {}", j, num_states);
+            }
+        }
+    }
+
+    #[test]
+    fn test_permute_iter() {
+        let mut rng = rand::thread_rng();
+        for num_states in [0, 1, MixedGL::SIZE_FACTOR, MixedGL::SIZE_FACTOR * 3 + 5] {
+            let original: Vec<[GoldilocksField; STATE_WIDTH]> = (0..num_states)
+                .map(|_| std::array::from_fn(|_| rand_from_rng(&mut rng)))
+                .collect();
+
+            let results: Vec<_> = Poseidon2Goldilocks::permute_iter(original.clone()).collect();
+            assert_eq!(results.len(), num_states);
+            for (j, (result, state)) in results.iter().zip(original.iter()).enumerate() {
+                let mut expected = *state;
+                poseidon2_permutation(&mut expected);
+                assert_eq!(*result, expected, "invalid state {} of {}", j, num_states);
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Description of the Poseidon2 instance, that can be stored alongside data produced with it and
+//! checked against the one compiled in before use.
+use super::*;
+use crate::implementations::poseidon_goldilocks_params::{
+    CAPACITY, NUM_FULL_ROUNDS_TOTAL, NUM_PARTIAL_ROUNDS, RATE,
+};
+
+/// Parameters of a Poseidon2 instance. The only supported one is [`Poseidon2Config::goldilocks`],
+/// that describes [`Poseidon2Goldilocks`], so a deserialized config should always be checked with
+/// [`Poseidon2Config::validate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Poseidon2Config {
+    pub width: usize,
+    pub rate: usize,
+    pub num_full_rounds: usize,
+    pub num_partial_rounds: usize,
+    /// Hash of all the round constants and matrices, see [`Poseidon2Config::constants_digest`].
+    pub constants_digest: [GoldilocksField; 4],
+}
+
+/// Reason why a [`Poseidon2Config`] can not be used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    UnsupportedWidth(usize),
+    /// Rate must leave room for the capacity of the supported width.
+    UnsupportedRate {
+        width: usize,
+        rate: usize,
+    },
+    UnsupportedRounds {
+        num_full_rounds: usize,
+        num_partial_rounds: usize,
+    },
+    /// Parameters are supported, but the round constants or matrices are different.
+    ConstantsMismatch,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedWidth(width) => {
+                write!(f, "unsupported width {}, only {} is supported", width, STATE_WIDTH)
+            }
+            Self::UnsupportedRate { width, rate } => {
+                write!(
+                    f,
+                    "unsupported rate {} for width {}, only {} is supported",
+                    rate, width, RATE
+                )
+            }
+            Self::UnsupportedRounds { num_full_rounds, num_partial_rounds } => write!(
+                f,
+                "unsupported number of rounds: {} full and {} partial, only {} and {} are supported",
+                num_full_rounds, num_partial_rounds, NUM_FULL_ROUNDS_TOTAL, NUM_PARTIAL_ROUNDS
+            ),
+            Self::ConstantsMismatch => write!(f, "round constants or matrices don't match"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Poseidon2Config {
+    /// Config of [`Poseidon2Goldilocks`].
+    pub fn goldilocks() -> Self {
+        Self {
+            width: STATE_WIDTH,
+            rate: RATE,
+            num_full_rounds: NUM_FULL_ROUNDS_TOTAL,
+            num_partial_rounds: NUM_PARTIAL_ROUNDS,
+            constants_digest: Self::constants_digest(),
+        }
+    }
+
+    /// Hash of the external matrix block, the internal matrix diagonal, and the full and partial
+    /// round constants (in this order), absorbed into the zero state in overwrite mode.
+    pub fn constants_digest() -> [GoldilocksField; 4] {
+        let mut elements = vec![];
+        elements.extend(params::EXTERNAL_MDS_MATRIX_BLOCK.iter().flatten().copied());
+        elements.extend_from_slice(&params::INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS);
+        elements.extend(params::FULL_ROUND_CONSTANTS.iter().flatten().copied());
+        elements.extend_from_slice(&params::PARTIAL_ROUND_CONSTANTS);
+
+        let mut state = Poseidon2Goldilocks.initial_state();
+        absorb_into_state_vararg::<_, Poseidon2Goldilocks, AbsorptionModeOverwrite, 8, 12, 4>(
+            &mut state, &elements,
+        );
+
+        Poseidon2Goldilocks.state_into_commitment_fixed::<4>(&state)
+    }
+
+    /// Checks that the parameters are supported, and that the constants they were created with
+    /// are the same as the ones used by [`Poseidon2Goldilocks`].
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.width != STATE_WIDTH {
+            return Err(ConfigError::UnsupportedWidth(self.width));
+        }
+        if self.rate != RATE || self.rate + CAPACITY != self.width {
+            return Err(ConfigError::UnsupportedRate { width: self.width, rate: self.rate });
+        }
+        if self.num_full_rounds != NUM_FULL_ROUNDS_TOTAL
+            || self.num_partial_rounds != NUM_PARTIAL_ROUNDS
+        {
+            return Err(ConfigError::UnsupportedRounds {
+                num_full_rounds: self.num_full_rounds,
+                num_partial_rounds: self.num_partial_rounds,
+            });
+        }
+        if self.constants_digest != Self::constants_digest() {
+            return Err(ConfigError::ConstantsMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let config = Poseidon2Config::goldilocks();
+        assert_eq!(config.width, 12);
+        assert_eq!(config.validate(), Ok(()));
+
+        let serialized = serde_json::to_string(&config).unwrap();
+        let deserialized: Poseidon2Config = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, config);
+        assert_eq!(deserialized.validate(), Ok(()));
+
+        let wide = Poseidon2Config { width: 16, rate: 12, ..config };
+        assert_eq!(wide.validate(), Err(ConfigError::UnsupportedWidth(16)));
+
+        let bad_rate = Poseidon2Config { rate: 4, ..config };
+        assert_eq!(bad_rate.validate(), Err(ConfigError::UnsupportedRate { width: 12, rate: 4 }));
+
+        let bad_rounds = Poseidon2Config { num_partial_rounds: 21, ..config };
+        assert_eq!(
+            bad_rounds.validate(),
+            Err(ConfigError::UnsupportedRounds { num_full_rounds: 8, num_partial_rounds: 21 })
+        );
+
+        let mut bad_constants = config;
+        bad_constants.constants_digest[0].add_assign(&GoldilocksField::ONE);
+        assert_eq!(bad_constants.validate(), Err(ConfigError::ConstantsMismatch));
+    }
+}
@@ -1,7 +1,11 @@
 use super::*;
 use crate::field::goldilocks::GoldilocksField;
 
+pub mod batch_soa;
+pub mod config;
 pub mod params;
+pub mod reference;
+pub mod wide;
 
 pub mod state_generic_impl;
 #[cfg(not(all(
@@ -31,11 +35,28 @@ use derivative::*;
     target_feature = "avx512vl"
 ))]
 pub use state_avx512::*;
+
+// the avx512 state has no folded variant of the permutation, so the feature would silently do
+// nothing there
+#[cfg(all(
+    feature = "poseidon2_folded_constants",
+    target_feature = "avx512bw",
+    target_feature = "avx512cd",
+    target_feature = "avx512dq",
+    target_feature = "avx512f",
+    target_feature = "avx512vl"
+))]
+compile_error!("`poseidon2_folded_constants` is not supported by the avx512 poseidon2 state");
+
 use unroll::unroll_for_loops;
 
 use crate::{
-    algebraic_props::round_function::*, field::traits::field::Field,
+    algebraic_props::{round_function::*, sponge::GoldilocksPoseidon2Sponge},
+    backend::{CpuBackend, HashBackend},
+    cs::oracle::TreeHasher,
+    field::traits::field::Field,
     implementations::poseidon_goldilocks_params::STATE_WIDTH,
+    worker::Worker,
 };
 
 #[derive(Derivative, serde::Serialize, serde::Deserialize)]
@@ -80,6 +101,13 @@ impl AlgebraicRoundFunctionWithParams<GoldilocksField, 8, 12, 4> for Poseidon2Go
                     i += 1;
                 }
             }
+            AbsorptionMode::Subtraction => {
+                let mut i = 0;
+                while i < 8 {
+                    state[i].sub_assign(&to_absorb[i]);
+                    i += 1;
+                }
+            }
         }
     }
 
@@ -138,3 +166,914 @@ impl AlgebraicRoundFunction<GoldilocksField, 8, 12, 4> for Poseidon2Goldilocks {
         result
     }
 }
+
+/// Same permutation as [`Poseidon2Goldilocks`], but the digest width `D` (4 or 8) is the commitment
+/// width of its [`AlgebraicRoundFunction`] impl, so [`TreeHasher::Output`] of a sponge over it has
+/// `D` elements. `MerkleTreeWithCap` over `GoldilocksPoseidon2SpongeWithDigest<_, D>` combines
+/// nodes with [`Poseidon2Goldilocks::compress`] of width `D`, and for `D = 4` it's the same tree
+/// as over [`GoldilocksPoseidon2Sponge`]. It's a separate type, as a second commitment width on
+/// [`Poseidon2Goldilocks`] would make the width ambiguous for all of its non-generic callers.
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Poseidon2GoldilocksDigest<const D: usize>;
+
+impl<const D: usize> AlgebraicRoundFunction<GoldilocksField, 8, 12, D>
+    for Poseidon2GoldilocksDigest<D>
+{
+    #[inline(always)]
+    fn round_function(state: &mut [GoldilocksField; 12]) {
+        poseidon2_permutation(state);
+    }
+    #[inline(always)]
+    fn initial_state() -> [GoldilocksField; 12] {
+        assert!(D == 4 || D == 8, "digest width must be 4 or 8, got {}", D);
+        [GoldilocksField::ZERO; STATE_WIDTH]
+    }
+    #[inline(always)]
+    fn specialize_for_len(len: u32, state: &mut [GoldilocksField; 12]) {
+        Poseidon2Goldilocks.specialize_for_len(len, state);
+    }
+    #[inline(always)]
+    #[unroll_for_loops]
+    fn absorb_into_state<M: AbsorptionModeTrait<GoldilocksField>>(
+        state: &mut [GoldilocksField; 12],
+        to_absorb: &[GoldilocksField; 8],
+    ) {
+        for i in 0..8 {
+            M::absorb(&mut state[i], &to_absorb[i]);
+        }
+    }
+    #[inline(always)]
+    fn state_into_commitment<const N: usize>(
+        state: &[GoldilocksField; 12],
+    ) -> [GoldilocksField; N] {
+        Poseidon2Goldilocks.state_into_commitment_fixed::<N>(state)
+    }
+}
+
+impl Poseidon2Goldilocks {
+    /// Same as `absorb_into_state`, but only the first `rate` elements of the state are written,
+    /// so the protocol can use a smaller rate than 8 (and larger capacity) without changing the
+    /// width. `to_absorb` must contain exactly `rate` elements, the rest of the state is left
+    /// untouched.
+    #[inline]
+    pub fn absorb_into_state_rate(
+        &self,
+        state: &mut [GoldilocksField; 12],
+        to_absorb: &[GoldilocksField],
+        rate: usize,
+        mode: AbsorptionMode,
+    ) {
+        assert!(rate > 0 && rate <= 8, "rate must be in 1..=8, got {}", rate);
+        assert_eq!(to_absorb.len(), rate);
+
+        match mode {
+            AbsorptionMode::Overwrite => {
+                state[..rate].copy_from_slice(to_absorb);
+            }
+            AbsorptionMode::Addition => {
+                for (dst, src) in state[..rate].iter_mut().zip(to_absorb.iter()) {
+                    dst.add_assign(src);
+                }
+            }
+            AbsorptionMode::Subtraction => {
+                for (dst, src) in state[..rate].iter_mut().zip(to_absorb.iter()) {
+                    dst.sub_assign(src);
+                }
+            }
+        }
+    }
+
+    /// Initial state with `iv` in the capacity elements `8..12`, and zeroes in the rate elements
+    /// `0..8`. Protocols that use different IVs get unrelated hash functions over the same
+    /// permutation. Note that `specialize_for_len` overwrites element 11, so the two ways of
+    /// domain separation should not be combined.
+    #[inline]
+    pub fn initial_state_with_iv(iv: [GoldilocksField; 4]) -> [GoldilocksField; 12] {
+        let mut state = [GoldilocksField::ZERO; 12];
+        state[8..].copy_from_slice(&iv);
+
+        state
+    }
+
+    /// One-shot hash of exactly `N` elements. The state is specialized for the length `N`, then
+    /// the input is absorbed 8 elements at a time in overwrite mode, and the last block is padded
+    /// with zeroes if `N` is not a multiple of 8. The number of blocks is known at compile time,
+    /// so there is no sponge bookkeeping left at runtime.
+    ///
+    /// The empty input (`N == 0`) is absorbed as a single all-zero padding block, so its digest
+    /// is the first 4 elements of the permutation of the zero state, and not the zero state
+    /// itself. It differs from the digest of `[ZERO]`, as the length is part of the state.
+    #[inline]
+    pub fn hash_fixed<const N: usize>(&self, input: &[GoldilocksField; N]) -> [GoldilocksField; 4] {
+        let mut state = self.initial_state();
+        self.specialize_for_len(N as u32, &mut state);
+
+        let mut chunks = input.array_chunks::<8>();
+        for chunk in &mut chunks {
+            self.absorb_into_state(&mut state, chunk, AbsorptionMode::Overwrite);
+            self.round_function(&mut state);
+        }
+
+        let remainder = chunks.remainder();
+        if remainder.is_empty() == false || N == 0 {
+            let mut last = [GoldilocksField::ZERO; 8];
+            last[..remainder.len()].copy_from_slice(remainder);
+            self.absorb_into_state(&mut state, &last, AbsorptionMode::Overwrite);
+            self.round_function(&mut state);
+        }
+
+        self.state_into_commitment_fixed::<4>(&state)
+    }
+
+    /// Domain tag of [`Self::prf`], the bytes `"p2prf"` as a little-endian integer.
+    pub const PRF_DOMAIN_TAG: GoldilocksField =
+        GoldilocksField(u64::from_le_bytes(*b"p2prf\0\0\0"));
+
+    /// Pseudorandom function of `input` keyed by `key`, e.g. to derive per-leaf blinding.
+    ///
+    /// The key is placed into the capacity elements `8..12`, [`Self::PRF_DOMAIN_TAG`] into the
+    /// rate element 0, and this state is permuted before any input is absorbed, so a keyed state
+    /// is never the initial state of a plain or an IV hash, where the rate starts zeroed. The input
+    /// is padded with a single `ONE` and then with zeroes up to a multiple of 8 elements, so inputs
+    /// that only differ by trailing zeroes don't collide, and absorbed 8 elements at a time in
+    /// overwrite mode. The first 4 elements of the state form the output.
+    pub fn prf(key: &[GoldilocksField; 4], input: &[GoldilocksField]) -> [GoldilocksField; 4] {
+        let mut state = Self::initial_state_with_iv(*key);
+        state[0] = Self::PRF_DOMAIN_TAG;
+        Poseidon2Goldilocks.round_function(&mut state);
+
+        let padded_len = (input.len() + 1).next_multiple_of(8);
+        let mut padded = Vec::with_capacity(padded_len);
+        padded.extend_from_slice(input);
+        padded.push(GoldilocksField::ONE);
+        padded.resize(padded_len, GoldilocksField::ZERO);
+        for block in padded.array_chunks::<8>() {
+            Poseidon2Goldilocks.absorb_into_state(&mut state, block, AbsorptionMode::Overwrite);
+            Poseidon2Goldilocks.round_function(&mut state);
+        }
+
+        Poseidon2Goldilocks.state_into_commitment_fixed::<4>(&state)
+    }
+
+    /// Number of witness elements hashed into a single leaf by [`Self::hash_witness`]. It's fixed,
+    /// so the digest doesn't depend on the number of threads in the worker.
+    pub const WITNESS_HASH_CHUNK_SIZE: usize = 1 << 12;
+
+    /// Hashes the full witness into a single digest, e.g. to be used as a cache key for proof
+    /// requests. The witness is split into chunks of [`Self::WITNESS_HASH_CHUNK_SIZE`] elements
+    /// that are hashed in parallel, leaf hashes are combined by layers as in a Merkle tree (an
+    /// unpaired node is moved to the next layer as is), and the root is hashed together with the
+    /// witness length.
+    pub fn hash_witness(witness: &[GoldilocksField], worker: &Worker) -> [GoldilocksField; 4] {
+        Self::hash_witness_with_backend(witness, &CpuBackend, worker)
+    }
+
+    /// Same as [`Self::hash_witness`], but the leaves are hashed by `backend`, e.g. on an
+    /// accelerator. Combining the leaf hashes stays on the CPU.
+    pub fn hash_witness_with_backend<B: HashBackend>(
+        witness: &[GoldilocksField],
+        backend: &B,
+        worker: &Worker,
+    ) -> [GoldilocksField; 4] {
+        type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        let leaves = backend.hash_leaves(witness, Self::WITNESS_HASH_CHUNK_SIZE, worker);
+        let root = Self::merkle_root(&leaves);
+
+        let mut hasher = H::default();
+        hasher.absorb(&root);
+        hasher.absorb(&[GoldilocksField::from_nonreduced_u64(witness.len() as u64)]);
+
+        hasher.finalize::<4>()
+    }
+
+    /// Digest of the width `D` (4 or 8) of two child nodes: `left || right` is absorbed into the
+    /// zero state in overwrite mode, 8 elements per permutation (so 2 permutations for `D = 8`),
+    /// and the digest is the first `D` elements of the state. It's the same as `hash_into_node` of
+    /// `GoldilocksPoseidon2SpongeWithDigest<AbsorptionModeOverwrite, D>`, and for `D = 4` of
+    /// `GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>`.
+    pub fn compress<const D: usize>(
+        left: [GoldilocksField; D],
+        right: [GoldilocksField; D],
+    ) -> [GoldilocksField; D] {
+        assert!(D == 4 || D == 8, "digest width must be 4 or 8, got {}", D);
+
+        let mut state = Self.initial_state();
+        absorb_into_state_vararg::<_, Self, AbsorptionModeOverwrite, 8, 12, 4>(
+            &mut state,
+            &[left, right].concat(),
+        );
+
+        Self.state_into_commitment_fixed::<D>(&state)
+    }
+
+    /// Root of the tree over `leaves` with nodes combined by [`Self::compress`] layer by layer.
+    /// An unpaired node is moved to the next layer as is, so the number of leaves doesn't need to
+    /// be a power of two, and the root of no leaves is all zeroes. For a power of two number of
+    /// leaves it's the cap of size 1 of `MerkleTreeWithCap` over
+    /// `GoldilocksPoseidon2SpongeWithDigest<AbsorptionModeOverwrite, D>` with these leaf hashes.
+    pub fn merkle_root<const D: usize>(leaves: &[[GoldilocksField; D]]) -> [GoldilocksField; D] {
+        let mut layer = leaves.to_vec();
+        while layer.len() > 1 {
+            layer = layer
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => Self::compress(*left, *right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+
+        layer.pop().unwrap_or([GoldilocksField::ZERO; D])
+    }
+}
+
+impl<M: AbsorptionModeTrait<GoldilocksField>> GoldilocksPoseidon2Sponge<M> {
+    /// Sponge that starts from [`Poseidon2Goldilocks::initial_state_with_iv`].
+    pub fn new_with_iv(iv: [GoldilocksField; 4]) -> Self {
+        Self::new_with_initial_state(Poseidon2Goldilocks::initial_state_with_iv(iv), &mut ())
+    }
+
+    /// Resets the sponge in place to the state of [`GoldilocksPoseidon2Sponge::new_with_iv`].
+    pub fn reset_with_iv(&mut self, iv: [GoldilocksField; 4]) {
+        self.reset_with_initial_state(Poseidon2Goldilocks::initial_state_with_iv(iv));
+    }
+}
+
+/// Hashes every row of the row-major `[num_rows][row_width]` matrix into a separate digest, rows
+/// are split between the worker threads. A row is absorbed into
+/// `GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>` by 8 elements, and if `row_width` is not a
+/// multiple of 8 the last chunk is padded with zeroes, same as by `hash_into_leaf`.
+pub fn hash_matrix_rows(
+    matrix: &[GoldilocksField],
+    num_rows: usize,
+    row_width: usize,
+    worker: &Worker,
+) -> Vec<[GoldilocksField; 4]> {
+    type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+    assert!(row_width > 0);
+    assert_eq!(matrix.len(), num_rows * row_width);
+
+    let mut result = vec![<H as TreeHasher<GoldilocksField>>::placeholder_output(); num_rows];
+    worker.scope(num_rows, |scope, chunk_size| {
+        for (dst, src) in result
+            .chunks_mut(chunk_size)
+            .zip(matrix.chunks(chunk_size * row_width))
+        {
+            scope.spawn(move |_| {
+                for (dst, row) in dst.iter_mut().zip(src.chunks_exact(row_width)) {
+                    *dst = <H as TreeHasher<GoldilocksField>>::hash_into_leaf(row);
+                }
+            });
+        }
+    });
+
+    result
+}
+
+/// Lazily hashes a stream of field elements, 8 at a time. Every chunk is hashed as a separate
+/// leaf with `GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>`, so the output is the same as
+/// of `hash_into_leaf` over the `chunks(8)` of the collected stream. In particular if the length of
+/// the stream is not a multiple of 8, the last digest is over the shorter chunk, i.e. it's padded
+/// with zeroes as by the sponge itself.
+#[derive(Clone, Debug)]
+pub struct HashChunks<I: Iterator<Item = GoldilocksField>> {
+    inner: I,
+}
+
+impl<I: Iterator<Item = GoldilocksField>> HashChunks<I> {
+    pub const CHUNK_SIZE: usize = 8;
+
+    pub fn new<T: IntoIterator<IntoIter = I>>(source: T) -> Self {
+        Self { inner: source.into_iter() }
+    }
+}
+
+impl<I: Iterator<Item = GoldilocksField>> Iterator for HashChunks<I> {
+    type Item = [GoldilocksField; 4];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buffer = [GoldilocksField::ZERO; 8];
+        let mut filled = 0;
+        for (dst, src) in buffer.iter_mut().zip(&mut self.inner) {
+            *dst = src;
+            filled += 1;
+        }
+
+        if filled == 0 {
+            return None;
+        }
+
+        Some(
+            <GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite> as TreeHasher<
+                GoldilocksField,
+            >>::hash_into_leaf(&buffer[..filled]),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.inner.size_hint();
+        (lower.div_ceil(Self::CHUNK_SIZE), upper.map(|el| el.div_ceil(Self::CHUNK_SIZE)))
+    }
+}
+
+/// Number of message bytes packed into a single field element by [`hash_bytes`].
+pub const HASH_BYTES_PER_ELEMENT: usize = 7;
+
+/// Canonical hash of an arbitrary byte message.
+///
+/// The message is padded with a single `0x01` byte and then with zero bytes up to a multiple of 7
+/// bytes. Every 7 bytes are interpreted as a little-endian integer, that is always below the field
+/// order, so the mapping into field elements is injective. Before absorption the state is
+/// specialized for the message length in bytes, then elements are absorbed 8 at a time in
+/// overwrite mode (the last chunk is padded with zeroes), and the first 4 elements of the state
+/// form the output.
+pub fn hash_bytes(data: &[u8]) -> [GoldilocksField; 4] {
+    assert!(data.len() <= u32::MAX as usize, "message is too long");
+
+    let num_elements = (data.len() + 1).div_ceil(HASH_BYTES_PER_ELEMENT);
+    let mut elements = Vec::with_capacity(num_elements);
+    let mut chunks = data.chunks_exact(HASH_BYTES_PER_ELEMENT);
+    for chunk in &mut chunks {
+        let mut buffer = [0u8; 8];
+        buffer[..HASH_BYTES_PER_ELEMENT].copy_from_slice(chunk);
+        elements.push(GoldilocksField(u64::from_le_bytes(buffer)));
+    }
+    let remainder = chunks.remainder();
+    let mut buffer = [0u8; 8];
+    buffer[..remainder.len()].copy_from_slice(remainder);
+    buffer[remainder.len()] = 0x01;
+    elements.push(GoldilocksField(u64::from_le_bytes(buffer)));
+    debug_assert_eq!(elements.len(), num_elements);
+
+    let mut state = Poseidon2Goldilocks.initial_state();
+    Poseidon2Goldilocks.specialize_for_len(data.len() as u32, &mut state);
+    absorb_into_state_vararg::<_, Poseidon2Goldilocks, AbsorptionModeOverwrite, 8, 12, 4>(
+        &mut state, &elements,
+    );
+
+    Poseidon2Goldilocks.state_into_commitment_fixed::<4>(&state)
+}
+
+/// Duplex sponge over the Poseidon2 permutation, for transcripts that interleave absorbing and
+/// squeezing.
+///
+/// The width 12 state starts as all zeroes. Elements `0..8` are the rate and elements `8..12` are
+/// the capacity, which is never written directly and is carried over between calls. Every call to
+/// [`Self::duplexing`] overwrites the rate with the input, followed by a single `ONE` and then
+/// zeroes up to 8 elements (so at most 7 elements can be absorbed per call), applies the
+/// permutation, and returns the first `out_len` elements of the rate.
+#[derive(Clone, Debug)]
+pub struct Poseidon2Duplex {
+    state: [GoldilocksField; 12],
+}
+
+impl Poseidon2Duplex {
+    pub const RATE: usize = 8;
+    pub const CAPACITY: usize = 4;
+    /// One rate element is always taken by the padding.
+    pub const MAX_INPUT_LEN: usize = Self::RATE - 1;
+
+    pub fn new() -> Self {
+        Self { state: Poseidon2Goldilocks.initial_state() }
+    }
+
+    pub fn duplexing(&mut self, input: &[GoldilocksField], out_len: usize) -> Vec<GoldilocksField> {
+        assert!(
+            input.len() <= Self::MAX_INPUT_LEN,
+            "at most {} elements can be absorbed per call, got {}",
+            Self::MAX_INPUT_LEN,
+            input.len()
+        );
+        assert!(
+            out_len <= Self::RATE,
+            "at most {} elements can be squeezed per call, got {}",
+            Self::RATE,
+            out_len
+        );
+
+        let mut block = [GoldilocksField::ZERO; 8];
+        block[..input.len()].copy_from_slice(input);
+        block[input.len()] = GoldilocksField::ONE;
+
+        Poseidon2Goldilocks.absorb_into_state(&mut self.state, &block, AbsorptionMode::Overwrite);
+        Poseidon2Goldilocks.round_function(&mut self.state);
+
+        self.state[..out_len].to_vec()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::rand_from_rng;
+
+    #[test]
+    fn test_hash_witness() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new();
+
+        let size = Poseidon2Goldilocks::WITNESS_HASH_CHUNK_SIZE * 5 + 17;
+        let witness: Vec<GoldilocksField> = (0..size).map(|_| rand_from_rng(&mut rng)).collect();
+
+        let digest = Poseidon2Goldilocks::hash_witness(&witness, &worker);
+        assert_eq!(digest, Poseidon2Goldilocks::hash_witness(&witness.clone(), &worker));
+
+        // independent of the number of threads
+        let single_threaded = Worker::new_with_num_threads(1);
+        assert_eq!(digest, Poseidon2Goldilocks::hash_witness(&witness, &single_threaded));
+
+        for idx in [0, Poseidon2Goldilocks::WITNESS_HASH_CHUNK_SIZE * 2 + 3, size - 1] {
+            let mut modified = witness.clone();
+            Field::add_assign(&mut modified[idx], &GoldilocksField::ONE);
+            assert_ne!(digest, Poseidon2Goldilocks::hash_witness(&modified, &worker));
+        }
+
+        // length is a part of the digest
+        assert_ne!(
+            Poseidon2Goldilocks::hash_witness(&[], &worker),
+            Poseidon2Goldilocks::hash_witness(&[GoldilocksField::ZERO], &worker)
+        );
+    }
+
+    #[test]
+    fn test_hash_bytes_known_answers() {
+        assert_eq!(
+            hash_bytes(&[]),
+            [
+                GoldilocksField(0x4fe68029cd243d66),
+                GoldilocksField(0x2fe0f24323e6f60c),
+                GoldilocksField(0xe653c2d561eec822),
+                GoldilocksField(0x2b40438dd69dd73c),
+            ]
+        );
+        assert_eq!(
+            hash_bytes(&[0x2a]),
+            [
+                GoldilocksField(0x7539075da1b6b15e),
+                GoldilocksField(0x5fb9f9483b9265af),
+                GoldilocksField(0xd61ebbc331319c14),
+                GoldilocksField(0x78b8ce47dff4d44d),
+            ]
+        );
+        let message: Vec<u8> = (0..100).collect();
+        assert_eq!(
+            hash_bytes(&message),
+            [
+                GoldilocksField(0xf70b252507c655e7),
+                GoldilocksField(0x11e37f256c133955),
+                GoldilocksField(0x6ed0262b12bd875a),
+                GoldilocksField(0x137c966952f6c6bd),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_bytes_padding() {
+        // trailing zeroes and the padding byte itself must not collide
+        assert_ne!(hash_bytes(&[]), hash_bytes(&[0]));
+        assert_ne!(hash_bytes(&[0]), hash_bytes(&[0, 0]));
+        assert_ne!(hash_bytes(&[1]), hash_bytes(&[1, 1]));
+        assert_ne!(hash_bytes(&[0; 7]), hash_bytes(&[0; 8]));
+    }
+
+    #[test]
+    fn test_absorb_into_state_rate() {
+        let mut rng = rand::thread_rng();
+        for mode in
+            [AbsorptionMode::Overwrite, AbsorptionMode::Addition, AbsorptionMode::Subtraction]
+        {
+            let state: [GoldilocksField; 12] = std::array::from_fn(|_| rand_from_rng(&mut rng));
+            let to_absorb: [GoldilocksField; 8] = std::array::from_fn(|_| rand_from_rng(&mut rng));
+
+            let mut expected = state;
+            Poseidon2Goldilocks.absorb_into_state(&mut expected, &to_absorb, mode);
+            let mut full_rate = state;
+            Poseidon2Goldilocks.absorb_into_state_rate(&mut full_rate, &to_absorb, 8, mode);
+            assert_eq!(full_rate, expected);
+
+            let mut half_rate = state;
+            Poseidon2Goldilocks.absorb_into_state_rate(&mut half_rate, &to_absorb[..4], 4, mode);
+            assert_eq!(half_rate[..4], expected[..4]);
+            assert_eq!(half_rate[4..], state[4..]);
+        }
+    }
+
+    #[test]
+    fn test_subtraction_reverts_addition() {
+        type R = Poseidon2Goldilocks;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let state: [GoldilocksField; 12] = std::array::from_fn(|_| rand_from_rng(&mut rng));
+            let to_absorb: [GoldilocksField; 8] = std::array::from_fn(|_| rand_from_rng(&mut rng));
+
+            let mut by_mode = state;
+            Poseidon2Goldilocks.absorb_into_state(
+                &mut by_mode,
+                &to_absorb,
+                AbsorptionMode::Addition,
+            );
+            assert_ne!(by_mode, state);
+            let mut by_trait = state;
+            <R as AlgebraicRoundFunction<GoldilocksField, 8, 12, 4>>::absorb_into_state::<
+                AbsorptionModeAdd,
+            >(&mut by_trait, &to_absorb);
+            assert_eq!(by_trait, by_mode);
+
+            Poseidon2Goldilocks.absorb_into_state(
+                &mut by_mode,
+                &to_absorb,
+                AbsorptionMode::Subtraction,
+            );
+            assert_eq!(by_mode, state);
+            <R as AlgebraicRoundFunction<GoldilocksField, 8, 12, 4>>::absorb_into_state::<
+                AbsorptionModeSub,
+            >(&mut by_trait, &to_absorb);
+            assert_eq!(by_trait, state);
+        }
+    }
+
+    #[test]
+    fn test_hash_chunks() {
+        type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        let mut rng = rand::thread_rng();
+        for size in [0, 1, 8, 15, 16, 1000] {
+            let values: Vec<GoldilocksField> = (0..size).map(|_| rand_from_rng(&mut rng)).collect();
+            let expected: Vec<[GoldilocksField; 4]> = values
+                .chunks(8)
+                .map(<H as TreeHasher<GoldilocksField>>::hash_into_leaf)
+                .collect();
+
+            let hashes = HashChunks::new(values.iter().copied());
+            assert_eq!(hashes.size_hint(), (expected.len(), Some(expected.len())));
+            let lazy: Vec<[GoldilocksField; 4]> = hashes.collect();
+            assert_eq!(lazy, expected);
+        }
+    }
+
+    #[test]
+    fn test_hash_matrix_rows() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(4);
+        for (num_rows, row_width) in [(0, 8), (1, 3), (17, 8), (33, 13), (64, 16)] {
+            let matrix: Vec<GoldilocksField> = (0..num_rows * row_width)
+                .map(|_| rand_from_rng(&mut rng))
+                .collect();
+
+            let mut expected = vec![];
+            for row in matrix.chunks(row_width) {
+                let mut hasher = GoldilocksPoseidon2Sponge::<AbsorptionModeOverwrite>::default();
+                for chunk in row.chunks(8) {
+                    let mut padded = [GoldilocksField::ZERO; 8];
+                    padded[..chunk.len()].copy_from_slice(chunk);
+                    hasher.absorb(&padded);
+                }
+                expected.push(hasher.finalize::<4>());
+            }
+
+            assert_eq!(hash_matrix_rows(&matrix, num_rows, row_width, &worker), expected);
+        }
+    }
+
+    #[test]
+    fn test_hash_fixed_known_answers() {
+        let input: [GoldilocksField; 12] = std::array::from_fn(|i| GoldilocksField(i as u64 + 1));
+
+        let input_4: [GoldilocksField; 4] = input[..4].try_into().unwrap();
+        assert_eq!(
+            Poseidon2Goldilocks.hash_fixed(&input_4),
+            [
+                GoldilocksField(0x16fa3b61737c74bd),
+                GoldilocksField(0x594f05d23bfd8307),
+                GoldilocksField(0xf05caba8861d5404),
+                GoldilocksField(0x0a4ef6b7a2c18cd2),
+            ]
+        );
+        let input_8: [GoldilocksField; 8] = input[..8].try_into().unwrap();
+        assert_eq!(
+            Poseidon2Goldilocks.hash_fixed(&input_8),
+            [
+                GoldilocksField(0x74bb8f319a4ca558),
+                GoldilocksField(0x98f958791880e8c8),
+                GoldilocksField(0x516622486adf52c7),
+                GoldilocksField(0x26027250acb1ab2a),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Goldilocks.hash_fixed(&input),
+            [
+                GoldilocksField(0x6fda81dd2adf11a6),
+                GoldilocksField(0x4d43e8d5c771cdac),
+                GoldilocksField(0x3484ea8d41bd47b1),
+                GoldilocksField(0xa9d38a5dafa66cc4),
+            ]
+        );
+
+        // empty input is padded to a single block
+        let empty = Poseidon2Goldilocks.hash_fixed(&[]);
+        assert_eq!(
+            empty,
+            [
+                GoldilocksField(0x78e86c27e831c353),
+                GoldilocksField(0xc4c13a505ffd93b8),
+                GoldilocksField(0xc3a6d7d7f7971adc),
+                GoldilocksField(0xf6ff8f53ab94d8c7),
+            ]
+        );
+        let mut state = Poseidon2Goldilocks.initial_state();
+        poseidon2_permutation(&mut state);
+        assert_eq!(empty, Poseidon2Goldilocks.state_into_commitment_fixed::<4>(&state));
+        assert_ne!(empty, [GoldilocksField::ZERO; 4]);
+        assert_eq!(
+            Poseidon2Goldilocks.hash_fixed(&[GoldilocksField::ZERO]),
+            [
+                GoldilocksField(0x49c55e8237156a9f),
+                GoldilocksField(0x3ca73d1e6a75d308),
+                GoldilocksField(0xe2e563a51e41c22c),
+                GoldilocksField(0x3d72e7fa181307f8),
+            ]
+        );
+
+        // same as the generic absorption with the length specialization
+        let mut state = Poseidon2Goldilocks.initial_state();
+        Poseidon2Goldilocks.specialize_for_len(12, &mut state);
+        absorb_into_state_vararg::<_, Poseidon2Goldilocks, AbsorptionModeOverwrite, 8, 12, 4>(
+            &mut state, &input,
+        );
+        assert_eq!(
+            Poseidon2Goldilocks.hash_fixed(&input),
+            Poseidon2Goldilocks.state_into_commitment_fixed::<4>(&state)
+        );
+    }
+
+    #[test]
+    fn test_duplex() {
+        let mut rng = rand::thread_rng();
+        let inputs: Vec<Vec<GoldilocksField>> = (0..=Poseidon2Duplex::MAX_INPUT_LEN)
+            .map(|len| (0..len).map(|_| rand_from_rng(&mut rng)).collect())
+            .collect();
+
+        let run = |inputs: &[Vec<GoldilocksField>]| {
+            let mut duplex = Poseidon2Duplex::new();
+            inputs
+                .iter()
+                .map(|input| duplex.duplexing(input, Poseidon2Duplex::RATE))
+                .collect::<Vec<_>>()
+        };
+
+        let outputs = run(&inputs);
+        assert_eq!(outputs, run(&inputs));
+
+        // the first call is a single permutation of the padded input
+        let mut state = [GoldilocksField::ZERO; 12];
+        state[0] = GoldilocksField::ONE;
+        Poseidon2Goldilocks.round_function(&mut state);
+        assert_eq!(outputs[0], state[..8]);
+
+        // a difference after the first permutation changes that and all the later outputs, but
+        // not the earlier ones
+        let mut modified = inputs.clone();
+        Field::add_assign(&mut modified[3][1], &GoldilocksField::ONE);
+        let modified_outputs = run(&modified);
+        assert_eq!(outputs[..3], modified_outputs[..3]);
+        for (a, b) in outputs[3..].iter().zip(modified_outputs[3..].iter()) {
+            assert_ne!(a, b);
+        }
+
+        // padding separates a trailing zero from a shorter input
+        let mut duplex = Poseidon2Duplex::new();
+        let mut other = Poseidon2Duplex::new();
+        assert_ne!(
+            duplex.duplexing(&[GoldilocksField::ONE], 8),
+            other.duplexing(&[GoldilocksField::ONE, GoldilocksField::ZERO], 8)
+        );
+    }
+
+    #[test]
+    fn test_sponge_with_iv() {
+        type Sponge = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        let mut rng = rand::thread_rng();
+        let input: Vec<GoldilocksField> = (0..19).map(|_| rand_from_rng(&mut rng)).collect();
+        let digest_with_iv = |iv: [GoldilocksField; 4]| {
+            let mut sponge = Sponge::new_with_iv(iv);
+            sponge.absorb(&input);
+            sponge.finalize::<4>()
+        };
+
+        // zero IV is the same as the default initial state
+        let mut sponge = Sponge::default();
+        sponge.absorb(&input);
+        assert_eq!(digest_with_iv([GoldilocksField::ZERO; 4]), sponge.finalize::<4>());
+
+        let iv_a = [GoldilocksField(1), GoldilocksField(2), GoldilocksField(3), GoldilocksField(4)];
+        let mut iv_b = iv_a;
+        iv_b[3] = GoldilocksField(5);
+        assert_eq!(digest_with_iv(iv_a), digest_with_iv(iv_a));
+        assert_ne!(digest_with_iv(iv_a), digest_with_iv(iv_b));
+        assert_ne!(digest_with_iv(iv_a), digest_with_iv([GoldilocksField::ZERO; 4]));
+
+        let state = Poseidon2Goldilocks::initial_state_with_iv(iv_a);
+        assert_eq!(state[..8], [GoldilocksField::ZERO; 8]);
+        assert_eq!(state[8..], iv_a);
+    }
+
+    #[test]
+    fn test_sponge_reset() {
+        type Sponge = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        let mut rng = rand::thread_rng();
+        let iv = [GoldilocksField(1), GoldilocksField(2), GoldilocksField(3), GoldilocksField(4)];
+        let mut sponge = Sponge::default();
+        // partially filled buffer, full rounds, and a buffer filled after a round
+        for len in [3, 8, 19, 0, 1] {
+            let input: Vec<GoldilocksField> = (0..len).map(|_| rand_from_rng(&mut rng)).collect();
+            let garbage: Vec<GoldilocksField> = (0..11).map(|_| rand_from_rng(&mut rng)).collect();
+
+            sponge.absorb(&garbage);
+            sponge.reset();
+            sponge.absorb(&input);
+            let mut fresh = Sponge::default();
+            fresh.absorb(&input);
+            assert_eq!(sponge.try_get_commitment::<4>(), fresh.try_get_commitment::<4>());
+            assert_eq!(sponge.finalize::<4>(), fresh.finalize::<4>());
+
+            sponge.absorb(&garbage);
+            sponge.reset_with_iv(iv);
+            sponge.absorb(&input);
+            let mut fresh = Sponge::new_with_iv(iv);
+            fresh.absorb(&input);
+            assert_eq!(sponge.finalize::<4>(), fresh.finalize::<4>());
+        }
+    }
+
+    #[test]
+    fn test_prf() {
+        type Sponge = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        let key = [GoldilocksField(1), GoldilocksField(2), GoldilocksField(3), GoldilocksField(4)];
+        let input = [GoldilocksField(5), GoldilocksField(6), GoldilocksField(7)];
+        // computed with an independent implementation of the permutation
+        assert_eq!(
+            Poseidon2Goldilocks::prf(&key, &input),
+            [
+                GoldilocksField(0x3b92f00237112e2e),
+                GoldilocksField(0x2e3144f29a454e2d),
+                GoldilocksField(0x6336a401171b28a9),
+                GoldilocksField(0x77fdaeb552142c9c),
+            ]
+        );
+        let long_input: Vec<GoldilocksField> = (100..109).map(GoldilocksField).collect();
+        assert_eq!(
+            Poseidon2Goldilocks::prf(&key, &long_input),
+            [
+                GoldilocksField(0xff0f1f2fb98decdb),
+                GoldilocksField(0x2b110f3e56b34f15),
+                GoldilocksField(0x95a84c8027928c15),
+                GoldilocksField(0xbaaf6b9d2011a963),
+            ]
+        );
+
+        let mut rng = rand::thread_rng();
+        for len in [0, 1, 7, 8, 19] {
+            let input: Vec<GoldilocksField> = (0..len).map(|_| rand_from_rng(&mut rng)).collect();
+            let key_a: [GoldilocksField; 4] = std::array::from_fn(|_| rand_from_rng(&mut rng));
+            let mut key_b = key_a;
+            key_b[3].add_assign(&GoldilocksField::ONE);
+
+            let output = Poseidon2Goldilocks::prf(&key_a, &input);
+            assert_eq!(Poseidon2Goldilocks::prf(&key_a, &input), output);
+            assert_ne!(Poseidon2Goldilocks::prf(&key_b, &input), output);
+
+            let mut extended = input.clone();
+            extended.push(GoldilocksField::ZERO);
+            assert_ne!(Poseidon2Goldilocks::prf(&key_a, &extended), output);
+
+            // not the same as hashing with the key as IV
+            let mut sponge = Sponge::new_with_iv(key_a);
+            sponge.absorb(&input);
+            assert_ne!(sponge.finalize::<4>(), output);
+        }
+    }
+
+    #[test]
+    fn test_compress() {
+        type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        let mut rng = rand::thread_rng();
+        let input: [GoldilocksField; 16] = std::array::from_fn(|_| rand_from_rng(&mut rng));
+
+        // width 4: a single block of both children
+        let left: [GoldilocksField; 4] = input[..4].try_into().unwrap();
+        let right: [GoldilocksField; 4] = input[4..8].try_into().unwrap();
+        let mut state = [GoldilocksField::ZERO; STATE_WIDTH];
+        state[..8].copy_from_slice(&input[..8]);
+        poseidon2_permutation(&mut state);
+        let digest = Poseidon2Goldilocks::compress(left, right);
+        assert_eq!(digest[..], state[..4]);
+        assert_eq!(digest, <H as TreeHasher<GoldilocksField>>::hash_into_node(&left, &right, 0));
+
+        // width 8: the left child, then the right one
+        let left: [GoldilocksField; 8] = input[..8].try_into().unwrap();
+        let right: [GoldilocksField; 8] = input[8..].try_into().unwrap();
+        let mut state = [GoldilocksField::ZERO; STATE_WIDTH];
+        state[..8].copy_from_slice(&left);
+        poseidon2_permutation(&mut state);
+        state[..8].copy_from_slice(&right);
+        poseidon2_permutation(&mut state);
+        assert_eq!(Poseidon2Goldilocks::compress(left, right)[..], state[..8]);
+        assert_ne!(Poseidon2Goldilocks::compress(right, left)[..], state[..8]);
+    }
+
+    #[test]
+    fn test_merkle_root() {
+        let mut rng = rand::thread_rng();
+        let leaves: Vec<[GoldilocksField; 8]> = (0..5)
+            .map(|_| std::array::from_fn(|_| rand_from_rng(&mut rng)))
+            .collect();
+
+        let compress = Poseidon2Goldilocks::compress::<8>;
+        let expected = compress(
+            compress(compress(leaves[0], leaves[1]), compress(leaves[2], leaves[3])),
+            leaves[4],
+        );
+        assert_eq!(Poseidon2Goldilocks::merkle_root(&leaves), expected);
+        assert_eq!(Poseidon2Goldilocks::merkle_root(&leaves[..1]), leaves[0]);
+        assert_eq!(Poseidon2Goldilocks::merkle_root::<8>(&[]), [GoldilocksField::ZERO; 8]);
+
+        let narrow: Vec<[GoldilocksField; 4]> = leaves
+            .iter()
+            .map(|el| el[..4].try_into().unwrap())
+            .collect();
+        let compress = Poseidon2Goldilocks::compress::<4>;
+        let expected = compress(
+            compress(compress(narrow[0], narrow[1]), compress(narrow[2], narrow[3])),
+            narrow[4],
+        );
+        assert_eq!(Poseidon2Goldilocks::merkle_root(&narrow), expected);
+    }
+
+    fn check_tree_with_digest<const D: usize>() {
+        use crate::{
+            algebraic_props::sponge::GoldilocksPoseidon2SpongeWithDigest,
+            cs::oracle::merkle_tree::MerkleTreeWithCap,
+        };
+
+        type F = GoldilocksField;
+
+        let worker = Worker::new_with_num_threads(4);
+        let mut rng = rand::thread_rng();
+        let elements_per_leaf = 4;
+        let num_leaves = 1 << 5;
+        let source: Vec<F> = (0..num_leaves * elements_per_leaf)
+            .map(|_| rand_from_rng(&mut rng))
+            .collect();
+        let sources = vec![&source];
+
+        let tree = MerkleTreeWithCap::<
+            F,
+            GoldilocksPoseidon2SpongeWithDigest<AbsorptionModeOverwrite, D>,
+        >::construct_by_chunking_from_flat_sources(
+            &sources, elements_per_leaf, 1, &worker
+        );
+        assert_eq!(tree.leaf_hashes.len(), num_leaves);
+
+        // leaves are hashed by the sponge in one go, nodes by compress
+        for (leaf, chunk) in tree
+            .leaf_hashes
+            .iter()
+            .zip(source.chunks(elements_per_leaf))
+        {
+            let mut state = [F::ZERO; STATE_WIDTH];
+            state[..elements_per_leaf].copy_from_slice(chunk);
+            poseidon2_permutation(&mut state);
+            assert_eq!(leaf[..], state[..D]);
+        }
+        let cap = tree.get_cap();
+        assert_eq!(cap[..], [Poseidon2Goldilocks::merkle_root::<D>(&tree.leaf_hashes)]);
+
+        let idx = 13;
+        let (leaf_hash, proof) = tree.get_proof::<std::alloc::Global>(idx);
+        assert!(MerkleTreeWithCap::<
+            F,
+            GoldilocksPoseidon2SpongeWithDigest<AbsorptionModeOverwrite, D>,
+        >::verify_proof_over_cap(&proof, &cap, leaf_hash, idx));
+    }
+
+    #[test]
+    fn test_merkle_tree_with_digest() {
+        check_tree_with_digest::<4>();
+        check_tree_with_digest::<8>();
+    }
+
+    #[test]
+    #[should_panic(expected = "digest width must be 4 or 8")]
+    fn test_compress_unsupported_width() {
+        Poseidon2Goldilocks::compress([GoldilocksField::ZERO; 2], [GoldilocksField::ONE; 2]);
+    }
+}
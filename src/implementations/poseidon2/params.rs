@@ -1,12 +1,22 @@
 //! A set of matrix parameters used for poseidon2.
+//!
+//! These are exactly the values used by `poseidon2_permutation`, and are the ones
+//! an in-circuit implementation must use to produce matching outputs. The permutation is
+//! - an initial multiplication by [EXTERNAL_MDS_MATRIX];
+//! - `HALF_NUM_FULL_ROUNDS` full rounds: add the round's [FULL_ROUND_CONSTANTS] row, apply `x^7` to
+//!   every element, multiply by [EXTERNAL_MDS_MATRIX];
+//! - `NUM_PARTIAL_ROUNDS` partial rounds: add [PARTIAL_ROUND_CONSTANTS] to element 0, apply `x^7`
+//!   to element 0 only, multiply by [INNER_ROUNDS_MATRIX];
+//! - the remaining `HALF_NUM_FULL_ROUNDS` full rounds.
 use super::*;
 use crate::{
     field::traits::field::*,
     implementations::poseidon_goldilocks_params::{
-        self, HALF_NUM_FULL_ROUNDS, NUM_FULL_ROUNDS_TOTAL, NUM_PARTIAL_ROUNDS,
+        self, HALF_NUM_FULL_ROUNDS, NUM_FULL_ROUNDS_TOTAL, NUM_PARTIAL_ROUNDS, TOTAL_NUM_ROUNDS,
     },
 };
 
+/// 4x4 block from which [EXTERNAL_MDS_MATRIX] is built.
 pub const EXTERNAL_MDS_MATRIX_BLOCK: [[GoldilocksField; 4]; 4] = [
     [GoldilocksField(5), GoldilocksField(7), GoldilocksField(1), GoldilocksField(3)],
     [GoldilocksField(4), GoldilocksField(6), GoldilocksField(1), GoldilocksField(1)],
@@ -14,9 +24,11 @@ pub const EXTERNAL_MDS_MATRIX_BLOCK: [[GoldilocksField; 4]; 4] = [
     [GoldilocksField(1), GoldilocksField(1), GoldilocksField(4), GoldilocksField(6)],
 ];
 
+/// Diagonal of [INNER_ROUNDS_MATRIX] minus one, as powers of two.
 pub const INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS_MINUS_ONE_SHIFTS: [u32; 12] =
     [4, 14, 11, 8, 0, 5, 2, 9, 13, 6, 3, 12];
 
+/// Diagonal of [INNER_ROUNDS_MATRIX] minus one.
 pub const INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS_MINUS_ONE: [GoldilocksField; 12] = const {
     let mut result = [GoldilocksField::ZERO; 12];
     let mut i = 0;
@@ -29,6 +41,7 @@ pub const INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS_MINUS_ONE: [GoldilocksField; 12]
     result
 };
 
+/// Diagonal of [INNER_ROUNDS_MATRIX]. All off-diagonal elements are one.
 pub const INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS: [GoldilocksField; 12] = const {
     let mut result = [GoldilocksField::ZERO; 12];
     let mut i = 0;
@@ -42,6 +55,8 @@ pub const INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS: [GoldilocksField; 12] = const {
     result
 };
 
+/// Matrix used in full rounds and before the first round. Block circulant
+/// `circ(2M, M, M)` for `M` = [EXTERNAL_MDS_MATRIX_BLOCK].
 pub const EXTERNAL_MDS_MATRIX: [[GoldilocksField; 12]; 12] = const {
     let mut result = [[GoldilocksField::ZERO; 12]; 12];
     let mut block_row = 0;
@@ -75,6 +90,8 @@ pub const EXTERNAL_MDS_MATRIX: [[GoldilocksField; 12]; 12] = const {
     result
 };
 
+/// Matrix used in partial rounds: all ones plus a diagonal of
+/// [INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS_MINUS_ONE].
 pub const INNER_ROUNDS_MATRIX: [[GoldilocksField; 12]; 12] = const {
     let mut result = [[GoldilocksField::ONE; 12]; 12];
     let mut i = 0;
@@ -86,6 +103,7 @@ pub const INNER_ROUNDS_MATRIX: [[GoldilocksField; 12]; 12] = const {
     result
 };
 
+/// Round constants of the full rounds, first and second half in order.
 pub const FULL_ROUND_CONSTANTS: [[GoldilocksField; STATE_WIDTH]; NUM_FULL_ROUNDS_TOTAL] = const {
     let mut constants_array = [[GoldilocksField::ZERO; STATE_WIDTH]; NUM_FULL_ROUNDS_TOTAL];
     let mut i = 0;
@@ -105,6 +123,7 @@ pub const FULL_ROUND_CONSTANTS: [[GoldilocksField; STATE_WIDTH]; NUM_FULL_ROUNDS
     constants_array
 };
 
+/// Round constants of the partial rounds, added to element 0 only.
 pub const PARTIAL_ROUND_CONSTANTS: [GoldilocksField; NUM_PARTIAL_ROUNDS] = const {
     let mut constants_array = [GoldilocksField::ZERO; NUM_PARTIAL_ROUNDS];
     let mut i = 0;
@@ -116,3 +135,70 @@ pub const PARTIAL_ROUND_CONSTANTS: [GoldilocksField; NUM_PARTIAL_ROUNDS] = const
     }
     constants_array
 };
+
+/// Round constants of every round in order, for the variant of the permutation that adds them in
+/// the linear layer of the previous round. Full rounds use the whole row, and partial rounds only
+/// its first element, so the rest is zeroed (the aligned table keeps the unused constants of the
+/// original Poseidon there).
+pub const FOLDED_ROUND_CONSTANTS: [[GoldilocksField; STATE_WIDTH]; TOTAL_NUM_ROUNDS] = const {
+    let mut constants_array =
+        poseidon_goldilocks_params::ROUND_CONSTANTS_ALIGNED_PER_ROUND_AS_FIELD_ELEMENTS;
+    let mut i = HALF_NUM_FULL_ROUNDS;
+    while i < HALF_NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS {
+        let mut j = 1;
+        while j < STATE_WIDTH {
+            constants_array[i][j] = GoldilocksField::ZERO;
+            j += 1;
+        }
+        i += 1;
+    }
+    constants_array
+};
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        field::rand_from_rng, implementations::poseidon2::reference::permutation_reference,
+    };
+
+    type F = GoldilocksField;
+
+    #[test]
+    fn test_permutation_from_params() {
+        let mut rng = rand::thread_rng();
+        for i in 0..12 {
+            let mut expected_diagonal = INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS_MINUS_ONE[i];
+            Field::add_assign(&mut expected_diagonal, &F::ONE);
+            assert_eq!(INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS[i], expected_diagonal);
+            assert_eq!(INNER_ROUNDS_MATRIX[i][i], expected_diagonal);
+        }
+
+        for _ in 0..16 {
+            let input: [F; 12] = std::array::from_fn(|_| rand_from_rng(&mut rng));
+
+            let mut state = input;
+            permutation_reference(&mut state);
+
+            let mut expected = input;
+            poseidon2_permutation(&mut expected);
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn test_folded_round_constants() {
+        for i in 0..HALF_NUM_FULL_ROUNDS {
+            assert_eq!(FOLDED_ROUND_CONSTANTS[i], FULL_ROUND_CONSTANTS[i]);
+            assert_eq!(
+                FOLDED_ROUND_CONSTANTS[HALF_NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS + i],
+                FULL_ROUND_CONSTANTS[HALF_NUM_FULL_ROUNDS + i]
+            );
+        }
+        for i in 0..NUM_PARTIAL_ROUNDS {
+            let mut expected = [F::ZERO; STATE_WIDTH];
+            expected[0] = PARTIAL_ROUND_CONSTANTS[i];
+            assert_eq!(FOLDED_ROUND_CONSTANTS[HALF_NUM_FULL_ROUNDS + i], expected);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Deliberately naive Poseidon2 permutation, written directly from the description in [params]
+//! with full matrix multiplications and `pow_u64` S-boxes, so the optimized implementations can be
+//! tested against something obviously correct. It's not meant to be fast.
+use super::{params::*, *};
+use crate::implementations::poseidon_goldilocks_params::{
+    HALF_NUM_FULL_ROUNDS, NUM_FULL_ROUNDS_TOTAL, NUM_PARTIAL_ROUNDS,
+};
+
+/// Same as `poseidon2_permutation`.
+pub fn permutation_reference(state: &mut [GoldilocksField; STATE_WIDTH]) {
+    *state = matrix_mul(&EXTERNAL_MDS_MATRIX, state);
+    for round in 0..HALF_NUM_FULL_ROUNDS {
+        full_round(state, round);
+    }
+    for round in 0..NUM_PARTIAL_ROUNDS {
+        partial_round(state, round);
+    }
+    for round in HALF_NUM_FULL_ROUNDS..NUM_FULL_ROUNDS_TOTAL {
+        full_round(state, round);
+    }
+}
+
+fn matrix_mul(
+    matrix: &[[GoldilocksField; STATE_WIDTH]; STATE_WIDTH],
+    state: &[GoldilocksField; STATE_WIDTH],
+) -> [GoldilocksField; STATE_WIDTH] {
+    let mut result = [GoldilocksField::ZERO; STATE_WIDTH];
+    for (dst, row) in result.iter_mut().zip(matrix.iter()) {
+        for (coeff, el) in row.iter().zip(state.iter()) {
+            let mut t = *coeff;
+            Field::mul_assign(&mut t, el);
+            Field::add_assign(dst, &t);
+        }
+    }
+
+    result
+}
+
+fn full_round(state: &mut [GoldilocksField; STATE_WIDTH], round: usize) {
+    for (el, constant) in state.iter_mut().zip(FULL_ROUND_CONSTANTS[round].iter()) {
+        Field::add_assign(el, constant);
+        *el = Field::pow_u64(el, 7);
+    }
+    *state = matrix_mul(&EXTERNAL_MDS_MATRIX, state);
+}
+
+fn partial_round(state: &mut [GoldilocksField; STATE_WIDTH], round: usize) {
+    Field::add_assign(&mut state[0], &PARTIAL_ROUND_CONSTANTS[round]);
+    state[0] = Field::pow_u64(&state[0], 7);
+    *state = matrix_mul(&INNER_ROUNDS_MATRIX, state);
+}
+
+#[cfg(test)]
+mod test {
+    use rand::Rng;
+
+    use super::*;
+    use crate::field::rand_from_rng;
+
+    #[test]
+    fn test_optimized_permutations_match_reference() {
+        let mut rng = rand::thread_rng();
+        let num_states = 4096;
+        let inputs: Vec<[GoldilocksField; STATE_WIDTH]> = (0..num_states)
+            .map(|_| {
+                std::array::from_fn(|_| {
+                    if rng.gen_bool(0.1) {
+                        // non-reduced one
+                        GoldilocksField(rng.gen_range(GoldilocksField::ORDER..=u64::MAX))
+                    } else {
+                        rand_from_rng(&mut rng)
+                    }
+                })
+            })
+            .collect();
+
+        let mut soa: Vec<GoldilocksField> = (0..STATE_WIDTH)
+            .flat_map(|i| inputs.iter().map(move |el| el[i]))
+            .collect();
+        Poseidon2Goldilocks::permute_batch_soa(&mut soa, num_states);
+
+        for (j, input) in inputs.iter().enumerate() {
+            let mut expected = *input;
+            permutation_reference(&mut expected);
+
+            let mut state = *input;
+            poseidon2_permutation(&mut state);
+            assert_eq!(state, expected, "invalid permutation of {:?}", input);
+
+            let mut state = *input;
+            state_generic_impl::poseidon2_permutation_folded(&mut state);
+            assert_eq!(state, expected, "invalid folded permutation of {:?}", input);
+
+            let state: [GoldilocksField; STATE_WIDTH] =
+                std::array::from_fn(|i| soa[i * num_states + j]);
+            assert_eq!(state, expected, "invalid batch permutation of {:?}", input);
+        }
+    }
+}
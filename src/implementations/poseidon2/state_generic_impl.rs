@@ -3,7 +3,7 @@ use std::usize;
 
 use unroll::unroll_for_loops;
 
-use super::{poseidon_goldilocks_params, suggested_mds};
+use super::{params, poseidon_goldilocks_params, suggested_mds};
 use crate::field::{goldilocks::GoldilocksField, traits::representation::U64Representable, Field};
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
@@ -65,6 +65,18 @@ impl State {
         unsafe { std::mem::transmute(Self::ALL_INNER_ROUND_CONSTANTS) }
     };
 
+    /// [`params::FOLDED_ROUND_CONSTANTS`] as states, for [`State::poseidon2_permutation_folded`].
+    pub const FOLDED_ROUND_CONSTANTS: [Self; Self::TOTAL_NUM_ROUNDS] = const {
+        let mut constants_array =
+            [Self([GoldilocksField::ZERO; Self::STATE_WIDTH]); Self::TOTAL_NUM_ROUNDS];
+        let mut i = 0;
+        while i < Self::TOTAL_NUM_ROUNDS {
+            constants_array[i] = Self(params::FOLDED_ROUND_CONSTANTS[i]);
+            i += 1;
+        }
+        constants_array
+    };
+
     pub const M_I_DIAGONAL_ELEMENTS_MINUS_ONE: Self = Self([
         GoldilocksField(1 << 4),
         GoldilocksField(1 << 14),
@@ -168,6 +180,34 @@ impl State {
     #[unroll_for_loops]
     #[inline(always)]
     pub fn m_i_mul(state: &mut [GoldilocksField; 12]) {
+        let rowwise_sum = Self::m_i_rowwise_sum(state);
+
+        for i in 0..12 {
+            state[i].mul_assign(&Self::M_I_DIAGONAL_ELEMENTS_MINUS_ONE.0[i]);
+        }
+
+        // and this is also well vectorizable later on
+        for i in 0..12 {
+            state[i].add_assign(&rowwise_sum);
+        }
+    }
+
+    /// Same as `m_i_mul`, but also adds `constants` to the result in the same pass.
+    #[unroll_for_loops]
+    #[inline(always)]
+    pub fn m_i_mul_add(state: &mut [GoldilocksField; 12], constants: &[GoldilocksField; 12]) {
+        let rowwise_sum = Self::m_i_rowwise_sum(state);
+
+        for i in 0..12 {
+            state[i]
+                .mul_assign(&Self::M_I_DIAGONAL_ELEMENTS_MINUS_ONE.0[i])
+                .add_assign(&rowwise_sum)
+                .add_assign(&constants[i]);
+        }
+    }
+
+    #[inline(always)]
+    fn m_i_rowwise_sum(state: &[GoldilocksField; 12]) -> GoldilocksField {
         let mut t0 = state[0];
         t0.add_assign(&state[1]);
         let mut t1 = state[2];
@@ -188,14 +228,7 @@ impl State {
         let mut rowwise_sum = t0;
         rowwise_sum.add_assign(&t2).add_assign(&t4);
 
-        for i in 0..12 {
-            state[i].mul_assign(&Self::M_I_DIAGONAL_ELEMENTS_MINUS_ONE.0[i]);
-        }
-
-        // and this is also well vectorizable later on
-        for i in 0..12 {
-            state[i].add_assign(&rowwise_sum);
-        }
+        rowwise_sum
     }
 
     #[inline(always)]
@@ -203,11 +236,7 @@ impl State {
         // add constant
         self.0[0].add_assign(&Self::ALL_INNER_ROUND_CONSTANTS_AS_FIELD_ELEMENTS[*round_counter]);
         // apply non-linearity to the single element
-        let mut t = self.0[0];
-        self.0[0].square();
-        t.mul_assign(&self.0[0]);
-        self.0[0].square();
-        self.0[0].mul_assign(&t);
+        self.apply_non_linearity_to_first();
 
         // multiply by MDS
         Self::m_i_mul(&mut self.0);
@@ -215,6 +244,15 @@ impl State {
         *round_counter += 1;
     }
 
+    #[inline(always)]
+    fn apply_non_linearity_to_first(&mut self) {
+        let mut t = self.0[0];
+        self.0[0].square();
+        t.mul_assign(&self.0[0]);
+        self.0[0].square();
+        self.0[0].mul_assign(&t);
+    }
+
     #[inline(always)]
     #[unroll_for_loops]
     pub fn poseidon2_permutation(&mut self) {
@@ -230,6 +268,48 @@ impl State {
             self.full_round(&mut round_counter);
         }
     }
+
+    /// Same permutation as [`State::poseidon2_permutation`], but the round constants are added
+    /// by the linear layer of the previous round in the same pass over the state, using
+    /// [`State::FOLDED_ROUND_CONSTANTS`], instead of a separate pass at the start of every round.
+    /// Between partial rounds only the first element gets a constant.
+    #[inline(always)]
+    #[unroll_for_loops]
+    pub fn poseidon2_permutation_folded(&mut self) {
+        // the initial linear layer adds the constants of the first round
+        suggested_mds::suggested_mds_mul_add(&mut self.0, &Self::FOLDED_ROUND_CONSTANTS[0].0);
+        for round in 0..3 {
+            self.apply_non_linearity();
+            suggested_mds::suggested_mds_mul_add(
+                &mut self.0,
+                &Self::FOLDED_ROUND_CONSTANTS[round + 1].0,
+            );
+        }
+        // the next round is partial
+        self.apply_non_linearity();
+        self.suggested_mds_mul();
+        self.0[0].add_assign(&Self::FOLDED_ROUND_CONSTANTS[4].0[0]);
+
+        for round in 4..25 {
+            self.apply_non_linearity_to_first();
+            Self::m_i_mul(&mut self.0);
+            self.0[0].add_assign(&Self::FOLDED_ROUND_CONSTANTS[round + 1].0[0]);
+        }
+        // the next round is full
+        self.apply_non_linearity_to_first();
+        Self::m_i_mul_add(&mut self.0, &Self::FOLDED_ROUND_CONSTANTS[26].0);
+
+        for round in 26..29 {
+            self.apply_non_linearity();
+            suggested_mds::suggested_mds_mul_add(
+                &mut self.0,
+                &Self::FOLDED_ROUND_CONSTANTS[round + 1].0,
+            );
+        }
+        // the last round has nothing to add
+        self.apply_non_linearity();
+        self.suggested_mds_mul();
+    }
 }
 
 impl Default for State {
@@ -241,7 +321,19 @@ impl Default for State {
 #[inline(always)]
 pub fn poseidon2_permutation(state: &mut [GoldilocksField; State::STATE_WIDTH]) {
     let mut state_vec = State::from_field_array(*state);
-    state_vec.poseidon2_permutation();
+    if cfg!(feature = "poseidon2_folded_constants") {
+        state_vec.poseidon2_permutation_folded();
+    } else {
+        state_vec.poseidon2_permutation();
+    }
+    *state = state_vec.as_field_array();
+}
+
+// for external benchmarks
+#[inline(never)]
+pub fn poseidon2_permutation_folded(state: &mut [GoldilocksField; State::STATE_WIDTH]) {
+    let mut state_vec = State::from_field_array(*state);
+    state_vec.poseidon2_permutation_folded();
     *state = state_vec.as_field_array();
 }
 
@@ -351,4 +443,32 @@ mod test {
 
         assert_eq!(state_ref.0, state_vec.0);
     }
+
+    #[test]
+    fn test_poseidon2_permutation_folded() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let state: [GoldilocksField; 12] = std::array::from_fn(|_| rand_from_rng(&mut rng));
+
+            let mut state_ref = State::from_field_array(state);
+            state_ref.poseidon2_permutation();
+            let mut state_folded = State::from_field_array(state);
+            state_folded.poseidon2_permutation_folded();
+
+            // bit-exact, including the representation
+            let raw = |state: State| state.0.map(|el| el.0);
+            assert_eq!(raw(state_ref), raw(state_folded));
+        }
+
+        for (i, row) in State::FOLDED_ROUND_CONSTANTS.iter().enumerate() {
+            let is_partial = (4..26).contains(&i);
+            for (j, el) in row.0.iter().enumerate() {
+                if is_partial && j > 0 {
+                    assert!(el.is_zero());
+                } else {
+                    assert_eq!(*el, State::ALL_ROUND_CONSTANTS[i].0[j]);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,228 @@
+//! Independent Poseidon2 sponges running in the lanes of `MixedGL` vectors, so
+//! `MixedGL::SIZE_FACTOR` messages of the same length are hashed at the cost of one vectorized
+//! permutation per block, e.g. for the leaves of a tree.
+use super::{
+    batch_soa::{permutation, VecState},
+    *,
+};
+use crate::{
+    field::{
+        goldilocks::MixedGL,
+        traits::field_like::{PrimeFieldLike, PrimeFieldLikeVectorized},
+    },
+    implementations::poseidon_goldilocks_params::{CAPACITY, RATE},
+};
+
+/// `MixedGL::SIZE_FACTOR` Poseidon2 sponge states, where lane `j` of `state[i]` is the element
+/// `i` of the sponge `j`. Every lane behaves exactly as [`Poseidon2Goldilocks`] used with
+/// `absorb_into_state` and `round_function` on its own state.
+#[derive(Clone, Copy, Debug)]
+pub struct Poseidon2Wide {
+    pub state: VecState,
+}
+
+impl Default for Poseidon2Wide {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Poseidon2Wide {
+    /// All the sponges in the initial (zero) state.
+    pub fn new() -> Self {
+        Self { state: [MixedGL::zero(&mut ()); STATE_WIDTH] }
+    }
+
+    /// Absorbs `blocks[j]` into the sponge `j` with the mode `M`, without permuting, so it's
+    /// usually followed by [`Poseidon2Wide::permute_wide`]. There must be exactly
+    /// `MixedGL::SIZE_FACTOR` blocks. The blocks are gathered into one vector per rate element,
+    /// that is absorbed with a single vectorized operation.
+    pub fn absorb_wide<M: AbsorptionModeTrait<GoldilocksField> + AbsorptionModeTrait<MixedGL>>(
+        &mut self,
+        blocks: &[[GoldilocksField; RATE]],
+    ) {
+        assert_eq!(blocks.len(), MixedGL::SIZE_FACTOR, "must absorb one block per lane");
+        for (i, dst) in self.state[..RATE].iter_mut().enumerate() {
+            let src = MixedGL::from_array(std::array::from_fn(|lane| blocks[lane][i]));
+            <M as AbsorptionModeTrait<MixedGL>>::absorb(dst, &src);
+        }
+    }
+
+    /// Applies the permutation to all the sponges at once.
+    #[inline]
+    pub fn permute_wide(&mut self) {
+        permutation(&mut self.state);
+    }
+
+    /// Commitments of all the sponges, one per lane.
+    pub fn squeeze_wide(&self) -> Vec<[GoldilocksField; CAPACITY]> {
+        (0..MixedGL::SIZE_FACTOR)
+            .map(|lane| std::array::from_fn(|i| self.state[i].0[lane]))
+            .collect()
+    }
+}
+
+impl MixedGL {
+    /// Cheap Poseidon2 commitment to `input`, e.g. to detect corruption of a buffer between
+    /// pipeline stages. Lane `j` of every vector is absorbed into the sponge `j` of a
+    /// [`Poseidon2Wide`], followed by the number of vectors, and the commitments of all the lanes
+    /// are hashed together. Lanes are reduced before absorption, so the result only depends on
+    /// the values and not on their representation.
+    pub fn checksum(input: &[Self]) -> [GoldilocksField; 4] {
+        let mut wide = Poseidon2Wide::new();
+        for chunk in input.chunks(RATE) {
+            for (dst, src) in wide.state[..RATE].iter_mut().zip(chunk.iter()) {
+                *dst = *src;
+                dst.to_reduced();
+            }
+            for dst in wide.state[chunk.len()..RATE].iter_mut() {
+                *dst = MixedGL::zero(&mut ());
+            }
+            wide.permute_wide();
+        }
+        // length block, so buffers that only differ by trailing zero vectors don't collide
+        wide.state[0] = MixedGL::constant(GoldilocksField(input.len() as u64), &mut ());
+        for dst in wide.state[1..RATE].iter_mut() {
+            *dst = MixedGL::zero(&mut ());
+        }
+        wide.permute_wide();
+
+        let commitments = wide.squeeze_wide();
+        let mut state = Poseidon2Goldilocks.initial_state();
+        absorb_into_state_vararg::<_, Poseidon2Goldilocks, AbsorptionModeOverwrite, 8, 12, 4>(
+            &mut state,
+            commitments.as_flattened(),
+        );
+
+        Poseidon2Goldilocks.state_into_commitment_fixed::<4>(&state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{algebraic_props::sponge::AlgebraicSponge, field::rand_from_rng};
+
+    #[test]
+    fn test_wide_sponge() {
+        let mut rng = rand::thread_rng();
+        let num_blocks = 5;
+        let messages: Vec<Vec<[GoldilocksField; RATE]>> = (0..MixedGL::SIZE_FACTOR)
+            .map(|_| {
+                (0..num_blocks)
+                    .map(|_| std::array::from_fn(|_| rand_from_rng(&mut rng)))
+                    .collect()
+            })
+            .collect();
+
+        let mut wide = Poseidon2Wide::new();
+        for block_idx in 0..num_blocks {
+            let blocks: Vec<[GoldilocksField; RATE]> =
+                messages.iter().map(|el| el[block_idx]).collect();
+            wide.absorb_wide::<AbsorptionModeOverwrite>(&blocks);
+            wide.permute_wide();
+        }
+        let commitments = wide.squeeze_wide();
+
+        for (lane, (message, commitment)) in messages.iter().zip(commitments.iter()).enumerate() {
+            let mut sponge = AlgebraicSponge::<GoldilocksField, 8, 12, 4, Poseidon2Goldilocks>::new(
+                Poseidon2Goldilocks,
+                AbsorptionMode::Overwrite,
+            );
+            sponge.absorb(message.as_flattened());
+            assert_eq!(*commitment, sponge.finalize::<4>(), "invalid lane {}", lane);
+        }
+
+        // addition mode, compared with the round function directly
+        let mut wide = Poseidon2Wide::new();
+        let mut expected = vec![Poseidon2Goldilocks.initial_state(); MixedGL::SIZE_FACTOR];
+        for block_idx in 0..num_blocks {
+            let blocks: Vec<[GoldilocksField; RATE]> =
+                messages.iter().map(|el| el[block_idx]).collect();
+            wide.absorb_wide::<AbsorptionModeAdd>(&blocks);
+            wide.permute_wide();
+            for (state, block) in expected.iter_mut().zip(blocks.iter()) {
+                Poseidon2Goldilocks.absorb_into_state(state, block, AbsorptionMode::Addition);
+                Poseidon2Goldilocks.round_function(state);
+            }
+        }
+        for (lane, state) in expected.iter().enumerate() {
+            let result: [GoldilocksField; STATE_WIDTH] =
+                std::array::from_fn(|i| wide.state[i].0[lane]);
+            assert_eq!(result, *state, "invalid lane {}", lane);
+        }
+    }
+
+    #[test]
+    fn test_absorb_wide_matches_scalar() {
+        fn check<M: AbsorptionModeTrait<GoldilocksField> + AbsorptionModeTrait<MixedGL>>() {
+            let mut rng = rand::thread_rng();
+            let mut wide = Poseidon2Wide::new();
+            for dst in wide.state.iter_mut() {
+                *dst = MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng)));
+            }
+            let blocks: Vec<[GoldilocksField; RATE]> = (0..MixedGL::SIZE_FACTOR)
+                .map(|_| std::array::from_fn(|_| rand_from_rng(&mut rng)))
+                .collect();
+
+            let mut expected = wide;
+            for (lane, block) in blocks.iter().enumerate() {
+                for (dst, src) in expected.state[..RATE].iter_mut().zip(block.iter()) {
+                    <M as AbsorptionModeTrait<GoldilocksField>>::absorb(&mut dst.0[lane], src);
+                }
+            }
+
+            wide.absorb_wide::<M>(&blocks);
+            for (i, (result, expected)) in wide.state.iter().zip(expected.state.iter()).enumerate()
+            {
+                assert_eq!(result.0, expected.0, "invalid element {}", i);
+            }
+        }
+
+        check::<AbsorptionModeOverwrite>();
+        check::<AbsorptionModeAdd>();
+        check::<AbsorptionModeSub>();
+    }
+
+    #[test]
+    fn test_checksum() {
+        let mut rng = rand::thread_rng();
+        for num_vectors in [0, 1, 5, RATE, 3 * RATE + 1] {
+            // lane 0 holds small values, so they also have a non-reduced representation
+            let input: Vec<MixedGL> = (0..num_vectors)
+                .map(|i| {
+                    let mut vector =
+                        MixedGL::from_array(std::array::from_fn(|_| rand_from_rng(&mut rng)));
+                    vector.0[0] = GoldilocksField(i as u64);
+                    vector
+                })
+                .collect();
+            let checksum = MixedGL::checksum(&input);
+            assert_eq!(MixedGL::checksum(&input), checksum);
+
+            let mut nonreduced = input.clone();
+            for vector in nonreduced.iter_mut() {
+                vector.0[0].0 += GoldilocksField::ORDER;
+            }
+            assert_eq!(MixedGL::checksum(&nonreduced), checksum);
+
+            for idx in 0..num_vectors {
+                for lane in [0, MixedGL::SIZE_FACTOR - 1] {
+                    let mut modified = input.clone();
+                    modified[idx].0[lane].add_assign(&GoldilocksField::ONE);
+                    assert_ne!(
+                        MixedGL::checksum(&modified),
+                        checksum,
+                        "collision for lane {} of vector {}",
+                        lane,
+                        idx
+                    );
+                }
+            }
+
+            let mut extended = input.clone();
+            extended.push(MixedGL::zero(&mut ()));
+            assert_ne!(MixedGL::checksum(&extended), checksum);
+        }
+    }
+}
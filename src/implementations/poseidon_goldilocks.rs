@@ -462,6 +462,13 @@ impl AlgebraicRoundFunctionWithParams<GoldilocksField, 8, 12, 4> for PoseidonGol
                     i += 1;
                 }
             }
+            AbsorptionMode::Subtraction => {
+                let mut i = 0;
+                while i < 8 {
+                    state[i].sub_assign(&to_absorb[i]);
+                    i += 1;
+                }
+            }
         }
     }
 
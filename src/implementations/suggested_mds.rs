@@ -108,6 +108,46 @@ pub(crate) fn suggested_mds_mul(state: &mut [GoldilocksField; 12]) {
     state[11].double().add_assign(&x3).add_assign(&x7);
 }
 
+/// Same as `suggested_mds_mul`, but also adds `constants` to the result in the same pass.
+#[unroll_for_loops]
+#[inline(always)]
+pub(crate) fn suggested_mds_mul_add(
+    state: &mut [GoldilocksField; 12],
+    constants: &[GoldilocksField; 12],
+) {
+    let [
+        mut x0,
+        mut x1,
+        mut x2,
+        mut x3,
+        mut x4,
+        mut x5,
+        mut x6,
+        mut x7,
+        mut x8,
+        mut x9,
+        mut x10,
+        mut x11,
+    ] = *state;
+
+    block_mul(&mut x0, &mut x1, &mut x2, &mut x3);
+    block_mul(&mut x4, &mut x5, &mut x6, &mut x7);
+    block_mul(&mut x8, &mut x9, &mut x10, &mut x11);
+
+    let x = [x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11];
+    for i in 0..12 {
+        // block `i / 4` is doubled, and the other two are added in the same order as in
+        // `suggested_mds_mul`, so the representation of the result is the same too
+        let (a, b) = ((i + 4) % 12, (i + 8) % 12);
+        state[i] = x[i];
+        state[i]
+            .double()
+            .add_assign(&x[std::cmp::min(a, b)])
+            .add_assign(&x[std::cmp::max(a, b)])
+            .add_assign(&constants[i]);
+    }
+}
+
 // for external benchmarks
 #[inline(never)]
 pub fn suggested_mds_mul_ext(state: &mut [GoldilocksField; 12]) {
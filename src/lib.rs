@@ -54,6 +54,7 @@
 #![feature(type_changing_struct_update)]
 
 pub mod algebraic_props;
+pub mod backend;
 pub mod config;
 pub mod cs;
 pub mod dag;
@@ -103,6 +103,48 @@ pub fn clone_respecting_allignment<T: Sized + Clone, U: Sized, A: GoodAllocator>
     result
 }
 
+/// Global allocator that aligns every allocation to at least 64 bytes, so a vector of any type
+/// allocated with it can be reinterpreted as a vector of `MixedGL` (and back) and still be freed
+/// with the layout it was allocated with.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Aligned64Allocator;
+
+impl Aligned64Allocator {
+    pub const ALIGNMENT: usize = 64;
+
+    #[inline(always)]
+    fn aligned_layout(layout: std::alloc::Layout) -> std::alloc::Layout {
+        layout
+            .align_to(Self::ALIGNMENT)
+            .expect("layout must be valid with 64 byte alignment")
+    }
+}
+
+unsafe impl std::alloc::Allocator for Aligned64Allocator {
+    #[inline]
+    fn allocate(
+        &self,
+        layout: std::alloc::Layout,
+    ) -> Result<std::ptr::NonNull<[u8]>, std::alloc::AllocError> {
+        std::alloc::Global.allocate(Self::aligned_layout(layout))
+    }
+
+    #[inline]
+    fn allocate_zeroed(
+        &self,
+        layout: std::alloc::Layout,
+    ) -> Result<std::ptr::NonNull<[u8]>, std::alloc::AllocError> {
+        std::alloc::Global.allocate_zeroed(Self::aligned_layout(layout))
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: std::ptr::NonNull<u8>, layout: std::alloc::Layout) {
+        std::alloc::Global.deallocate(ptr, Self::aligned_layout(layout))
+    }
+}
+
+impl GoodAllocator for Aligned64Allocator {}
+
 // Allocate a vector of type T, but with extra restriction that it has an alignment
 // of type U. Capacity should be divisible by size_of::<U>/size_of::<T>
 #[inline]
@@ -1,8 +1,18 @@
 //! Machinery for multi-threaded proving.
+//!
+//! With the `global_rayon_pool` feature the `Worker` doesn't own any threads and delegates all the
+//! work to the ambient rayon pool, so the whole process can share a single pool.
+//!
+//! Scopes can be nested, e.g. a task spawned in a scope may open another scope of the same
+//! `Worker`. A pool thread that waits for a scope to finish executes pending jobs of the pool
+//! in the meantime, so nested work makes progress even if every thread is already busy, and
+//! doesn't deadlock even with a single thread.
+#[cfg(not(feature = "global_rayon_pool"))]
 use rayon::{ThreadPool, ThreadPoolBuilder};
 
 // We allocate a pool of (ideally) high-performance cores only!
 pub struct Worker {
+    #[cfg(not(feature = "global_rayon_pool"))]
     pool: ThreadPool,
     pub num_cores: usize,
 }
@@ -11,6 +21,7 @@ pub struct Worker {
 // we enforce it
 pub const REQUIRED_STACK_SIZE: usize = 8 * 1024 * 1024;
 
+#[cfg(not(feature = "global_rayon_pool"))]
 impl Worker {
     pub fn new() -> Self {
         let num_cores = num_cpus::get_physical();
@@ -33,6 +44,95 @@ impl Worker {
         Self { pool, num_cores: num_threads }
     }
 
+    pub fn scope<'a, F, R>(&self, work_size: usize, f: F) -> R
+    where
+        F: FnOnce(&rayon::Scope<'a>, usize) -> R,
+    {
+        let chunk_size = self.get_chunk_size(work_size);
+
+        self.pool.in_place_scope(|scope| f(scope, chunk_size))
+    }
+
+    pub fn scope_with_num_chunks<'a, F, R>(&self, work_size: usize, f: F) -> R
+    where
+        F: FnOnce(&rayon::Scope<'a>, usize, usize) -> R,
+    {
+        let chunk_size = self.get_chunk_size(work_size);
+        let num_chunks = Self::compute_num_chunks(work_size, chunk_size);
+
+        self.pool
+            .in_place_scope(|scope| f(scope, chunk_size, num_chunks))
+    }
+
+    /// Runs `f`, that may spawn any number of independent tasks, and returns once all of them are
+    /// done. See [`TaskScope`].
+    pub fn task_scope<'scope, F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&TaskScope<'_, 'scope>) -> R,
+    {
+        self.pool.in_place_scope(|scope| f(&TaskScope { scope }))
+    }
+}
+
+#[cfg(feature = "global_rayon_pool")]
+impl Worker {
+    /// Splits the work by the number of threads in the ambient rayon pool.
+    pub fn new() -> Self {
+        Self { num_cores: rayon::current_num_threads() }
+    }
+
+    /// Only affects how the work is split, as the work itself is still done by the ambient rayon
+    /// pool.
+    pub fn new_with_num_threads(num_threads: usize) -> Self {
+        Self { num_cores: num_threads }
+    }
+
+    pub fn scope<'a, F, R>(&self, work_size: usize, f: F) -> R
+    where
+        F: FnOnce(&rayon::Scope<'a>, usize) -> R,
+    {
+        let chunk_size = self.get_chunk_size(work_size);
+
+        rayon::in_place_scope(|scope| f(scope, chunk_size))
+    }
+
+    pub fn scope_with_num_chunks<'a, F, R>(&self, work_size: usize, f: F) -> R
+    where
+        F: FnOnce(&rayon::Scope<'a>, usize, usize) -> R,
+    {
+        let chunk_size = self.get_chunk_size(work_size);
+        let num_chunks = Self::compute_num_chunks(work_size, chunk_size);
+
+        rayon::in_place_scope(|scope| f(scope, chunk_size, num_chunks))
+    }
+
+    /// Runs `f`, that may spawn any number of independent tasks, and returns once all of them are
+    /// done. See [`TaskScope`].
+    pub fn task_scope<'scope, F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&TaskScope<'_, 'scope>) -> R,
+    {
+        rayon::in_place_scope(|scope| f(&TaskScope { scope }))
+    }
+}
+
+/// Spawns heterogeneous tasks of [`Worker::task_scope`], e.g. FFTs of two different polynomials,
+/// instead of splitting a single piece of work into chunks. Tasks may borrow anything that
+/// outlives the scope, including disjoint mutable slices.
+pub struct TaskScope<'a, 'scope> {
+    scope: &'a rayon::Scope<'scope>,
+}
+
+impl<'a, 'scope> TaskScope<'a, 'scope> {
+    pub fn spawn<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'scope,
+    {
+        self.scope.spawn(move |_| f());
+    }
+}
+
+impl Worker {
     pub const fn compute_chunk_size(work_size: usize, num_chunks: usize) -> usize {
         if work_size <= num_chunks {
             1
@@ -62,24 +162,192 @@ impl Worker {
     pub const fn get_chunk_size(&self, work_size: usize) -> usize {
         Self::compute_chunk_size(work_size, self.num_cores)
     }
+}
 
-    pub fn scope<'a, F, R>(&self, work_size: usize, f: F) -> R
-    where
-        F: FnOnce(&rayon::Scope<'a>, usize) -> R,
-    {
-        let chunk_size = self.get_chunk_size(work_size);
+#[cfg(all(test, feature = "global_rayon_pool"))]
+mod test {
+    use std::{alloc::Global, sync::Mutex};
 
-        self.pool.in_place_scope(|scope| f(scope, chunk_size))
+    use rayon::ThreadPoolBuilder;
+
+    use super::*;
+    use crate::{
+        fft::fft_natural_to_bitreversed,
+        field::{
+            goldilocks::GoldilocksField, rand_from_rng,
+            traits::field_like::PrimeFieldLikeVectorized,
+        },
+    };
+
+    fn named_pool(num_threads: usize) -> rayon::ThreadPool {
+        ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|idx| format!("ambient-pool-{}", idx))
+            .build()
+            .unwrap()
     }
 
-    pub fn scope_with_num_chunks<'a, F, R>(&self, work_size: usize, f: F) -> R
-    where
-        F: FnOnce(&rayon::Scope<'a>, usize, usize) -> R,
-    {
-        let chunk_size = self.get_chunk_size(work_size);
-        let num_chunks = Self::compute_num_chunks(work_size, chunk_size);
+    #[test]
+    fn test_work_runs_on_ambient_pool() {
+        let pool = named_pool(4);
+        let thread_names = Mutex::new(vec![]);
 
-        self.pool
-            .in_place_scope(|scope| f(scope, chunk_size, num_chunks))
+        pool.install(|| {
+            let worker = Worker::new();
+            assert_eq!(worker.num_cores, 4);
+
+            let mut data = vec![0u64; 1 << 10];
+            worker.scope(data.len(), |scope, chunk_size| {
+                for (idx, chunk) in data.chunks_mut(chunk_size).enumerate() {
+                    let thread_names = &thread_names;
+                    scope.spawn(move |_| {
+                        chunk.iter_mut().for_each(|el| *el = idx as u64);
+                        let name = std::thread::current().name().map(|el| el.to_owned());
+                        thread_names.lock().unwrap().push(name);
+                    });
+                }
+            });
+        });
+
+        let thread_names = thread_names.into_inner().unwrap();
+        assert!(!thread_names.is_empty());
+        for name in thread_names {
+            assert!(name.unwrap().starts_with("ambient-pool-"));
+        }
+    }
+
+    #[test]
+    fn test_fft_on_ambient_pool() {
+        let mut rng = rand::thread_rng();
+        let poly_size = 1 << 12;
+        let original: Vec<GoldilocksField> =
+            (0..poly_size).map(|_| rand_from_rng(&mut rng)).collect();
+
+        let run = |pool: rayon::ThreadPool| {
+            pool.install(|| {
+                let worker = Worker::new();
+                let twiddles = GoldilocksField::precompute_forward_twiddles_for_fft::<Global>(
+                    poly_size,
+                    &worker,
+                    &mut (),
+                );
+                let mut result = original.clone();
+                fft_natural_to_bitreversed(&mut result, GoldilocksField(7), &twiddles);
+
+                result
+            })
+        };
+
+        assert_eq!(run(named_pool(1)), run(named_pool(4)));
+    }
+}
+
+#[cfg(test)]
+mod test_nested {
+    use std::{alloc::Global, sync::mpsc, time::Duration};
+
+    use super::*;
+    use crate::{
+        fft::fft_natural_to_bitreversed,
+        field::{
+            goldilocks::GoldilocksField, rand_from_rng,
+            traits::field_like::PrimeFieldLikeVectorized,
+        },
+    };
+
+    fn nested_fft(worker: &Worker, inputs: &mut [Vec<GoldilocksField>]) {
+        worker.scope(inputs.len(), |scope, chunk_size| {
+            for chunk in inputs.chunks_mut(chunk_size) {
+                scope.spawn(move |_| {
+                    for input in chunk.iter_mut() {
+                        // uses the same worker from one of its threads
+                        let twiddles = GoldilocksField::precompute_forward_twiddles_for_fft::<
+                            Global,
+                        >(input.len(), worker, &mut ());
+                        fft_natural_to_bitreversed(input, GoldilocksField::ONE, &twiddles);
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_nested_scopes() {
+        let mut rng = rand::thread_rng();
+        let poly_size = 1 << 10;
+        let original: Vec<Vec<GoldilocksField>> = (0..8)
+            .map(|_| (0..poly_size).map(|_| rand_from_rng(&mut rng)).collect())
+            .collect();
+
+        let reference_worker = Worker::new_with_num_threads(1);
+        let mut expected = original.clone();
+        for input in expected.iter_mut() {
+            let twiddles = GoldilocksField::precompute_forward_twiddles_for_fft::<Global>(
+                poly_size,
+                &reference_worker,
+                &mut (),
+            );
+            fft_natural_to_bitreversed(input, GoldilocksField::ONE, &twiddles);
+        }
+
+        for num_threads in [1, 2, 4] {
+            let (sender, receiver) = mpsc::channel();
+            let mut inputs = original.clone();
+            let mut deeper = original.clone();
+            std::thread::spawn(move || {
+                let worker = Worker::new_with_num_threads(num_threads);
+                nested_fft(&worker, &mut inputs);
+                // and one more level
+                worker.scope(1, |scope, _| {
+                    scope.spawn(|_| nested_fft(&worker, &mut deeper));
+                });
+                sender.send((inputs, deeper)).unwrap();
+            });
+
+            let (inputs, deeper) = receiver
+                .recv_timeout(Duration::from_secs(60))
+                .unwrap_or_else(|_| {
+                    panic!("nested scopes deadlocked with {} threads", num_threads)
+                });
+            assert_eq!(inputs, expected);
+            assert_eq!(deeper, expected);
+        }
+    }
+
+    #[test]
+    fn test_task_scope() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new_with_num_threads(2);
+        let original: Vec<Vec<GoldilocksField>> = [1 << 10, 1 << 8]
+            .into_iter()
+            .map(|poly_size| (0..poly_size).map(|_| rand_from_rng(&mut rng)).collect())
+            .collect();
+
+        let mut expected = original.clone();
+        for input in expected.iter_mut() {
+            let twiddles = GoldilocksField::precompute_forward_twiddles_for_fft::<Global>(
+                input.len(),
+                &worker,
+                &mut (),
+            );
+            fft_natural_to_bitreversed(input, GoldilocksField::ONE, &twiddles);
+        }
+
+        let mut inputs = original.clone();
+        let (first, second) = inputs.split_at_mut(1);
+        let worker = &worker;
+        worker.task_scope(|scope| {
+            for input in [&mut first[0], &mut second[0]] {
+                scope.spawn(move || {
+                    let twiddles = GoldilocksField::precompute_forward_twiddles_for_fft::<Global>(
+                        input.len(),
+                        worker,
+                        &mut (),
+                    );
+                    fft_natural_to_bitreversed(input, GoldilocksField::ONE, &twiddles);
+                });
+            }
+        });
+        assert_eq!(inputs, expected);
     }
 }